@@ -0,0 +1,13 @@
+/**
+ * This file was automatically generated by Stylus and represents a Rust program.
+ * For more information, please see [The Stylus SDK](https://github.com/OffchainLabs/stylus-sdk-rs).
+ */
+
+// SPDX-License-Identifier: MIT-OR-APACHE-2.0
+pragma solidity ^0.8.23;
+
+interface IGroth16Verifier {
+    function verify(bytes calldata proof, bytes32[] memory public_inputs) external view returns (bool);
+
+    function vkHash() external view returns (bytes32);
+}
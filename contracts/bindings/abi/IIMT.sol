@@ -0,0 +1,23 @@
+/**
+ * This file was automatically generated by Stylus and represents a Rust program.
+ * For more information, please see [The Stylus SDK](https://github.com/OffchainLabs/stylus-sdk-rs).
+ */
+
+// SPDX-License-Identifier: MIT-OR-APACHE-2.0
+pragma solidity ^0.8.23;
+
+interface IIMT {
+    function insert(bytes32 leaf) external returns (uint32);
+
+    function isKnownRoot(bytes32 root) external view returns (bool);
+
+    function zeros(uint256 i) external view returns (bytes32);
+
+    function getDepth() external view returns (uint32);
+
+    function getCurrentRootIndex() external view returns (uint32);
+
+    function getNextLeafIndex() external view returns (uint32);
+
+    function getRootFromRootIndex(uint32 root_index) external view returns (bytes32);
+}
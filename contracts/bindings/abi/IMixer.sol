@@ -0,0 +1,57 @@
+/**
+ * This file was automatically generated by Stylus and represents a Rust program.
+ * For more information, please see [The Stylus SDK](https://github.com/OffchainLabs/stylus-sdk-rs).
+ */
+
+// SPDX-License-Identifier: MIT-OR-APACHE-2.0
+pragma solidity ^0.8.23;
+
+interface IMixer {
+    function registerVerifier(uint32 version, address verifier, uint32 proof_size, bytes32 vk_hash) external;
+
+    function deprecateVerifier(uint32 version) external;
+
+    function setBatchVerifier(uint32 version, address verifier, uint32 proof_size, bytes32 vk_hash, bytes32 key_hash) external;
+
+    function deposit(bytes32 commitment) external payable;
+
+    function withdraw(bytes calldata proof, bytes32 root, bytes32 nullifier_hash, address recipient, uint32 circuit_version) external;
+
+    function batchWithdraw(bytes calldata aggregated_proof, WithdrawRequest[] memory requests) external;
+
+    function admin() external view returns (address);
+
+    function imt() external view returns (address);
+
+    function isSpent(bytes32 nullifier_hash) external view returns (bool);
+
+    function circuitDepth() external view returns (uint32);
+
+    function denomination() external view returns (uint256);
+
+    function batchSize() external view returns (uint32);
+
+    function batchVerifier() external view returns (address);
+
+    function batchVkHash() external view returns (bytes32);
+
+    function batchKeyHash() external view returns (bytes32);
+
+    function batchProofSize() external view returns (uint32);
+
+    function batchCircuitVersion() external view returns (uint32);
+
+    function verifier(uint32 version) external view returns (address);
+
+    function proofSize(uint32 version) external view returns (uint32);
+
+    function vkHash(uint32 version) external view returns (bytes32);
+
+    function isDeprecated(uint32 version) external view returns (bool);
+
+    struct WithdrawRequest {
+        bytes32 root;
+        bytes32 nullifier_hash;
+        address recipient;
+    }
+}
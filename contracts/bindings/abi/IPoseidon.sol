@@ -0,0 +1,17 @@
+/**
+ * This file was automatically generated by Stylus and represents a Rust program.
+ * For more information, please see [The Stylus SDK](https://github.com/OffchainLabs/stylus-sdk-rs).
+ */
+
+// SPDX-License-Identifier: MIT-OR-APACHE-2.0
+pragma solidity ^0.8.23;
+
+interface IPoseidon {
+    function hash(uint256[2] memory inputs) external view returns (uint256);
+
+    function hashSingle(uint256 input) external view returns (uint256);
+
+    function hashWithDomain(uint64 domain, uint256[] memory inputs) external view returns (uint256);
+
+    function hashBytes(bytes calldata data) external view returns (uint256);
+}
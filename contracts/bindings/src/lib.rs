@@ -0,0 +1,59 @@
+//! alloy bindings for the mixer's contracts, generated from `cargo stylus export-abi` output.
+//!
+//! `abi/` holds the exported interfaces, `scripts/export_abi.sh` rewrites them. Events and errors
+//! are not part of the export, they are read from the `.sol` files the contracts declare them in.
+
+pub mod mixer {
+    alloy::sol!(
+        #[sol(rpc, all_derives)]
+        "abi/IMixer.sol"
+    );
+    alloy::sol!(
+        #[sol(all_derives)]
+        "../mixer/src/events.sol"
+    );
+    alloy::sol!(
+        #[sol(all_derives)]
+        "../common/src/errors/mixer.sol"
+    );
+}
+
+pub mod imt {
+    alloy::sol!(
+        #[sol(rpc, all_derives)]
+        "abi/IIMT.sol"
+    );
+    alloy::sol!(
+        #[sol(all_derives)]
+        "../common/src/errors/imt.sol"
+    );
+}
+
+pub mod poseidon {
+    alloy::sol!(
+        #[sol(rpc, all_derives)]
+        "abi/IPoseidon.sol"
+    );
+}
+
+pub mod groth16 {
+    alloy::sol!(
+        #[sol(rpc, all_derives)]
+        "abi/IGroth16Verifier.sol"
+    );
+    alloy::sol!(
+        #[sol(all_derives)]
+        "../common/src/errors/groth16.sol"
+    );
+}
+
+pub mod honk {
+    alloy::sol!(
+        #[sol(rpc, all_derives)]
+        "abi/IHonkVerifier.sol"
+    );
+    alloy::sol!(
+        #[sol(all_derives)]
+        "../common/src/errors/honk.sol"
+    );
+}
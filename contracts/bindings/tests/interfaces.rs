@@ -0,0 +1,84 @@
+//! The contracts call each other through `sol_interface!` blocks, which cannot read `abi/`.
+//! These tests keep them in step with the exported interfaces.
+
+use alloy::primitives::keccak256;
+use stylus_mixer_bindings::{
+    groth16::IGroth16Verifier, honk::IHonkVerifier, imt::IIMT, mixer::IMixer,
+};
+
+const MIXER_INTERFACES: &str = include_str!("../../mixer/src/interface.rs");
+const IMT_INTERFACES: &str = include_str!("../../imt/src/interface.rs");
+
+/* canonical signatures of the `function` lines in `interface <name> { ... }` */
+fn signatures(source: &str, interface: &str) -> Vec<String> {
+    let start = source
+        .find(&format!("interface {interface} {{"))
+        .unwrap_or_else(|| panic!("no interface {interface}"));
+    let body = &source[start..];
+    let body = &body[..body.find('}').unwrap()];
+
+    body.lines()
+        .filter_map(|line| line.trim().strip_prefix("function "))
+        .map(|function| {
+            let (name, rest) = function.split_once('(').unwrap();
+            let (params, _) = rest.split_once(')').unwrap();
+            let types: Vec<&str> = params
+                .split(',')
+                .filter_map(|param| param.split_whitespace().next())
+                .collect();
+            format!("{name}({})", types.join(","))
+        })
+        .collect()
+}
+
+fn assert_exported(source: &str, interface: &str, exported: &[[u8; 4]]) {
+    let signatures = signatures(source, interface);
+    assert!(!signatures.is_empty());
+    for signature in signatures {
+        let selector: [u8; 4] = keccak256(signature.as_bytes())[..4].try_into().unwrap();
+        assert!(
+            exported.contains(&selector),
+            "{interface}::{signature} is not in the exported ABI"
+        );
+    }
+}
+
+#[test]
+fn mixer_interface_matches_export() {
+    assert_exported(
+        MIXER_INTERFACES,
+        "MixerInterface",
+        IMixer::IMixerCalls::SELECTORS,
+    );
+}
+
+#[test]
+fn imt_interface_matches_export() {
+    assert_exported(IMT_INTERFACES, "IMTInterface", IIMT::IIMTCalls::SELECTORS);
+}
+
+#[test]
+fn groth16_verifier_fits_the_mixer() {
+    assert_exported(
+        MIXER_INTERFACES,
+        "VerifierInterface",
+        IGroth16Verifier::IGroth16VerifierCalls::SELECTORS,
+    );
+}
+
+#[test]
+fn honk_verifier_fits_the_mixer() {
+    assert_exported(
+        MIXER_INTERFACES,
+        "VerifierInterface",
+        IHonkVerifier::IHonkVerifierCalls::SELECTORS,
+    );
+}
+
+#[test]
+fn signatures_are_canonical() {
+    assert_eq!(
+        signatures(MIXER_INTERFACES, "VerifierInterface"),
+        ["verify(bytes,bytes32[])", "vkHash()"]
+    );
+}
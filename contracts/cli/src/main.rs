@@ -0,0 +1,397 @@
+use std::{io::IsTerminal, path::PathBuf};
+
+use alloy::{
+    network::EthereumWallet,
+    primitives::{uint, Address, FixedBytes, U256},
+    providers::{Provider, ProviderBuilder},
+    signers::local::PrivateKeySigner,
+    transports::http::reqwest::Url,
+};
+use clap::{Parser, Subcommand};
+use eyre::{ensure, eyre, Result, WrapErr};
+use stylus_common::public_inputs::WithdrawPublicInputs;
+use stylus_mixer_bindings::mixer::{self, IMixer};
+use stylus_mixer_client::{Deployment, Note};
+use stylus_mixer_indexer::{IndexedTree, Indexer};
+use stylus_mixer_wallet::{NoteStatus, NoteWallet};
+use zeroize::Zeroizing;
+
+mod prove;
+mod wallet;
+
+use prove::ProofFile;
+use wallet::{NoteRef, WalletFile};
+
+/* 1 ether, `deposit` checks it against the pool's `denomination()` */
+const DEFAULT_DENOMINATION: U256 = uint!(1_000_000_000_000_000_000_U256);
+const DEFAULT_CIRCUIT_VERSION: u32 = 1;
+/* never a flag, like the wallet password */
+const PRIVATE_KEY_ENV: &str = "PRIVATE_KEY";
+
+#[derive(Parser)]
+#[command(
+    name = "mixer-cli",
+    about = "Deposit into and withdraw from a Stylus mixer",
+    after_help = "`deposit` and `withdraw` sign with the key in PRIVATE_KEY, or ask for it on the terminal."
+)]
+struct Cli {
+    /// JSON-RPC endpoint, a nitro dev node by default.
+    #[arg(long, env = "RPC_URL", default_value = "http://localhost:8547", global = true)]
+    rpc_url: Url,
+
+    /// Encrypted note wallet, created by the first command that stores a note. Its password is
+    /// read from MIXER_WALLET_PASSWORD, or asked for on the terminal.
+    #[arg(long, env = "MIXER_WALLET", default_value = "wallet.json", global = true)]
+    wallet: PathBuf,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    #[command(subcommand)]
+    Note(NoteCommand),
+
+    /// Deposit a note into the pool it was made for, and record the deposit in the wallet.
+    Deposit {
+        /// A note string, stored in the wallet first, or the commitment of a stored note.
+        #[arg(long)]
+        note: NoteRef,
+    },
+
+    /// Bring the pool's tree cache up to date with its `Deposit` logs.
+    Sync {
+        #[arg(long)]
+        pool: Address,
+        /// Block the pool was deployed in, only read when the cache is created.
+        #[arg(long, default_value_t = 0)]
+        from_block: u64,
+        #[arg(long, default_value = "deposits.json")]
+        cache: PathBuf,
+    },
+
+    /// Prove a withdrawal of `note` to `recipient` with nargo and bb.
+    Prove {
+        /// A note string, or the commitment of a note in the wallet.
+        #[arg(long)]
+        note: NoteRef,
+        #[arg(long)]
+        recipient: Address,
+        /// Tree cache written by `sync`.
+        #[arg(long, default_value = "deposits.json")]
+        cache: PathBuf,
+        /// The withdraw circuit's Noir package.
+        #[arg(long, default_value = "circuits")]
+        circuit: PathBuf,
+        #[arg(long, default_value = "proof.json")]
+        out: PathBuf,
+    },
+
+    /// Submit a proof from `prove`, the pool and recipient are read from its public inputs.
+    Withdraw {
+        #[arg(long, default_value = "proof.json")]
+        proof: PathBuf,
+        #[arg(long, default_value_t = DEFAULT_CIRCUIT_VERSION)]
+        circuit_version: u32,
+    },
+}
+
+#[derive(Subcommand)]
+enum NoteCommand {
+    /// Store a fresh note for `pool` in the wallet and print it.
+    New {
+        #[arg(long)]
+        pool: Address,
+        #[arg(long, default_value_t = DEFAULT_DENOMINATION)]
+        denomination: U256,
+        /// Skips asking the RPC endpoint for it.
+        #[arg(long)]
+        chain_id: Option<u64>,
+    },
+
+    /// Store note strings given as arguments or one per line in `file`.
+    Import {
+        notes: Vec<String>,
+        #[arg(long)]
+        file: Option<PathBuf>,
+    },
+
+    /// Print stored notes as note strings, all of them unless `commitment` is given.
+    Export { commitment: Option<FixedBytes<32>> },
+
+    /// List stored notes with their deposit and spent status.
+    List,
+
+    /// Ask each pool which stored notes have been withdrawn and mark them spent.
+    Refresh,
+}
+
+/// The key in `PRIVATE_KEY` if it is set, otherwise from a prompt.
+fn signer(command: &str) -> Result<PrivateKeySigner> {
+    let key = match std::env::var(PRIVATE_KEY_ENV) {
+        Ok(key) => Zeroizing::new(key),
+        Err(_) => {
+            ensure!(
+                std::io::stdin().is_terminal(),
+                "{command} needs {PRIVATE_KEY_ENV}, or a terminal to ask for the key"
+            );
+            rpassword::prompt_password("private key: ")
+                .map(Zeroizing::new)
+                .wrap_err("cannot read the private key")?
+        }
+    };
+    key.trim()
+        .parse()
+        .map_err(|_| eyre!("{PRIVATE_KEY_ENV} is not a private key"))
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let wallet_file = WalletFile::new(cli.wallet);
+
+    match cli.command {
+        Command::Note(NoteCommand::New {
+            pool,
+            denomination,
+            chain_id,
+        }) => {
+            let chain_id = match chain_id {
+                Some(chain_id) => chain_id,
+                None => read_chain_id(&cli.rpc_url).await?,
+            };
+            let deployment = Deployment::eth(denomination, chain_id, pool);
+            let note = Note::random(deployment, &mut rand::thread_rng());
+
+            let mut wallet = wallet_file.open_or_create()?;
+            wallet.add(note.clone())?;
+            wallet.save()?;
+            println!("{note}");
+        }
+        Command::Note(NoteCommand::Import { notes, file }) => {
+            let mut text = notes.join("\n");
+            if let Some(file) = file {
+                text.push('\n');
+                text.push_str(
+                    &std::fs::read_to_string(&file)
+                        .wrap_err_with(|| format!("cannot read {}", file.display()))?,
+                );
+            }
+
+            let mut wallet = wallet_file.open_or_create()?;
+            let added = wallet.import(&text)?;
+            wallet.save()?;
+            println!("imported {} new notes", added.len());
+        }
+        Command::Note(NoteCommand::Export { commitment }) => {
+            let wallet = wallet_file.open()?;
+            match commitment {
+                Some(commitment) => {
+                    let stored = wallet
+                        .get(commitment)
+                        .ok_or_else(|| eyre!("no stored note has commitment {commitment}"))?;
+                    println!("{}", stored.note());
+                }
+                None => print!("{}", wallet.export()),
+            }
+        }
+        Command::Note(NoteCommand::List) => {
+            for stored in wallet_file.open()?.notes() {
+                let deployment = stored.note().deployment();
+                let leaf = stored
+                    .leaf_index()
+                    .map_or("-".to_string(), |index| index.to_string());
+                print!(
+                    "{} {:<9} leaf {leaf:<6} {} wei, chain {}, pool {}",
+                    stored.commitment(),
+                    stored.status(),
+                    deployment.denomination,
+                    deployment.chain_id,
+                    deployment.pool
+                );
+                match stored.deposit_tx() {
+                    Some(tx) => println!(", deposit {tx}"),
+                    None => println!(),
+                }
+            }
+        }
+        Command::Note(NoteCommand::Refresh) => {
+            let mut wallet = wallet_file.open()?;
+            let provider = ProviderBuilder::new().on_http(cli.rpc_url);
+            let spent = wallet.refresh_spent(&provider).await?;
+            wallet.save()?;
+            for commitment in &spent {
+                println!("spent {commitment}");
+            }
+            println!("{} notes newly spent", spent.len());
+        }
+        Command::Deposit { note } => {
+            let signer = signer("deposit")?;
+            let mut wallet = wallet_file.open_or_create()?;
+            let note = wallet::store(&mut wallet, note)?;
+            deposit(cli.rpc_url, signer, &mut wallet, note).await?;
+        }
+        Command::Sync {
+            pool,
+            from_block,
+            cache,
+        } => {
+            let provider = ProviderBuilder::new().on_http(cli.rpc_url);
+            let mut indexer = Indexer::open(provider, pool, from_block, &cache).await?;
+            let report = indexer.sync().await?;
+            if report.reorged_deposits > 0 {
+                println!("dropped {} reorged deposits", report.reorged_deposits);
+            }
+            println!(
+                "{} deposits ({} new) up to block {}, root {}",
+                indexer.tree().deposits().len(),
+                report.new_deposits,
+                report.synced_to.number,
+                indexer.tree().tree().root()
+            );
+        }
+        Command::Prove {
+            note,
+            recipient,
+            cache,
+            circuit,
+            out,
+        } => {
+            let note = wallet_file.resolve(note)?;
+            let tree = IndexedTree::load(&cache)
+                .wrap_err_with(|| format!("cannot load {}, run `sync` first", cache.display()))?;
+            note.check_deployment(&Deployment::eth(
+                note.deployment().denomination,
+                tree.chain_id(),
+                tree.pool(),
+            ))?;
+            let proof = prove::prove(&note, recipient, &tree, &circuit)?;
+            proof.write(&out)?;
+            println!("proof for root {} written to {}", proof.public_inputs[0], out.display());
+        }
+        Command::Withdraw {
+            proof,
+            circuit_version,
+        } => {
+            let signer = signer("withdraw")?;
+            let proof = ProofFile::read(&proof)?;
+            let public = withdraw(cli.rpc_url, signer, proof, circuit_version).await?;
+            if wallet_file.exists() {
+                let mut wallet = wallet_file.open()?;
+                if wallet.mark_spent(public.nullifier_hash) {
+                    wallet.save()?;
+                    println!("marked the note spent in {}", wallet.path().display());
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+async fn read_chain_id(rpc_url: &Url) -> Result<u64> {
+    let provider = ProviderBuilder::new().on_http(rpc_url.clone());
+    provider
+        .get_chain_id()
+        .await
+        .wrap_err_with(|| format!("cannot reach {rpc_url}"))
+}
+
+async fn deposit(
+    rpc_url: Url,
+    signer: PrivateKeySigner,
+    wallet: &mut NoteWallet,
+    note: Note,
+) -> Result<()> {
+    /* the same commitment twice is a second leaf that no nullifier can ever withdraw */
+    if let Some(stored) = wallet.get(note.commitment()) {
+        ensure!(
+            stored.status() == NoteStatus::New,
+            "note {} is already {}",
+            note.commitment(),
+            stored.status()
+        );
+    }
+    let chain_id = read_chain_id(&rpc_url).await?;
+    ensure!(
+        note.deployment().chain_id == chain_id,
+        "note is for chain {}, {rpc_url} is chain {chain_id}",
+        note.deployment().chain_id
+    );
+
+    let provider = ProviderBuilder::new()
+        .with_recommended_fillers()
+        .wallet(EthereumWallet::from(signer))
+        .on_http(rpc_url);
+    let mixer = IMixer::new(note.deployment().pool, &provider);
+
+    let IMixer::denominationReturn { _0: amount } = mixer.denomination().call().await?;
+    ensure!(
+        note.deployment().denomination == amount,
+        "note is for {} wei, the pool takes {amount} wei",
+        note.deployment().denomination
+    );
+
+    let receipt = mixer
+        .deposit(note.commitment())
+        .value(note.deployment().denomination)
+        .send()
+        .await?
+        .get_receipt()
+        .await?;
+    ensure!(receipt.status(), "deposit {} reverted", receipt.transaction_hash);
+
+    let index = receipt
+        .inner
+        .logs()
+        .iter()
+        .find_map(|log| log.log_decode::<mixer::Deposit>().ok())
+        .map(|log| log.inner.data.index)
+        .ok_or_else(|| eyre!("deposit {} emitted no Deposit event", receipt.transaction_hash))?;
+    println!("deposited leaf {index} in {}", receipt.transaction_hash);
+
+    wallet.record_deposit(note.commitment(), receipt.transaction_hash, index)?;
+    wallet.save()?;
+
+    Ok(())
+}
+
+async fn withdraw(
+    rpc_url: Url,
+    signer: PrivateKeySigner,
+    proof: ProofFile,
+    circuit_version: u32,
+) -> Result<WithdrawPublicInputs> {
+    let public = WithdrawPublicInputs::from_verifier_inputs(&proof.public_inputs)
+        .ok_or_else(|| eyre!("proof does not carry withdraw circuit public inputs"))?;
+    let chain_id = read_chain_id(&rpc_url).await?;
+    ensure!(
+        public.chain_id == chain_id,
+        "proof is for chain {}, {rpc_url} is chain {chain_id}",
+        public.chain_id
+    );
+
+    let provider = ProviderBuilder::new()
+        .with_recommended_fillers()
+        .wallet(EthereumWallet::from(signer))
+        .on_http(rpc_url);
+    let mixer = IMixer::new(public.pool, &provider);
+
+    let receipt = mixer
+        .withdraw(
+            proof.proof,
+            public.root,
+            public.nullifier_hash,
+            public.recipient,
+            circuit_version,
+        )
+        .send()
+        .await?
+        .get_receipt()
+        .await?;
+    ensure!(receipt.status(), "withdrawal {} reverted", receipt.transaction_hash);
+    println!("withdrew to {} in {}", public.recipient, receipt.transaction_hash);
+
+    Ok(public)
+}
@@ -0,0 +1,124 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use alloy::primitives::{Address, Bytes, FixedBytes};
+use eyre::{bail, ensure, Result, WrapErr};
+use serde::{Deserialize, Serialize};
+use stylus_mixer_client::{Note, WithdrawInputs};
+use stylus_mixer_indexer::IndexedTree;
+
+/// Same shape as the output of `scripts/js/generateProof.ts`.
+#[derive(Serialize, Deserialize)]
+pub struct ProofFile {
+    pub proof: Bytes,
+    #[serde(rename = "publicInputs")]
+    pub public_inputs: Vec<FixedBytes<32>>,
+}
+
+impl ProofFile {
+    pub fn read(path: &Path) -> Result<Self> {
+        let json = fs::read_to_string(path)
+            .wrap_err_with(|| format!("cannot read {}, run `prove` first", path.display()))?;
+        serde_json::from_str(&json).wrap_err_with(|| format!("malformed {}", path.display()))
+    }
+
+    pub fn write(&self, path: &Path) -> Result<()> {
+        fs::write(path, serde_json::to_string_pretty(self)?)
+            .wrap_err_with(|| format!("cannot write {}", path.display()))
+    }
+}
+
+/*
+ * proves in a scratch copy of the circuit package with DEPTH set to the pool's depth,
+ * the same rewrite scripts/build_circuits.sh does for the verifier artifacts
+ */
+pub fn prove(
+    note: &Note,
+    recipient: Address,
+    tree: &IndexedTree,
+    circuit: &Path,
+) -> Result<ProofFile> {
+    let inputs = WithdrawInputs::from_tree(note, recipient, tree.tree())?;
+
+    let package = scratch_package(circuit, tree.depth())?;
+    let result = (|| {
+        inputs.write_prover_toml(&package)?;
+        run(Command::new("nargo").arg("execute").current_dir(&package))?;
+        run(Command::new("bb")
+            .args(["prove", "--oracle_hash", "keccak"])
+            .args(["-b", "target/circuits.json", "-w", "target/circuits.gz", "-o", "target"])
+            .current_dir(&package))?;
+
+        let proof = fs::read(package.join("target/proof"))?;
+        let public_inputs = fs::read(package.join("target/public_inputs"))?;
+        Ok::<_, eyre::Report>((proof, public_inputs))
+    })();
+    let _ = fs::remove_dir_all(&package);
+    let (proof, public_inputs) = result?;
+
+    let public_inputs: Vec<FixedBytes<32>> =
+        public_inputs.chunks(32).map(FixedBytes::from_slice).collect();
+    ensure!(
+        public_inputs == inputs.public.to_verifier_inputs(),
+        "bb proved different public inputs than the ones written to Prover.toml"
+    );
+
+    Ok(ProofFile {
+        proof: proof.into(),
+        public_inputs,
+    })
+}
+
+fn scratch_package(circuit: &Path, depth: u32) -> Result<PathBuf> {
+    let package = std::env::temp_dir().join(format!("mixer-cli-{}", std::process::id()));
+    fs::create_dir_all(package.join("src"))?;
+    fs::copy(circuit.join("Nargo.toml"), package.join("Nargo.toml"))
+        .wrap_err_with(|| format!("{} is not a Noir package", circuit.display()))?;
+
+    for entry in fs::read_dir(circuit.join("src"))? {
+        let entry = entry?;
+        let source = fs::read_to_string(entry.path())?;
+        let source = if entry.file_name() == "main.nr" {
+            set_depth(&source, depth)?
+        } else {
+            source
+        };
+        fs::write(package.join("src").join(entry.file_name()), source)?;
+    }
+
+    Ok(package)
+}
+
+fn set_depth(main: &str, depth: u32) -> Result<String> {
+    let mut found = false;
+    let lines: Vec<String> = main
+        .lines()
+        .map(|line| {
+            if line.starts_with("global DEPTH: u32 =") {
+                found = true;
+                format!("global DEPTH: u32 = {depth};")
+            } else {
+                line.to_string()
+            }
+        })
+        .collect();
+    ensure!(found, "main.nr declares no `global DEPTH`");
+    Ok(lines.join("\n") + "\n")
+}
+
+fn run(command: &mut Command) -> Result<()> {
+    let output = command
+        .output()
+        .wrap_err_with(|| format!("cannot run {:?}, is it installed?", command.get_program()))?;
+    if !output.status.success() {
+        bail!(
+            "{:?} failed:\n{}",
+            command.get_program(),
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,120 @@
+use std::{path::PathBuf, str::FromStr};
+
+use alloy::primitives::FixedBytes;
+use eyre::{ensure, eyre, Result, WrapErr};
+use stylus_mixer_client::{Note, NoteError};
+use stylus_mixer_wallet::{KdfParams, NoteWallet};
+use zeroize::Zeroizing;
+
+/* never a flag, command lines end up in shell history and `ps` */
+const PASSWORD_ENV: &str = "MIXER_WALLET_PASSWORD";
+
+/// A portable note string, or the commitment of a note in the wallet.
+#[derive(Clone)]
+pub enum NoteRef {
+    Note(Note),
+    Commitment(FixedBytes<32>),
+}
+
+impl FromStr for NoteRef {
+    type Err = NoteError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().parse() {
+            Ok(commitment) => Ok(Self::Commitment(commitment)),
+            Err(_) => s.parse().map(Self::Note),
+        }
+    }
+}
+
+/// Where the wallet lives and how to unlock it, opened only by commands that need it.
+pub struct WalletFile {
+    path: PathBuf,
+    password: Option<Zeroizing<String>>,
+}
+
+impl WalletFile {
+    /// The password comes from `MIXER_WALLET_PASSWORD` if it is set, otherwise from a prompt.
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            password: std::env::var(PASSWORD_ENV).ok().map(Zeroizing::new),
+        }
+    }
+
+    pub fn exists(&self) -> bool {
+        self.path.exists()
+    }
+
+    pub fn open(&self) -> Result<NoteWallet> {
+        ensure!(
+            self.exists(),
+            "no wallet at {}, `note new` or `note import` creates one",
+            self.path.display()
+        );
+        let password = match &self.password {
+            Some(password) => password.clone(),
+            None => prompt(&format!("password for {}: ", self.path.display()))?,
+        };
+        NoteWallet::open(&self.path, &password)
+            .wrap_err_with(|| format!("cannot open {}", self.path.display()))
+    }
+
+    pub fn open_or_create(&self) -> Result<NoteWallet> {
+        if self.exists() {
+            return self.open();
+        }
+        let password = match &self.password {
+            Some(password) => password.clone(),
+            None => {
+                let password = prompt(&format!("new password for {}: ", self.path.display()))?;
+                ensure!(
+                    *password == *prompt("repeat it: ")?,
+                    "passwords do not match"
+                );
+                password
+            }
+        };
+        ensure!(!password.is_empty(), "wallet password is empty");
+
+        let wallet = NoteWallet::create(&self.path, &password, KdfParams::default())
+            .wrap_err_with(|| format!("cannot create {}", self.path.display()))?;
+        eprintln!("created wallet {}", self.path.display());
+        Ok(wallet)
+    }
+
+    /// The note `note` names, without touching the wallet when it is given in full.
+    pub fn resolve(&self, note: NoteRef) -> Result<Note> {
+        match note {
+            NoteRef::Note(note) => Ok(note),
+            NoteRef::Commitment(commitment) => find(&self.open()?, commitment),
+        }
+    }
+}
+
+/// Stores `note` first if it is given in full, so it is on disk before any funds move.
+pub fn store(wallet: &mut NoteWallet, note: NoteRef) -> Result<Note> {
+    match note {
+        NoteRef::Note(note) => {
+            if wallet.get(note.commitment()).is_none() {
+                wallet.add(note.clone())?;
+                wallet.save()?;
+            }
+            Ok(note)
+        }
+        NoteRef::Commitment(commitment) => find(wallet, commitment),
+    }
+}
+
+fn find(wallet: &NoteWallet, commitment: FixedBytes<32>) -> Result<Note> {
+    wallet
+        .get(commitment)
+        .map(|stored| stored.note().clone())
+        .ok_or_else(|| eyre!("{} holds no note {commitment}", wallet.path().display()))
+}
+
+fn prompt(message: &str) -> Result<Zeroizing<String>> {
+    rpassword::prompt_password(message)
+        .map(Zeroizing::new)
+        .wrap_err("cannot read the wallet password")
+}
@@ -0,0 +1,139 @@
+use std::{
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use alloy::primitives::{address, uint};
+use stylus_mixer_client::Note;
+
+fn mixer_cli(args: &[&str]) -> std::process::Output {
+    Command::new(env!("CARGO_BIN_EXE_mixer-cli"))
+        .args(args)
+        .output()
+        .expect("mixer-cli runs")
+}
+
+/* a fresh wallet path per test, unlocked through the environment */
+fn with_wallet(wallet: &Path, args: &[&str]) -> std::process::Output {
+    Command::new(env!("CARGO_BIN_EXE_mixer-cli"))
+        .args(args)
+        .env("MIXER_WALLET", wallet)
+        .env("MIXER_WALLET_PASSWORD", "correct horse battery staple")
+        .output()
+        .expect("mixer-cli runs")
+}
+
+fn scratch_wallet(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("mixer-cli-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join(name);
+    let _ = std::fs::remove_file(&path);
+    path
+}
+
+fn stdout(output: &std::process::Output) -> String {
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+#[test]
+fn note_new_prints_a_parseable_note() {
+    let wallet = scratch_wallet("new.json");
+    let output = with_wallet(
+        &wallet,
+        &[
+            "note",
+            "new",
+            "--pool",
+            "0x5FbDB2315678afecb367f032d93F642f64180aa3",
+            "--chain-id",
+            "412346",
+        ],
+    );
+
+    let note: Note = stdout(&output).trim().parse().unwrap();
+    let deployment = note.deployment();
+    assert_eq!(deployment.chain_id, 412346);
+    assert_eq!(deployment.pool, address!("5FbDB2315678afecb367f032d93F642f64180aa3"));
+    assert_eq!(deployment.denomination, uint!(1_000_000_000_000_000_000_U256));
+
+    /* the note is in the wallet, and only in encrypted form */
+    assert_eq!(stdout(&with_wallet(&wallet, &["note", "export"])).trim(), note.to_string());
+    assert!(!std::fs::read_to_string(&wallet).unwrap().contains("stylusmixer"));
+}
+
+#[test]
+fn notes_move_between_wallets() {
+    let source = scratch_wallet("source.json");
+    let pool = "0x5FbDB2315678afecb367f032d93F642f64180aa3";
+    for _ in 0..2 {
+        stdout(&with_wallet(&source, &["note", "new", "--pool", pool, "--chain-id", "412346"]));
+    }
+    let exported = stdout(&with_wallet(&source, &["note", "export"]));
+    let notes: Vec<&str> = exported.lines().collect();
+    assert_eq!(notes.len(), 2);
+
+    let target = scratch_wallet("target.json");
+    let imported = stdout(&with_wallet(&target, &["note", "import", notes[0], notes[1]]));
+    assert!(imported.contains("imported 2 new notes"));
+    let again = stdout(&with_wallet(&target, &["note", "import", notes[1]]));
+    assert!(again.contains("imported 0 new notes"));
+
+    let listed = stdout(&with_wallet(&target, &["note", "list"]));
+    assert_eq!(listed.lines().count(), 2);
+    assert!(listed.lines().all(|line| line.contains(" new ")));
+
+    let commitment = notes[1].parse::<Note>().unwrap().commitment().to_string();
+    let single = stdout(&with_wallet(&target, &["note", "export", &commitment]));
+    assert_eq!(single.trim(), notes[1]);
+}
+
+#[test]
+fn wallet_refuses_a_wrong_password() {
+    let wallet = scratch_wallet("password.json");
+    stdout(&with_wallet(
+        &wallet,
+        &["note", "new", "--pool", "0x5FbDB2315678afecb367f032d93F642f64180aa3", "--chain-id", "1"],
+    ));
+
+    let output = Command::new(env!("CARGO_BIN_EXE_mixer-cli"))
+        .args(["note", "list"])
+        .env("MIXER_WALLET", &wallet)
+        .env("MIXER_WALLET_PASSWORD", "hunter2")
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("wrong password"));
+}
+
+#[test]
+fn wallet_password_is_not_a_flag() {
+    let output = mixer_cli(&["--wallet-password", "hunter2", "note", "list"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("unexpected argument"));
+}
+
+#[test]
+fn deposit_rejects_a_malformed_note() {
+    let output = mixer_cli(&["deposit", "--note", "stylusmixer-v1-eth-1"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("malformed note string"));
+}
+
+#[test]
+fn withdraw_needs_a_signer() {
+    let output = Command::new(env!("CARGO_BIN_EXE_mixer-cli"))
+        .args(["withdraw", "--proof", "missing.json"])
+        .env_remove("PRIVATE_KEY")
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("withdraw needs PRIVATE_KEY"));
+}
+
+#[test]
+fn private_key_is_not_a_flag() {
+    let output = mixer_cli(&["--private-key", "0x01", "note", "list"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("unexpected argument"));
+}
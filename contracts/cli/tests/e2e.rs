@@ -0,0 +1,97 @@
+#![cfg(feature = "e2e")]
+
+//! Runs `mixer-cli` against a pool deployed on the nitro dev node at `RPC_URL`
+//! (http://localhost:8547 by default). Needs the wasm builds, `scripts/build_circuits.sh`
+//! output, forge, cargo-stylus, nargo and bb.
+
+use std::{
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use alloy::{
+    primitives::{uint, Address, U256},
+    providers::{Provider, ProviderBuilder},
+    signers::local::PrivateKeySigner,
+};
+use eyre::Result;
+use mixer_deploy::{checked_stdout, Tools};
+use stylus_mixer_client::Note;
+
+const TREE_DEPTH: u32 = 15;
+const DENOMINATION: U256 = uint!(1_000_000_000_000_000_000_U256);
+
+/* a fresh pool, so the note is the tree's only leaf */
+fn deploy_pool(tools: &Tools) -> Result<Address> {
+    Ok(tools.deploy_pool(TREE_DEPTH, DENOMINATION)?)
+}
+
+fn scratch_dir() -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("mixer-cli-e2e-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn mixer_cli(tools: &Tools, dir: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new(env!("CARGO_BIN_EXE_mixer-cli"))
+        .args(args)
+        .env("RPC_URL", &tools.rpc_url)
+        .env("PRIVATE_KEY", &tools.private_key)
+        .env("MIXER_WALLET", dir.join("wallet.json"))
+        .env("MIXER_WALLET_PASSWORD", "correct horse battery staple")
+        .current_dir(dir)
+        .output()?;
+    let what = format!("mixer-cli {}", args.join(" "));
+    Ok(checked_stdout(&what, output)?)
+}
+
+#[tokio::test]
+async fn deposit_sync_prove_withdraw() -> Result<()> {
+    let tools = Tools::dev()?;
+    let pool = deploy_pool(&tools)?.to_string();
+    let dir = scratch_dir();
+    let circuit = tools.root.join("circuits");
+    let recipient = PrivateKeySigner::random().address();
+
+    let note: Note = mixer_cli(&tools, &dir, &["note", "new", "--pool", &pool])?
+        .trim()
+        .parse()?;
+    let commitment = note.commitment().to_string();
+
+    let deposited = mixer_cli(&tools, &dir, &["deposit", "--note", &commitment])?;
+    assert!(deposited.contains("deposited leaf 0"), "{deposited}");
+
+    /* cache and proof go to their defaults in `dir` */
+    let synced = mixer_cli(&tools, &dir, &["sync", "--pool", &pool])?;
+    assert!(synced.starts_with("1 deposits (1 new)"), "{synced}");
+
+    mixer_cli(
+        &tools,
+        &dir,
+        &[
+            "prove",
+            "--note",
+            &commitment,
+            "--recipient",
+            &recipient.to_string(),
+            "--circuit",
+            circuit.to_str().unwrap(),
+        ],
+    )?;
+    let withdrew = mixer_cli(&tools, &dir, &["withdraw"])?;
+    assert!(withdrew.contains("marked the note spent"), "{withdrew}");
+
+    let provider = ProviderBuilder::new().on_http(tools.rpc_url.parse()?);
+    assert_eq!(provider.get_balance(recipient).await?, DENOMINATION);
+
+    /* the pool agrees with the wallet */
+    let refreshed = mixer_cli(&tools, &dir, &["note", "refresh"])?;
+    assert!(refreshed.contains("0 notes newly spent"), "{refreshed}");
+    let listed = mixer_cli(&tools, &dir, &["note", "list"])?;
+    assert!(listed.contains(&format!("{commitment} spent")), "{listed}");
+
+    /* the nullifier is spent, a second withdrawal reverts */
+    assert!(mixer_cli(&tools, &dir, &["withdraw"]).is_err());
+    Ok(())
+}
@@ -0,0 +1,153 @@
+use core::fmt;
+use std::{io, path::Path};
+
+use alloy_primitives::{Address, FixedBytes};
+use serde_json::{Map, Value};
+use stylus_common::public_inputs::WithdrawPublicInputs;
+use stylus_imt::offchain::{OffchainTree, OffchainTreeError};
+
+use crate::Note;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum InputsError {
+    /// The note's commitment is not a leaf of the tree.
+    CommitmentNotFound,
+    Tree(OffchainTreeError),
+}
+
+impl fmt::Display for InputsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::CommitmentNotFound => write!(f, "note commitment is not in the tree"),
+            Self::Tree(err) => write!(f, "cannot rebuild the tree: {err:?}"),
+        }
+    }
+}
+
+impl std::error::Error for InputsError {}
+
+impl From<OffchainTreeError> for InputsError {
+    fn from(err: OffchainTreeError) -> Self {
+        Self::Tree(err)
+    }
+}
+
+/// One value of the circuit's input map.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum InputValue {
+    Field(FixedBytes<32>),
+    Fields(Vec<FixedBytes<32>>),
+    Bools(Vec<bool>),
+}
+
+/// Everything `circuits/src/main.nr` takes to prove one withdrawal.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WithdrawInputs {
+    pub public: WithdrawPublicInputs,
+    pub nullifier: FixedBytes<32>,
+    pub secret: FixedBytes<32>,
+    /// Sibling at each level, leaf level first.
+    pub merkle_proof: Vec<FixedBytes<32>>,
+    /// Whether the path node at each level is the left child.
+    pub is_even: Vec<bool>,
+}
+
+impl WithdrawInputs {
+    /// Proves `note` against the current root of `tree`, chain and pool come from the note.
+    pub fn from_tree(
+        note: &Note,
+        recipient: Address,
+        tree: &OffchainTree,
+    ) -> Result<Self, InputsError> {
+        let index = tree
+            .index_of(note.commitment())
+            .ok_or(InputsError::CommitmentNotFound)?;
+        let proof = tree.proof(index).ok_or(InputsError::CommitmentNotFound)?;
+
+        Ok(Self {
+            public: WithdrawPublicInputs {
+                root: proof.root,
+                nullifier_hash: note.nullifier_hash(),
+                recipient,
+                chain_id: note.deployment().chain_id,
+                pool: note.deployment().pool,
+            },
+            nullifier: note.nullifier(),
+            secret: note.secret(),
+            merkle_proof: proof.siblings,
+            is_even: proof.is_even,
+        })
+    }
+
+    /// Same as [`Self::from_tree`] over a tree of `depth` holding `leaves` in deposit order.
+    pub fn from_leaves(
+        note: &Note,
+        recipient: Address,
+        depth: u32,
+        leaves: impl IntoIterator<Item = FixedBytes<32>>,
+    ) -> Result<Self, InputsError> {
+        let tree: OffchainTree = OffchainTree::with_leaves(depth, leaves)?;
+        Self::from_tree(note, recipient, &tree)
+    }
+
+    /// Named inputs in the order `main` declares its parameters.
+    pub fn input_map(&self) -> Vec<(&'static str, InputValue)> {
+        let public = self.public.to_verifier_inputs();
+        let [root, nullifier_hash, recipient, chain_id, pool] = public[..] else {
+            unreachable!("the withdraw circuit has {} public inputs", WithdrawPublicInputs::LEN);
+        };
+        vec![
+            ("root", InputValue::Field(root)),
+            ("nullifier_hash", InputValue::Field(nullifier_hash)),
+            ("recipient", InputValue::Field(recipient)),
+            ("chain_id", InputValue::Field(chain_id)),
+            ("pool", InputValue::Field(pool)),
+            ("nullifier", InputValue::Field(self.nullifier)),
+            ("secret", InputValue::Field(self.secret)),
+            ("merkle_proof", InputValue::Fields(self.merkle_proof.clone())),
+            ("is_even", InputValue::Bools(self.is_even.clone())),
+        ]
+    }
+
+    /// `Prover.toml` for `nargo execute`.
+    pub fn to_prover_toml(&self) -> String {
+        let mut toml = String::new();
+        for (name, value) in self.input_map() {
+            let value = match value {
+                InputValue::Field(field) => format!("\"{field}\""),
+                InputValue::Fields(fields) => {
+                    let fields: Vec<String> = fields.iter().map(|f| format!("\"{f}\"")).collect();
+                    format!("[{}]", fields.join(", "))
+                }
+                InputValue::Bools(bools) => {
+                    let bools: Vec<String> = bools.iter().map(bool::to_string).collect();
+                    format!("[{}]", bools.join(", "))
+                }
+            };
+            toml.push_str(&format!("{name} = {value}\n"));
+        }
+        toml
+    }
+
+    /// The `InputMap` `Noir::execute` takes in noir_js.
+    pub fn to_json(&self) -> Value {
+        let mut map = Map::new();
+        for (name, value) in self.input_map() {
+            let value = match value {
+                InputValue::Field(field) => Value::String(field.to_string()),
+                InputValue::Fields(fields) => fields
+                    .iter()
+                    .map(|field| Value::String(field.to_string()))
+                    .collect(),
+                InputValue::Bools(bools) => bools.into_iter().map(Value::Bool).collect(),
+            };
+            map.insert(name.to_string(), value);
+        }
+        Value::Object(map)
+    }
+
+    /// Writes `Prover.toml` into `dir`, usually the circuit's package directory.
+    pub fn write_prover_toml(&self, dir: impl AsRef<Path>) -> io::Result<()> {
+        std::fs::write(dir.as_ref().join("Prover.toml"), self.to_prover_toml())
+    }
+}
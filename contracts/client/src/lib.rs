@@ -0,0 +1,7 @@
+//! Host-side tooling for the mixer: everything a depositor or relayer computes off-chain.
+
+mod inputs;
+mod note;
+
+pub use inputs::{InputValue, InputsError, WithdrawInputs};
+pub use note::{Deployment, Note, NoteError};
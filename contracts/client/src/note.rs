@@ -0,0 +1,259 @@
+use core::fmt;
+use core::str::FromStr;
+
+use alloy_primitives::{hex, keccak256, uint, Address, FixedBytes, U256};
+use openzeppelin_poseidon::hash_fixed_bytes;
+use rand::{CryptoRng, RngCore};
+
+/* BN254 scalar field modulus, nullifier and secret are elements of this field */
+const FIELD_MODULUS: U256 =
+    uint!(21888242871839275222246405745257275088548364400416034343698204186575808495617_U256);
+
+const NOTE_PREFIX: &str = "stylusmixer";
+const NOTE_VERSION: &str = "v1";
+/* bytes of keccak256(body) appended to the note string */
+const CHECKSUM_LEN: usize = 4;
+
+/// The pool a note was deposited into. A note is only spendable against this exact deployment.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Deployment {
+    /// Lowercase symbol of the deposited asset, `eth` for the native mixer.
+    pub token: String,
+    /// Amount per deposit in the token's smallest unit.
+    pub denomination: U256,
+    pub chain_id: u64,
+    pub pool: Address,
+}
+
+impl Deployment {
+    pub fn eth(denomination: U256, chain_id: u64, pool: Address) -> Self {
+        Self {
+            token: "eth".to_string(),
+            denomination,
+            chain_id,
+            pool,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum NoteError {
+    InvalidFormat,
+    UnsupportedVersion(String),
+    InvalidToken,
+    InvalidChecksum,
+    /// Nullifier or secret is not below the field modulus.
+    NonCanonicalField,
+    WrongToken { expected: String, actual: String },
+    WrongDenomination { expected: U256, actual: U256 },
+    WrongChain { expected: u64, actual: u64 },
+    WrongPool { expected: Address, actual: Address },
+}
+
+impl fmt::Display for NoteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidFormat => write!(f, "malformed note string"),
+            Self::UnsupportedVersion(version) => write!(f, "unsupported note version `{version}`"),
+            Self::InvalidToken => write!(f, "token must be a lowercase alphanumeric symbol"),
+            Self::InvalidChecksum => write!(f, "note checksum does not match, the note is corrupted"),
+            Self::NonCanonicalField => write!(f, "nullifier or secret is not a field element"),
+            Self::WrongToken { expected, actual } => {
+                write!(f, "note is for token {actual}, expected {expected}")
+            }
+            Self::WrongDenomination { expected, actual } => {
+                write!(f, "note is for amount {actual}, expected {expected}")
+            }
+            Self::WrongChain { expected, actual } => {
+                write!(f, "note is for chain {actual}, expected {expected}")
+            }
+            Self::WrongPool { expected, actual } => {
+                write!(f, "note is for pool {actual}, expected {expected}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for NoteError {}
+
+/// The private half of a deposit. Whoever knows `nullifier` and `secret` can withdraw it.
+///
+/// Renders as `stylusmixer-v1-<token>-<amount>-<chainId>-<pool>-0x<nullifier><secret>-<checksum>`,
+/// where the checksum is the first 4 bytes of keccak256 over everything before it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Note {
+    deployment: Deployment,
+    nullifier: FixedBytes<32>,
+    secret: FixedBytes<32>,
+}
+
+impl Note {
+    /// Returns `None` unless both values are canonical field elements.
+    pub fn new(
+        deployment: Deployment,
+        nullifier: FixedBytes<32>,
+        secret: FixedBytes<32>,
+    ) -> Option<Self> {
+        if !is_field_element(nullifier) || !is_field_element(secret) {
+            return None;
+        }
+        Some(Self {
+            deployment,
+            nullifier,
+            secret,
+        })
+    }
+
+    /// Draws nullifier and secret uniformly from the field, like bb.js `Fr.random()`.
+    pub fn random<R: RngCore + CryptoRng>(deployment: Deployment, rng: &mut R) -> Self {
+        Self {
+            deployment,
+            nullifier: random_field_element(rng),
+            secret: random_field_element(rng),
+        }
+    }
+
+    pub fn deployment(&self) -> &Deployment {
+        &self.deployment
+    }
+
+    pub fn nullifier(&self) -> FixedBytes<32> {
+        self.nullifier
+    }
+
+    pub fn secret(&self) -> FixedBytes<32> {
+        self.secret
+    }
+
+    /// `Poseidon2::hash([nullifier, secret], 2)`, the leaf `Mixer::deposit` inserts.
+    pub fn commitment(&self) -> FixedBytes<32> {
+        hash_fixed_bytes(&[self.nullifier, self.secret])
+    }
+
+    /// `Poseidon2::hash([nullifier], 1)`, the value `Mixer::withdraw` marks as spent.
+    pub fn nullifier_hash(&self) -> FixedBytes<32> {
+        hash_fixed_bytes(&[self.nullifier])
+    }
+
+    /// Refuses the note unless it was made for `deployment`, call before building a withdrawal.
+    pub fn check_deployment(&self, deployment: &Deployment) -> Result<(), NoteError> {
+        let ours = &self.deployment;
+        if ours.chain_id != deployment.chain_id {
+            return Err(NoteError::WrongChain {
+                expected: deployment.chain_id,
+                actual: ours.chain_id,
+            });
+        }
+        if ours.pool != deployment.pool {
+            return Err(NoteError::WrongPool {
+                expected: deployment.pool,
+                actual: ours.pool,
+            });
+        }
+        if ours.token != deployment.token {
+            return Err(NoteError::WrongToken {
+                expected: deployment.token.clone(),
+                actual: ours.token.clone(),
+            });
+        }
+        if ours.denomination != deployment.denomination {
+            return Err(NoteError::WrongDenomination {
+                expected: deployment.denomination,
+                actual: ours.denomination,
+            });
+        }
+        Ok(())
+    }
+
+    fn body(&self) -> String {
+        let Deployment {
+            token,
+            denomination,
+            chain_id,
+            pool,
+        } = &self.deployment;
+        format!(
+            "{NOTE_PREFIX}-{NOTE_VERSION}-{token}-{denomination}-{chain_id}-{pool:#x}-0x{}{}",
+            hex::encode(self.nullifier),
+            hex::encode(self.secret),
+        )
+    }
+}
+
+impl fmt::Display for Note {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let body = self.body();
+        write!(f, "{body}-{}", checksum(&body))
+    }
+}
+
+impl FromStr for Note {
+    type Err = NoteError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let (body, sum) = s.rsplit_once('-').ok_or(NoteError::InvalidFormat)?;
+
+        let parts: Vec<&str> = body.split('-').collect();
+        if parts.len() < 2 || parts[0] != NOTE_PREFIX {
+            return Err(NoteError::InvalidFormat);
+        }
+        if parts[1] != NOTE_VERSION {
+            return Err(NoteError::UnsupportedVersion(parts[1].to_string()));
+        }
+        let [_, _, token, denomination, chain_id, pool, secrets] = parts[..] else {
+            return Err(NoteError::InvalidFormat);
+        };
+
+        if !sum.eq_ignore_ascii_case(&checksum(body)) {
+            return Err(NoteError::InvalidChecksum);
+        }
+
+        if token.is_empty()
+            || !token
+                .bytes()
+                .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit())
+        {
+            return Err(NoteError::InvalidToken);
+        }
+        let denomination =
+            U256::from_str_radix(denomination, 10).map_err(|_| NoteError::InvalidFormat)?;
+        let chain_id = chain_id.parse().map_err(|_| NoteError::InvalidFormat)?;
+        let pool = Address::from_str(pool).map_err(|_| NoteError::InvalidFormat)?;
+
+        let secrets = secrets.strip_prefix("0x").ok_or(NoteError::InvalidFormat)?;
+        let mut bytes = [0u8; 64];
+        hex::decode_to_slice(secrets, &mut bytes).map_err(|_| NoteError::InvalidFormat)?;
+        let nullifier = FixedBytes::from_slice(&bytes[..32]);
+        let secret = FixedBytes::from_slice(&bytes[32..]);
+
+        let deployment = Deployment {
+            token: token.to_string(),
+            denomination,
+            chain_id,
+            pool,
+        };
+        Note::new(deployment, nullifier, secret).ok_or(NoteError::NonCanonicalField)
+    }
+}
+
+fn checksum(body: &str) -> String {
+    hex::encode(&keccak256(body.as_bytes())[..CHECKSUM_LEN])
+}
+
+fn is_field_element(value: FixedBytes<32>) -> bool {
+    U256::from_be_bytes(value.0) < FIELD_MODULUS
+}
+
+/* rejection sampling over 254 bits, so every element is equally likely */
+fn random_field_element<R: RngCore + CryptoRng>(rng: &mut R) -> FixedBytes<32> {
+    loop {
+        let mut bytes = [0u8; 32];
+        rng.fill_bytes(&mut bytes);
+        bytes[0] &= 0x3f;
+        let candidate = FixedBytes::from(bytes);
+        if is_field_element(candidate) {
+            return candidate;
+        }
+    }
+}
@@ -0,0 +1,137 @@
+use std::path::Path;
+
+use alloy_primitives::{address, uint, Address, FixedBytes};
+use rand::{rngs::StdRng, SeedableRng};
+use serde_json::Value;
+use stylus_imt::hasher::{MerkleHasher, Poseidon2Hasher};
+use stylus_mixer_client::{Deployment, InputValue, InputsError, Note, WithdrawInputs};
+
+const DEPTH: u32 = 15;
+const RECIPIENT: Address = address!("70997970C51812dc3A010C7d01b50e0d17dc79C8");
+
+fn deployment() -> Deployment {
+    Deployment::eth(
+        uint!(1_000_000_000_000_000_000_U256),
+        412346,
+        address!("5FbDB2315678afecb367f032d93F642f64180aa3"),
+    )
+}
+
+/* three deposits, the note under test is the middle one */
+fn sample() -> (Note, WithdrawInputs) {
+    let mut rng = StdRng::seed_from_u64(1);
+    let notes: Vec<Note> = (0..3).map(|_| Note::random(deployment(), &mut rng)).collect();
+    let leaves = notes.iter().map(Note::commitment);
+    let inputs = WithdrawInputs::from_leaves(&notes[1], RECIPIENT, DEPTH, leaves).unwrap();
+    (notes[1].clone(), inputs)
+}
+
+#[test]
+fn inputs_prove_the_note() {
+    let (note, inputs) = sample();
+
+    assert_eq!(inputs.public.nullifier_hash, note.nullifier_hash());
+    assert_eq!(inputs.public.recipient, RECIPIENT);
+    assert_eq!(inputs.public.chain_id, 412346);
+    assert_eq!(inputs.public.pool, deployment().pool);
+    assert_eq!(inputs.merkle_proof.len(), DEPTH as usize);
+    assert_eq!(inputs.is_even[..2], [false, true]);
+
+    /* what circuits/src/imt.nr recomputes */
+    let root = inputs
+        .merkle_proof
+        .iter()
+        .zip(&inputs.is_even)
+        .fold(note.commitment(), |node, (sibling, is_even)| {
+            if *is_even {
+                Poseidon2Hasher::hash_pair(node, *sibling)
+            } else {
+                Poseidon2Hasher::hash_pair(*sibling, node)
+            }
+        });
+    assert_eq!(root, inputs.public.root);
+}
+
+#[test]
+fn missing_commitment_is_rejected() {
+    let note = Note::random(deployment(), &mut StdRng::seed_from_u64(2));
+    let leaves = [FixedBytes::repeat_byte(1), FixedBytes::repeat_byte(2)];
+    assert_eq!(
+        WithdrawInputs::from_leaves(&note, RECIPIENT, DEPTH, leaves),
+        Err(InputsError::CommitmentNotFound)
+    );
+}
+
+/* gitignored, scripts/build_circuits.sh writes it and scripts/check.sh runs that first */
+fn circuit_artifact() -> Option<Value> {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../circuits/target/circuits.json");
+    let Ok(artifact) = std::fs::read_to_string(&path) else {
+        eprintln!("skipped, no {}, run scripts/build_circuits.sh", path.display());
+        return None;
+    };
+    Some(serde_json::from_str(&artifact).expect("invalid circuit artifact"))
+}
+
+#[test]
+fn input_map_matches_circuit_abi() {
+    let Some(artifact) = circuit_artifact() else {
+        return;
+    };
+    let parameters = artifact["abi"]["parameters"]
+        .as_array()
+        .expect("abi without parameters");
+
+    let (_, inputs) = sample();
+    let map = inputs.input_map();
+    assert_eq!(map.len(), parameters.len());
+
+    for ((name, value), param) in map.iter().zip(parameters) {
+        assert_eq!(*name, param["name"].as_str().unwrap());
+        let ty = &param["type"];
+        match value {
+            InputValue::Field(_) => assert_eq!(ty["kind"], "field", "{name}"),
+            InputValue::Fields(fields) => {
+                assert_eq!(ty["type"]["kind"], "field", "{name}");
+                assert_eq!(ty["length"].as_u64(), Some(fields.len() as u64), "{name}");
+            }
+            InputValue::Bools(bools) => {
+                assert_eq!(ty["type"]["kind"], "boolean", "{name}");
+                assert_eq!(ty["length"].as_u64(), Some(bools.len() as u64), "{name}");
+            }
+        }
+    }
+}
+
+#[test]
+fn prover_toml_has_one_line_per_input() {
+    let (note, inputs) = sample();
+    let toml = inputs.to_prover_toml();
+    let lines: Vec<&str> = toml.lines().collect();
+
+    assert_eq!(lines.len(), 9);
+    assert_eq!(lines[0], format!("root = \"{}\"", inputs.public.root));
+    assert_eq!(
+        lines[2],
+        "recipient = \"0x00000000000000000000000070997970c51812dc3a010c7d01b50e0d17dc79c8\""
+    );
+    assert_eq!(
+        lines[3],
+        "chain_id = \"0x0000000000000000000000000000000000000000000000000000000000064aba\""
+    );
+    assert_eq!(lines[5], format!("nullifier = \"{}\"", note.nullifier()));
+    assert!(lines[7].starts_with("merkle_proof = [\"0x"));
+    assert!(lines[8].starts_with("is_even = [false, true, "));
+}
+
+#[test]
+fn json_matches_prover_toml() {
+    let (_, inputs) = sample();
+    let json = inputs.to_json();
+
+    assert_eq!(json["root"], inputs.public.root.to_string());
+    assert_eq!(json["merkle_proof"].as_array().unwrap().len(), DEPTH as usize);
+    assert_eq!(json["merkle_proof"][0], inputs.merkle_proof[0].to_string());
+    assert_eq!(json["is_even"][0], false);
+    assert_eq!(json["is_even"][1], true);
+    assert_eq!(json.as_object().unwrap().len(), 9);
+}
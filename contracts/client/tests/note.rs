@@ -0,0 +1,168 @@
+use alloy_primitives::{address, fixed_bytes, uint, FixedBytes, U256};
+use rand::{rngs::StdRng, SeedableRng};
+use stylus_mixer_client::{Deployment, Note, NoteError};
+
+const NOTE: &str = "stylusmixer-v1-eth-1000000000000000000-412346-0x5fbdb2315678afecb367f032d93f642f64180aa3-0x0c7d5d4b3bd1e2f0ba6e6d1e5b3b4a9a2f6e5d4c3b2a190807060504030201001a2b3c4d5e6f708192a3b4c5d6e7f8091a2b3c4d5e6f708192a3b4c5d6e7f809-63f6b022";
+
+fn deployment() -> Deployment {
+    Deployment::eth(
+        uint!(1_000_000_000_000_000_000_U256),
+        412346,
+        address!("5FbDB2315678afecb367f032d93F642f64180aa3"),
+    )
+}
+
+fn sample_note() -> Note {
+    Note::new(
+        deployment(),
+        fixed_bytes!("0x0c7d5d4b3bd1e2f0ba6e6d1e5b3b4a9a2f6e5d4c3b2a19080706050403020100"),
+        fixed_bytes!("0x1a2b3c4d5e6f708192a3b4c5d6e7f8091a2b3c4d5e6f708192a3b4c5d6e7f809"),
+    )
+    .unwrap()
+}
+
+/* expected digests are bb.js `poseidon2Hash` outputs */
+#[test]
+fn note_matches_bb_js() {
+    let note = sample_note();
+
+    assert_eq!(
+        note.commitment(),
+        fixed_bytes!("0x13eabbf0713ace73107ef68529bf8107b97b63c1f8032564138614c776affe0e")
+    );
+    assert_eq!(
+        note.nullifier_hash(),
+        fixed_bytes!("0x0702034e1d5f29dae7b5f863c91207a6ea1853770686bf342400aa78c83dc4cc")
+    );
+}
+
+#[test]
+fn small_inputs_match_bb_js() {
+    let one = FixedBytes::from(U256::from(1));
+    let two = FixedBytes::from(U256::from(2));
+    let note = Note::new(deployment(), one, two).unwrap();
+
+    assert_eq!(
+        note.commitment(),
+        fixed_bytes!("0x038682aa1cb5ae4e0a3f13da432a95c77c5c111f6f030faf9cad641ce1ed7383")
+    );
+    assert_eq!(
+        note.nullifier_hash(),
+        fixed_bytes!("0x168758332d5b3e2d13be8048c8011b454590e06c44bce7f702f09103eef5a373")
+    );
+}
+
+#[test]
+fn random_notes_are_field_elements() {
+    let mut rng = StdRng::seed_from_u64(7);
+    for _ in 0..64 {
+        let note = Note::random(deployment(), &mut rng);
+        assert_eq!(
+            Note::new(deployment(), note.nullifier(), note.secret()),
+            Some(note.clone())
+        );
+        assert_ne!(note.nullifier(), note.secret());
+    }
+}
+
+#[test]
+fn new_rejects_values_outside_the_field() {
+    assert_eq!(
+        Note::new(deployment(), FixedBytes::repeat_byte(0xff), FixedBytes::ZERO),
+        None
+    );
+    assert_eq!(
+        Note::new(deployment(), FixedBytes::ZERO, FixedBytes::repeat_byte(0xff)),
+        None
+    );
+}
+
+/* ======================================================================
+ *                              NOTE STRING
+ * ====================================================================== */
+#[test]
+fn note_string_is_stable() {
+    assert_eq!(sample_note().to_string(), NOTE);
+    assert_eq!(NOTE.parse::<Note>().unwrap(), sample_note());
+}
+
+#[test]
+fn note_string_roundtrips() {
+    let mut rng = StdRng::seed_from_u64(42);
+    for _ in 0..16 {
+        let note = Note::random(deployment(), &mut rng);
+        assert_eq!(note.to_string().parse::<Note>().unwrap(), note);
+    }
+}
+
+#[test]
+fn note_string_accepts_checksummed_pool() {
+    let note = NOTE.replace(
+        "0x5fbdb2315678afecb367f032d93f642f64180aa3",
+        "0x5FbDB2315678afecb367f032d93F642f64180aa3",
+    );
+    /* the checksum covers the text as written */
+    assert_eq!(note.parse::<Note>(), Err(NoteError::InvalidChecksum));
+
+    let (body, _) = note.rsplit_once('-').unwrap();
+    let resummed = format!("{body}-{}", &sample_checksum(body));
+    assert_eq!(resummed.parse::<Note>().unwrap(), sample_note());
+}
+
+#[test]
+fn note_string_rejects_corruption() {
+    /* flip one nibble of the secret */
+    let corrupted = NOTE.replacen("e7f809-", "e7f808-", 1);
+    assert_eq!(corrupted.parse::<Note>(), Err(NoteError::InvalidChecksum));
+
+    assert_eq!(
+        NOTE.replace("-v1-", "-v2-").parse::<Note>(),
+        Err(NoteError::UnsupportedVersion("v2".to_string()))
+    );
+    assert_eq!(
+        NOTE.replace("stylusmixer", "tornado").parse::<Note>(),
+        Err(NoteError::InvalidFormat)
+    );
+    assert_eq!("".parse::<Note>(), Err(NoteError::InvalidFormat));
+    assert_eq!(
+        NOTE.rsplit_once('-').unwrap().0.parse::<Note>(),
+        Err(NoteError::InvalidFormat)
+    );
+}
+
+#[test]
+fn check_deployment_rejects_other_deployments() {
+    let note = sample_note();
+    assert_eq!(note.check_deployment(&deployment()), Ok(()));
+
+    let mut other = deployment();
+    other.chain_id = 42161;
+    assert_eq!(
+        note.check_deployment(&other),
+        Err(NoteError::WrongChain {
+            expected: 42161,
+            actual: 412346
+        })
+    );
+
+    let mut other = deployment();
+    other.pool = address!("e7f1725E7734CE288F8367e1Bb143E90bb3F0512");
+    assert_eq!(
+        note.check_deployment(&other),
+        Err(NoteError::WrongPool {
+            expected: other.pool,
+            actual: deployment().pool
+        })
+    );
+
+    let mut other = deployment();
+    other.denomination = uint!(100_000_000_000_000_000_U256);
+    assert!(matches!(
+        note.check_deployment(&other),
+        Err(NoteError::WrongDenomination { .. })
+    ));
+}
+
+fn sample_checksum(body: &str) -> String {
+    alloy_primitives::hex::encode(&alloy_primitives::keccak256(body)[..4])
+}
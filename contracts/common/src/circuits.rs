@@ -0,0 +1,50 @@
+/// One member of the withdraw circuit family built by `scripts/build_circuits.sh`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WithdrawCircuit {
+    pub depth: u32,
+    /// `nargo compile` output, relative to the repository root.
+    pub artifact: &'static str,
+    /// `bb write_vk` output, relative to the repository root.
+    pub vk: &'static str,
+    /// `forge create` identifier of the pinned verifier, relative to `contracts/mixer`.
+    pub verifier: &'static str,
+    /// Bytes in a proof `verifier` takes.
+    pub proof_size: u32,
+}
+
+/*
+ * UltraHonk (keccak transcript) proof size for the bb version in package.json: 8 witness
+ * commitments (4 fields each), 28 * 8 sumcheck univariates, 40 sumcheck evaluations,
+ * 27 gemini fold commitments, 28 gemini evaluations, shplonk Q and the KZG quotient
+ */
+pub const ULTRA_HONK_PROOF_SIZE: u32 = 440 * 32;
+
+macro_rules! withdraw_circuit {
+    ($depth:literal) => {
+        WithdrawCircuit {
+            depth: $depth,
+            artifact: concat!("circuits/target/depth_", $depth, "/circuits.json"),
+            vk: concat!("circuits/target/depth_", $depth, "/vk"),
+            verifier: concat!(
+                "src/verifiers/depth_",
+                $depth,
+                "/PinnedVerifier.sol:PinnedHonkVerifier"
+            ),
+            proof_size: ULTRA_HONK_PROOF_SIZE,
+        }
+    };
+}
+
+pub const SUPPORTED_DEPTHS: [u32; 4] = [10, 15, 20, 31];
+
+pub const WITHDRAW_CIRCUITS: [WithdrawCircuit; 4] = [
+    withdraw_circuit!(10),
+    withdraw_circuit!(15),
+    withdraw_circuit!(20),
+    withdraw_circuit!(31),
+];
+
+/// Circuit to deploy for a tree of `depth` levels, `None` if no artifact is built for it.
+pub fn withdraw_circuit(depth: u32) -> Option<&'static WithdrawCircuit> {
+    WITHDRAW_CIRCUITS.iter().find(|circuit| circuit.depth == depth)
+}
@@ -1,19 +1,12 @@
 extern crate alloc;
+use stylus_sdk::alloy_primitives::{FixedBytes, U256};
 use stylus_sdk::alloy_sol_types::sol;
 use stylus_sdk::prelude::*;
 
-sol! {
-    /* imt */
-    error InvalidDepth();
-    error TreeIsFull();
-
-    /* mixer */
-    error CommitmentAlreadyExists();
-    error InvalidDenomination();
-    error NullifierHashAlreadyUsed();
-    error InvalidRoot();
-    error InvalidProof();
-}
+sol!("src/errors/imt.sol");
+sol!("src/errors/mixer.sol");
+sol!("src/errors/groth16.sol");
+sol!("src/errors/honk.sol");
 
 #[derive(SolidityError)]
 pub enum ImtErrors {
@@ -38,6 +31,16 @@ pub enum MixerErrors {
     NullifierHashAlreadyUsed(NullifierHashAlreadyUsed),
     InvalidRoot(InvalidRoot),
     InvalidProof(InvalidProof),
+    Unauthorized(Unauthorized),
+    VerifierAlreadyRegistered(VerifierAlreadyRegistered),
+    UnknownCircuitVersion(UnknownCircuitVersion),
+    CircuitVersionDeprecated(CircuitVersionDeprecated),
+    MalformedProof(MalformedProof),
+    CircuitDepthMismatch(CircuitDepthMismatch),
+    UnsupportedCircuitDepth(UnsupportedCircuitDepth),
+    BatchWithdrawDisabled(BatchWithdrawDisabled),
+    InvalidBatchSize(InvalidBatchSize),
+    VerificationKeyMismatch(VerificationKeyMismatch),
 }
 
 impl MixerErrors {
@@ -60,4 +63,106 @@ impl MixerErrors {
     pub fn invalid_proof() -> Self {
         Self::InvalidProof(InvalidProof {})
     }
+
+    pub fn unauthorized() -> Self {
+        Self::Unauthorized(Unauthorized {})
+    }
+
+    pub fn verifier_already_registered(version: u32) -> Self {
+        Self::VerifierAlreadyRegistered(VerifierAlreadyRegistered { version })
+    }
+
+    pub fn unknown_circuit_version(version: u32) -> Self {
+        Self::UnknownCircuitVersion(UnknownCircuitVersion { version })
+    }
+
+    pub fn circuit_version_deprecated(version: u32) -> Self {
+        Self::CircuitVersionDeprecated(CircuitVersionDeprecated { version })
+    }
+
+    pub fn malformed_proof(length: U256) -> Self {
+        Self::MalformedProof(MalformedProof { length })
+    }
+
+    pub fn circuit_depth_mismatch(tree: u32, circuit: u32) -> Self {
+        Self::CircuitDepthMismatch(CircuitDepthMismatch { tree, circuit })
+    }
+
+    pub fn unsupported_circuit_depth(depth: u32) -> Self {
+        Self::UnsupportedCircuitDepth(UnsupportedCircuitDepth { depth })
+    }
+
+    pub fn batch_withdraw_disabled() -> Self {
+        Self::BatchWithdrawDisabled(BatchWithdrawDisabled {})
+    }
+
+    pub fn invalid_batch_size(expected: u32, actual: u32) -> Self {
+        Self::InvalidBatchSize(InvalidBatchSize { expected, actual })
+    }
+
+    pub fn verification_key_mismatch(expected: FixedBytes<32>, actual: FixedBytes<32>) -> Self {
+        Self::VerificationKeyMismatch(VerificationKeyMismatch { expected, actual })
+    }
+}
+
+#[derive(SolidityError)]
+pub enum Groth16Errors {
+    InvalidVerifyingKey(InvalidVerifyingKey),
+    InvalidProofLength(InvalidProofLength),
+    InvalidPublicInputsLength(InvalidPublicInputsLength),
+    PublicInputNotInField(PublicInputNotInField),
+    PrecompileFailed(PrecompileFailed),
+}
+
+impl Groth16Errors {
+    pub fn invalid_verifying_key() -> Self {
+        Self::InvalidVerifyingKey(InvalidVerifyingKey {})
+    }
+
+    pub fn invalid_proof_length() -> Self {
+        Self::InvalidProofLength(InvalidProofLength {})
+    }
+
+    pub fn invalid_public_inputs_length() -> Self {
+        Self::InvalidPublicInputsLength(InvalidPublicInputsLength {})
+    }
+
+    pub fn public_input_not_in_field() -> Self {
+        Self::PublicInputNotInField(PublicInputNotInField {})
+    }
+
+    pub fn precompile_failed() -> Self {
+        Self::PrecompileFailed(PrecompileFailed {})
+    }
+}
+
+#[derive(SolidityError)]
+pub enum HonkErrors {
+    InvalidVerificationKey(InvalidVerificationKey),
+    ProofLengthWrong(ProofLengthWrong),
+    PublicInputsLengthWrong(PublicInputsLengthWrong),
+    SumcheckFailed(SumcheckFailed),
+    ShpleminiFailed(ShpleminiFailed),
+}
+
+impl HonkErrors {
+    pub fn invalid_verification_key() -> Self {
+        Self::InvalidVerificationKey(InvalidVerificationKey {})
+    }
+
+    pub fn proof_length_wrong() -> Self {
+        Self::ProofLengthWrong(ProofLengthWrong {})
+    }
+
+    pub fn public_inputs_length_wrong() -> Self {
+        Self::PublicInputsLengthWrong(PublicInputsLengthWrong {})
+    }
+
+    pub fn sumcheck_failed() -> Self {
+        Self::SumcheckFailed(SumcheckFailed {})
+    }
+
+    pub fn shplemini_failed() -> Self {
+        Self::ShpleminiFailed(ShpleminiFailed {})
+    }
 }
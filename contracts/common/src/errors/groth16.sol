@@ -0,0 +1,7 @@
+// Reverts of `Groth16Verifier`, also read by stylus-mixer-bindings.
+
+error InvalidVerifyingKey();
+error InvalidProofLength();
+error InvalidPublicInputsLength();
+error PublicInputNotInField();
+error PrecompileFailed();
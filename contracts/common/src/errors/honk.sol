@@ -0,0 +1,7 @@
+// Reverts of `HonkVerifier`, also read by stylus-mixer-bindings.
+
+error InvalidVerificationKey();
+error ProofLengthWrong();
+error PublicInputsLengthWrong();
+error SumcheckFailed();
+error ShpleminiFailed();
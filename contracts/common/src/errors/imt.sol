@@ -0,0 +1,4 @@
+// Reverts of `IMT`, also read by stylus-mixer-bindings.
+
+error InvalidDepth();
+error TreeIsFull();
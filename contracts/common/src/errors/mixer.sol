@@ -0,0 +1,17 @@
+// Reverts of `Mixer`, also read by stylus-mixer-bindings.
+
+error CommitmentAlreadyExists();
+error InvalidDenomination();
+error NullifierHashAlreadyUsed();
+error InvalidRoot();
+error InvalidProof();
+error Unauthorized();
+error VerifierAlreadyRegistered(uint32 version);
+error UnknownCircuitVersion(uint32 version);
+error CircuitVersionDeprecated(uint32 version);
+error MalformedProof(uint256 length);
+error CircuitDepthMismatch(uint32 tree, uint32 circuit);
+error UnsupportedCircuitDepth(uint32 depth);
+error BatchWithdrawDisabled();
+error InvalidBatchSize(uint32 expected, uint32 actual);
+error VerificationKeyMismatch(bytes32 expected, bytes32 actual);
@@ -1 +1,4 @@
+pub mod circuits;
 pub mod errors;
+pub mod public_inputs;
+pub mod vk;
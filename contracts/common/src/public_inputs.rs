@@ -0,0 +1,52 @@
+extern crate alloc;
+use alloc::vec::Vec;
+use stylus_sdk::alloy_primitives::{Address, FixedBytes, U256};
+
+/*
+ * public inputs of the withdraw circuit (circuits/src/main.nr), in the order the
+ * verifier expects them: root || nullifier_hash || recipient || chain_id || pool
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WithdrawPublicInputs {
+    pub root: FixedBytes<32>,
+    pub nullifier_hash: FixedBytes<32>,
+    pub recipient: Address,
+    pub chain_id: u64,
+    pub pool: Address,
+}
+
+impl WithdrawPublicInputs {
+    /// Number of field elements the circuit exposes as public inputs.
+    pub const LEN: usize = 5;
+
+    pub fn to_verifier_inputs(&self) -> Vec<FixedBytes<32>> {
+        let mut inputs = Vec::with_capacity(Self::LEN);
+        inputs.push(self.root);
+        inputs.push(self.nullifier_hash);
+        inputs.push(self.recipient.into_word());
+        inputs.push(U256::from(self.chain_id).into());
+        inputs.push(self.pool.into_word());
+        inputs
+    }
+
+    /// Returns `None` if `inputs` has the wrong length or a word does not fit its field.
+    pub fn from_verifier_inputs(inputs: &[FixedBytes<32>]) -> Option<Self> {
+        let [root, nullifier_hash, recipient, chain_id, pool] = inputs else {
+            return None;
+        };
+        Some(Self {
+            root: *root,
+            nullifier_hash: *nullifier_hash,
+            recipient: word_to_address(recipient)?,
+            chain_id: u64::try_from(U256::from_be_bytes(chain_id.0)).ok()?,
+            pool: word_to_address(pool)?,
+        })
+    }
+}
+
+fn word_to_address(word: &FixedBytes<32>) -> Option<Address> {
+    if word[..12].iter().any(|byte| *byte != 0) {
+        return None;
+    }
+    Some(Address::from_word(*word))
+}
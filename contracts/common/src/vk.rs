@@ -0,0 +1,12 @@
+use std::{io, path::Path};
+use stylus_sdk::alloy_primitives::{keccak256, FixedBytes};
+
+/// `keccak256` of a serialized verification key, the value verifiers report from `vkHash()`.
+pub fn vk_hash(vk: &[u8]) -> FixedBytes<32> {
+    keccak256(vk)
+}
+
+/// Hashes a `bb write_vk` output file, e.g. `circuits/target/vk`.
+pub fn vk_hash_from_file(path: impl AsRef<Path>) -> io::Result<FixedBytes<32>> {
+    Ok(vk_hash(&std::fs::read(path)?))
+}
@@ -0,0 +1,32 @@
+use std::path::Path;
+
+use stylus_common::circuits::{withdraw_circuit, SUPPORTED_DEPTHS, WITHDRAW_CIRCUITS};
+
+#[test]
+fn every_supported_depth_has_a_circuit() {
+    for depth in SUPPORTED_DEPTHS {
+        let circuit = withdraw_circuit(depth).expect("missing circuit");
+        assert_eq!(circuit.depth, depth);
+        assert!(circuit.artifact.contains(&format!("depth_{depth}/")));
+        assert!(circuit.verifier.contains(&format!("depth_{depth}/")));
+    }
+    assert_eq!(WITHDRAW_CIRCUITS.len(), SUPPORTED_DEPTHS.len());
+    assert_eq!(withdraw_circuit(12), None);
+}
+
+#[test]
+fn build_script_builds_every_supported_depth() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../scripts/build_circuits.sh");
+    let script = std::fs::read_to_string(path).expect("missing scripts/build_circuits.sh");
+
+    let line = script
+        .lines()
+        .find_map(|line| line.strip_prefix("DEPTHS=("))
+        .expect("DEPTHS not found");
+    let depths: Vec<u32> = line
+        .trim_end_matches(')')
+        .split_whitespace()
+        .map(|depth| depth.parse().unwrap())
+        .collect();
+    assert_eq!(depths, SUPPORTED_DEPTHS);
+}
@@ -0,0 +1,80 @@
+use std::path::Path;
+
+use serde_json::Value;
+use stylus_common::public_inputs::WithdrawPublicInputs;
+use stylus_sdk::alloy_primitives::{address, FixedBytes, U256};
+
+/* number of field elements a Noir ABI type flattens to */
+fn field_count(ty: &Value) -> usize {
+    match ty["kind"].as_str().expect("abi type without kind") {
+        "field" | "boolean" | "integer" => 1,
+        "array" => {
+            let length = ty["length"].as_u64().expect("array without length") as usize;
+            length * field_count(&ty["type"])
+        }
+        "struct" => ty["fields"]
+            .as_array()
+            .expect("struct without fields")
+            .iter()
+            .map(|field| field_count(&field["type"]))
+            .sum(),
+        kind => panic!("unsupported abi type {kind}"),
+    }
+}
+
+/* gitignored, scripts/build_circuits.sh writes it and scripts/check.sh runs that first */
+fn circuit_artifact() -> Option<Value> {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../circuits/target/circuits.json");
+    let Ok(artifact) = std::fs::read_to_string(&path) else {
+        eprintln!("skipped, no {}, run scripts/build_circuits.sh", path.display());
+        return None;
+    };
+    Some(serde_json::from_str(&artifact).expect("invalid circuit artifact"))
+}
+
+#[test]
+fn withdraw_public_inputs_match_circuit_abi() {
+    let Some(artifact) = circuit_artifact() else {
+        return;
+    };
+
+    let public: Vec<&Value> = artifact["abi"]["parameters"]
+        .as_array()
+        .expect("abi without parameters")
+        .iter()
+        .filter(|param| param["visibility"] == "public")
+        .collect();
+
+    let names: Vec<&str> = public.iter().map(|param| param["name"].as_str().unwrap()).collect();
+    assert_eq!(names, ["root", "nullifier_hash", "recipient", "chain_id", "pool"]);
+
+    let count: usize = public.iter().map(|param| field_count(&param["type"])).sum();
+    assert_eq!(count, WithdrawPublicInputs::LEN);
+}
+
+#[test]
+fn withdraw_public_inputs_roundtrip() {
+    let inputs = WithdrawPublicInputs {
+        root: FixedBytes::repeat_byte(0x11),
+        nullifier_hash: FixedBytes::repeat_byte(0x22),
+        recipient: address!("0x00000000000000000000000000000000000000aa"),
+        chain_id: 412346,
+        pool: address!("0x00000000000000000000000000000000000000bb"),
+    };
+
+    let words = inputs.to_verifier_inputs();
+    assert_eq!(words.len(), WithdrawPublicInputs::LEN);
+    assert_eq!(words[2], inputs.recipient.into_word());
+    assert_eq!(U256::from_be_bytes(words[3].0), U256::from(412346));
+    assert_eq!(WithdrawPublicInputs::from_verifier_inputs(&words), Some(inputs));
+
+    assert_eq!(WithdrawPublicInputs::from_verifier_inputs(&words[..4]), None);
+
+    let mut dirty = words.clone();
+    dirty[2].0[0] = 1;
+    assert_eq!(WithdrawPublicInputs::from_verifier_inputs(&dirty), None);
+
+    let mut dirty = words.clone();
+    dirty[3].0[0] = 1;
+    assert_eq!(WithdrawPublicInputs::from_verifier_inputs(&dirty), None);
+}
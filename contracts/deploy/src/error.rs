@@ -0,0 +1,54 @@
+use core::fmt;
+use std::io;
+
+#[derive(Debug)]
+pub enum DeployError {
+    Io(io::Error),
+    Toml(toml::de::Error),
+    Json(serde_json::Error),
+    /// The manifest parsed but asks for something that cannot be deployed.
+    Manifest(String),
+    /// `forge` or `cargo stylus` failed, or a build output is missing.
+    Tool(String),
+    /// `deployments.json` was written on another chain.
+    WrongChain {
+        expected: u64,
+        actual: u64,
+    },
+}
+
+impl fmt::Display for DeployError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "{err}"),
+            Self::Toml(err) => write!(f, "malformed manifest: {err}"),
+            Self::Json(err) => write!(f, "malformed deployments file: {err}"),
+            Self::Manifest(reason) => write!(f, "invalid manifest: {reason}"),
+            Self::Tool(reason) => write!(f, "{reason}"),
+            Self::WrongChain { expected, actual } => write!(
+                f,
+                "deployments file is for chain {actual}, the node is chain {expected}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for DeployError {}
+
+impl From<io::Error> for DeployError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<toml::de::Error> for DeployError {
+    fn from(err: toml::de::Error) -> Self {
+        Self::Toml(err)
+    }
+}
+
+impl From<serde_json::Error> for DeployError {
+    fn from(err: serde_json::Error) -> Self {
+        Self::Json(err)
+    }
+}
@@ -0,0 +1,16 @@
+//! Deploys the verifier, trees and pools a manifest lists, and remembers what it deployed.
+
+mod error;
+mod manifest;
+mod output;
+mod record;
+mod tools;
+
+pub use error::DeployError;
+pub use manifest::{Manifest, Pool, VerifierArtifact};
+pub use output::{parse_forge_create, parse_stylus_deploy, Created};
+pub use record::{inputs_hash, Deployed, Deployments, PoolDeployment};
+pub use tools::{
+    checked_stdout, mixer_args, read_artifact, MixerArgs, Tools, DEV_PRIVATE_KEY, DEV_RPC_URL,
+    DEV_STYLUS_DEPLOYER, IMT_WASM, MIXER_WASM,
+};
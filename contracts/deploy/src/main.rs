@@ -0,0 +1,225 @@
+use std::{fs, path::PathBuf};
+
+use alloy::{
+    primitives::{Address, B256},
+    providers::{Provider, ProviderBuilder, RootProvider},
+    signers::local::PrivateKeySigner,
+    transports::http::{reqwest::Url, Client, Http},
+};
+use clap::Parser;
+use eyre::{ensure, Result, WrapErr};
+use mixer_deploy::{
+    inputs_hash, mixer_args, read_artifact, Created, Deployed, Deployments, Manifest, MixerArgs,
+    PoolDeployment, Tools, DEV_RPC_URL, DEV_STYLUS_DEPLOYER, IMT_WASM, MIXER_WASM,
+};
+use stylus_common::vk::vk_hash_from_file;
+
+#[derive(Parser)]
+#[command(
+    name = "mixer-deploy",
+    about = "Deploy the verifier, trees and pools listed in a manifest"
+)]
+struct Args {
+    #[arg(default_value = "mixer.toml")]
+    manifest: PathBuf,
+
+    /// Addresses and transaction hashes, read back on the next run to skip what is deployed.
+    #[arg(long, default_value = "deployments.json")]
+    out: PathBuf,
+
+    /// Repository root, build outputs and manifest paths are resolved against it.
+    #[arg(long, default_value = ".")]
+    root: PathBuf,
+
+    /// JSON-RPC endpoint, a nitro dev node by default.
+    #[arg(long, env = "RPC_URL", default_value = DEV_RPC_URL)]
+    rpc_url: Url,
+
+    #[arg(long, env = "PRIVATE_KEY", hide_env_values = true)]
+    private_key: PrivateKeySigner,
+
+    /// `StylusDeployer` that runs the IMT and `Mixer` constructors.
+    #[arg(long, env = "DEPLOYER_ADDRESS", default_value_t = DEV_STYLUS_DEPLOYER)]
+    stylus_deployer: Address,
+}
+
+/* runs forge and cargo stylus, and reads back what they deployed */
+struct Deployer {
+    provider: RootProvider<Http<Client>>,
+    tools: Tools,
+}
+
+impl Deployer {
+    async fn is_current(&self, deployed: &Deployed, inputs_hash: B256) -> Result<bool> {
+        let code = self.provider.get_code_at(deployed.address).await?;
+        Ok(deployed.is_current(&code, inputs_hash))
+    }
+
+    async fn record(&self, created: Created, inputs_hash: B256) -> Result<Deployed> {
+        let code = self.provider.get_code_at(created.address).await?;
+        ensure!(
+            !code.is_empty(),
+            "no code at {} after deployment",
+            created.address
+        );
+        Ok(Deployed::new(created, &code, inputs_hash))
+    }
+
+    async fn forge_create(&self, contract: &str, inputs_hash: B256) -> Result<Deployed> {
+        let created = self.tools.forge_create(contract)?;
+        self.record(created, inputs_hash).await
+    }
+
+    async fn stylus_deploy(
+        &self,
+        crate_dir: &str,
+        wasm: &str,
+        constructor_args: &[String],
+        inputs_hash: B256,
+    ) -> Result<Deployed> {
+        let created = self
+            .tools
+            .stylus_deploy(crate_dir, wasm, constructor_args)?;
+        self.record(created, inputs_hash).await
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let args = Args::parse();
+    /* forge and cargo stylus run inside the crates, so paths handed to them must be absolute */
+    let root = fs::canonicalize(&args.root)
+        .wrap_err_with(|| format!("cannot find {}", args.root.display()))?;
+    let manifest = Manifest::load(&args.manifest)
+        .wrap_err_with(|| format!("cannot load {}", args.manifest.display()))?;
+
+    let provider = ProviderBuilder::new().on_http(args.rpc_url.clone());
+    let chain_id = provider
+        .get_chain_id()
+        .await
+        .wrap_err_with(|| format!("cannot reach {}", args.rpc_url))?;
+    let mut deployments = Deployments::load_or_new(&args.out, chain_id)?;
+    let admin = manifest.admin.unwrap_or(args.private_key.address());
+
+    let deployer = Deployer {
+        provider,
+        tools: Tools {
+            root: root.clone(),
+            rpc_url: args.rpc_url.to_string(),
+            private_key: args.private_key.to_bytes().to_string(),
+            stylus_deployer: args.stylus_deployer,
+        },
+    };
+
+    /* 1. the verifier, shared by every pool, its source carries the vk hash */
+    let vk_hash = vk_hash_from_file(root.join(&manifest.verifier.vk))
+        .wrap_err_with(|| format!("cannot read {}", manifest.verifier.vk))?;
+    let verifier_source = format!("contracts/mixer/{}", manifest.verifier_source());
+    let verifier_inputs = inputs_hash(&read_artifact(&root, &verifier_source)?, &[]);
+
+    let recorded_verifier = deployments.verifier;
+    let verifier = match recorded_verifier {
+        Some(verifier) if deployer.is_current(&verifier, verifier_inputs).await? => {
+            println!("verifier {} is up to date", verifier.address);
+            verifier
+        }
+        _ => {
+            let verifier = deployer
+                .forge_create(&manifest.verifier.contract, verifier_inputs)
+                .await?;
+            println!(
+                "verifier deployed at {} in {}",
+                verifier.address, verifier.tx_hash
+            );
+            deployments.verifier = Some(verifier);
+            deployments.save(&args.out)?;
+            verifier
+        }
+    };
+
+    /* 2. an IMT and a Mixer per pool */
+    let imt_wasm = read_artifact(&root, IMT_WASM)?;
+    let mixer_wasm = read_artifact(&root, MIXER_WASM)?;
+    let imt_args = vec![manifest.tree_depth.to_string()];
+    let imt_inputs = inputs_hash(&imt_wasm, &imt_args);
+
+    for pool in &manifest.pools {
+        let mixer_args = |imt: Address| {
+            mixer_args(&MixerArgs {
+                verifier: verifier.address,
+                imt,
+                admin,
+                vk_hash,
+                proof_size: manifest.verifier.proof_size,
+                circuit_depth: manifest.tree_depth,
+                denomination: pool.denomination,
+            })
+        };
+
+        let recorded = deployments
+            .pools
+            .get(&pool.name)
+            .cloned()
+            .unwrap_or_default();
+        let imt_current = match recorded.imt {
+            Some(imt) => deployer.is_current(&imt, imt_inputs).await?,
+            None => false,
+        };
+        if let (true, Some(imt), Some(mixer)) = (imt_current, recorded.imt, recorded.mixer) {
+            let mixer_inputs = inputs_hash(&mixer_wasm, &mixer_args(imt.address));
+            if deployer.is_current(&mixer, mixer_inputs).await? {
+                println!("pool {} at {} is up to date", pool.name, mixer.address);
+                continue;
+            }
+        }
+
+        /* a tree that ever backed a Mixer holds its leaves, only a fresh one is reused */
+        let imt = match recorded.imt {
+            Some(imt) if imt_current && recorded.mixer.is_none() => imt,
+            _ => {
+                let imt = deployer
+                    .stylus_deploy("contracts/imt", IMT_WASM, &imt_args, imt_inputs)
+                    .await?;
+                println!(
+                    "pool {} IMT deployed at {} in {}",
+                    pool.name, imt.address, imt.tx_hash
+                );
+                deployments.replace_pool(
+                    &pool.name,
+                    PoolDeployment {
+                        denomination: pool.denomination,
+                        imt: Some(imt),
+                        mixer: None,
+                        previous: Vec::new(),
+                    },
+                );
+                deployments.save(&args.out)?;
+                imt
+            }
+        };
+
+        let mixer_args = mixer_args(imt.address);
+        let mixer = deployer
+            .stylus_deploy(
+                "contracts/mixer",
+                MIXER_WASM,
+                &mixer_args,
+                inputs_hash(&mixer_wasm, &mixer_args),
+            )
+            .await?;
+        println!(
+            "pool {} deployed at {} in {}",
+            pool.name, mixer.address, mixer.tx_hash
+        );
+        /* the entry the IMT was recorded in, or reused from */
+        let recorded = deployments
+            .pools
+            .get_mut(&pool.name)
+            .expect("pool IMT is recorded");
+        recorded.denomination = pool.denomination;
+        recorded.mixer = Some(mixer);
+        deployments.save(&args.out)?;
+    }
+
+    Ok(())
+}
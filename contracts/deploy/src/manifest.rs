@@ -0,0 +1,120 @@
+use std::{collections::HashSet, fs, path::Path, str::FromStr};
+
+use alloy::primitives::{utils::parse_ether, Address, U256};
+use serde::{de, Deserialize, Deserializer};
+use stylus_common::circuits::{withdraw_circuit, ULTRA_HONK_PROOF_SIZE};
+
+use crate::DeployError;
+
+/// What to deploy: one verifier shared by every pool, and an IMT plus a `Mixer` per pool.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Manifest {
+    pub tree_depth: u32,
+    /// Admin of every pool, the deployer when unset.
+    pub admin: Option<Address>,
+    pub verifier: VerifierArtifact,
+    pub pools: Vec<Pool>,
+}
+
+/// A pinned verifier and the vk it is pinned to.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct VerifierArtifact {
+    /// `forge create` identifier, relative to `contracts/mixer`.
+    pub contract: String,
+    /// `bb write_vk` output, relative to the repository root.
+    pub vk: String,
+    /// Bytes in a proof the verifier takes, an UltraHonk proof when unset.
+    #[serde(default = "ultra_honk_proof_size")]
+    pub proof_size: u32,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Pool {
+    /// Key of the pool in `deployments.json`.
+    pub name: String,
+    /// Given in ether, e.g. `"0.1"`, kept in wei.
+    #[serde(deserialize_with = "ether")]
+    pub denomination: U256,
+}
+
+/* on-disk form, the verifier defaults to the one scripts/build_circuits.sh writes for the depth */
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ManifestFile {
+    tree_depth: u32,
+    admin: Option<Address>,
+    verifier: Option<VerifierArtifact>,
+    #[serde(default)]
+    pools: Vec<Pool>,
+}
+
+fn ultra_honk_proof_size() -> u32 {
+    ULTRA_HONK_PROOF_SIZE
+}
+
+fn ether<'de, D: Deserializer<'de>>(deserializer: D) -> Result<U256, D::Error> {
+    let amount = String::deserialize(deserializer)?;
+    parse_ether(&amount).map_err(de::Error::custom)
+}
+
+impl Manifest {
+    pub fn load(path: &Path) -> Result<Self, DeployError> {
+        fs::read_to_string(path)?.parse()
+    }
+
+    /// `forge create` source file of the verifier, relative to `contracts/mixer`.
+    pub fn verifier_source(&self) -> &str {
+        self.verifier
+            .contract
+            .split_once(':')
+            .map_or(self.verifier.contract.as_str(), |(source, _)| source)
+    }
+}
+
+impl FromStr for Manifest {
+    type Err = DeployError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let file: ManifestFile = toml::from_str(s)?;
+
+        let circuit = withdraw_circuit(file.tree_depth).ok_or_else(|| {
+            DeployError::Manifest(format!("no withdraw circuit for depth {}", file.tree_depth))
+        })?;
+        let verifier = file.verifier.unwrap_or_else(|| VerifierArtifact {
+            contract: circuit.verifier.to_string(),
+            vk: circuit.vk.to_string(),
+            proof_size: circuit.proof_size,
+        });
+
+        if file.pools.is_empty() {
+            return Err(DeployError::Manifest("no pools".to_string()));
+        }
+        let mut names = HashSet::new();
+        for pool in &file.pools {
+            if pool.name.is_empty() {
+                return Err(DeployError::Manifest("pool without a name".to_string()));
+            }
+            if !names.insert(pool.name.as_str()) {
+                return Err(DeployError::Manifest(format!(
+                    "pool {} is listed twice",
+                    pool.name
+                )));
+            }
+            if pool.denomination.is_zero() {
+                return Err(DeployError::Manifest(format!(
+                    "pool {} has a zero denomination",
+                    pool.name
+                )));
+            }
+        }
+
+        Ok(Self {
+            tree_depth: file.tree_depth,
+            admin: file.admin,
+            verifier,
+            pools: file.pools,
+        })
+    }
+}
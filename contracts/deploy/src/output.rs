@@ -0,0 +1,63 @@
+use alloy::primitives::{Address, B256};
+use serde::Deserialize;
+
+/// A contract creation reported by `forge create` or `cargo stylus deploy`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Created {
+    pub address: Address,
+    pub tx_hash: B256,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ForgeCreateOutput {
+    deployed_to: Address,
+    transaction_hash: B256,
+}
+
+/// Reads `forge create --json` output, or the plain text older forge versions print.
+pub fn parse_forge_create(stdout: &str) -> Option<Created> {
+    let json = stdout
+        .lines()
+        .find_map(|line| serde_json::from_str::<ForgeCreateOutput>(line).ok());
+    if let Some(output) = json {
+        return Some(Created {
+            address: output.deployed_to,
+            tx_hash: output.transaction_hash,
+        });
+    }
+
+    Some(Created {
+        address: field(stdout, "Deployed to:")?.parse().ok()?,
+        tx_hash: field(stdout, "Transaction hash:")?.parse().ok()?,
+    })
+}
+
+/// Reads `cargo stylus deploy` output, colored or not.
+pub fn parse_stylus_deploy(stdout: &str) -> Option<Created> {
+    let stdout = strip_ansi(stdout);
+    Some(Created {
+        address: field(&stdout, "deployed code at address:")?.parse().ok()?,
+        tx_hash: field(&stdout, "deployment tx hash:")?.parse().ok()?,
+    })
+}
+
+fn field<'a>(stdout: &'a str, label: &str) -> Option<&'a str> {
+    stdout
+        .lines()
+        .find_map(|line| line.split_once(label).map(|(_, value)| value.trim()))
+}
+
+/* drops `ESC [ ... m` color sequences */
+fn strip_ansi(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            chars.by_ref().find(|c| c.is_ascii_alphabetic());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
@@ -0,0 +1,110 @@
+use std::{collections::BTreeMap, fs, path::Path};
+
+use alloy::primitives::{keccak256, Address, B256, U256};
+use serde::{Deserialize, Serialize};
+
+use crate::{Created, DeployError};
+
+/// One deployed contract, as written to `deployments.json`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Deployed {
+    pub address: Address,
+    pub tx_hash: B256,
+    /// `keccak256` of the code the node had at `address` right after deployment.
+    pub code_hash: B256,
+    /// [`inputs_hash`] of the artifact and constructor arguments it was deployed from.
+    pub inputs_hash: B256,
+}
+
+impl Deployed {
+    /// `code` is what the node has at `created.address` now.
+    pub fn new(created: Created, code: &[u8], inputs_hash: B256) -> Self {
+        Self {
+            address: created.address,
+            tx_hash: created.tx_hash,
+            code_hash: keccak256(code),
+            inputs_hash,
+        }
+    }
+
+    /// Whether `code`, read from `address` now, is still this deployment of `inputs_hash`.
+    pub fn is_current(&self, code: &[u8], inputs_hash: B256) -> bool {
+        !code.is_empty() && keccak256(code) == self.code_hash && self.inputs_hash == inputs_hash
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PoolDeployment {
+    pub denomination: U256,
+    pub imt: Option<Deployed>,
+    pub mixer: Option<Deployed>,
+    /// Pools this one replaced, oldest first. They may still hold deposits.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub previous: Vec<PoolDeployment>,
+}
+
+/// Everything deployed on one chain, keyed by the manifest's pool names.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Deployments {
+    pub chain_id: u64,
+    pub verifier: Option<Deployed>,
+    pub pools: BTreeMap<String, PoolDeployment>,
+}
+
+impl Deployments {
+    pub fn new(chain_id: u64) -> Self {
+        Self {
+            chain_id,
+            verifier: None,
+            pools: BTreeMap::new(),
+        }
+    }
+
+    /// Records `deployment` as pool `name`. A replaced pool that got as far as its `Mixer` is
+    /// kept in `previous`, a lone IMT never held a leaf and is dropped.
+    pub fn replace_pool(&mut self, name: &str, mut deployment: PoolDeployment) {
+        if let Some(mut replaced) = self.pools.remove(name) {
+            deployment.previous = std::mem::take(&mut replaced.previous);
+            if replaced.mixer.is_some() {
+                deployment.previous.push(replaced);
+            }
+        }
+        self.pools.insert(name.to_string(), deployment);
+    }
+
+    /// Reads `path` if it exists, otherwise starts empty.
+    pub fn load_or_new(path: &Path, chain_id: u64) -> Result<Self, DeployError> {
+        if !path.exists() {
+            return Ok(Self::new(chain_id));
+        }
+        let deployments: Self = serde_json::from_str(&fs::read_to_string(path)?)?;
+        if deployments.chain_id != chain_id {
+            return Err(DeployError::WrongChain {
+                expected: chain_id,
+                actual: deployments.chain_id,
+            });
+        }
+        Ok(deployments)
+    }
+
+    /// Writes to a sibling file first, so a crash never loses what was already deployed.
+    pub fn save(&self, path: &Path) -> Result<(), DeployError> {
+        let tmp = path.with_extension("tmp");
+        fs::write(&tmp, serde_json::to_string_pretty(self)?)?;
+        fs::rename(&tmp, path)?;
+        Ok(())
+    }
+}
+
+/// Identifies what a contract is deployed from: its artifact and the constructor arguments
+/// handed to `forge` or `cargo stylus`.
+pub fn inputs_hash(artifact: &[u8], constructor_args: &[String]) -> B256 {
+    let mut preimage = keccak256(artifact).to_vec();
+    for arg in constructor_args {
+        preimage.extend_from_slice(&keccak256(arg.as_bytes()).0);
+    }
+    keccak256(preimage)
+}
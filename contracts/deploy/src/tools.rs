@@ -0,0 +1,184 @@
+use std::{
+    env,
+    path::{Path, PathBuf},
+    process::{Command, Output},
+};
+
+use alloy::{
+    primitives::{address, Address, B256, U256},
+    signers::local::PrivateKeySigner,
+};
+use stylus_common::{circuits::withdraw_circuit, vk::vk_hash_from_file};
+
+use crate::{parse_forge_create, parse_stylus_deploy, Created, DeployError};
+
+/// Release builds of the IMT and `Mixer`, relative to the repository root.
+pub const IMT_WASM: &str = "contracts/imt/target/wasm32-unknown-unknown/release/stylus_imt.wasm";
+pub const MIXER_WASM: &str =
+    "contracts/mixer/target/wasm32-unknown-unknown/release/stylus_mixer.wasm";
+
+/// JSON-RPC endpoint of the nitro dev node.
+pub const DEV_RPC_URL: &str = "http://localhost:8547";
+/// Pre-funded account of the nitro dev node.
+pub const DEV_PRIVATE_KEY: &str =
+    "0xb6b15c8cb491557369f3c7d2c287b053eb229daa9c22138887752191c9520659";
+/// `StylusDeployer` predeployed on the nitro dev node.
+pub const DEV_STYLUS_DEPLOYER: Address = address!("cEcba2F1DC234f70Dd89F2041029807F8D03A990");
+
+/// Runs `forge create` and `cargo stylus deploy` against one node, signing with one key.
+#[derive(Clone, Debug)]
+pub struct Tools {
+    /// Repository root, absolute since the tools run inside the crates.
+    pub root: PathBuf,
+    pub rpc_url: String,
+    pub private_key: String,
+    /// `StylusDeployer` that runs the IMT and `Mixer` constructors.
+    pub stylus_deployer: Address,
+}
+
+impl Tools {
+    /// The dev node, unless `RPC_URL`, `PRIVATE_KEY` or `DEPLOYER_ADDRESS` say otherwise,
+    /// from the checkout this crate was built in. For tests and local runs.
+    pub fn dev() -> Result<Self, DeployError> {
+        /* contracts/deploy -> repository root */
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .ancestors()
+            .nth(2)
+            .expect("contracts/deploy has a repository root")
+            .to_path_buf();
+        let stylus_deployer = match env::var("DEPLOYER_ADDRESS") {
+            Ok(address) => address.parse().map_err(|_| {
+                DeployError::Tool(format!("DEPLOYER_ADDRESS {address} is not an address"))
+            })?,
+            Err(_) => DEV_STYLUS_DEPLOYER,
+        };
+        Ok(Self {
+            root,
+            rpc_url: env::var("RPC_URL").unwrap_or_else(|_| DEV_RPC_URL.to_string()),
+            private_key: env::var("PRIVATE_KEY").unwrap_or_else(|_| DEV_PRIVATE_KEY.to_string()),
+            stylus_deployer,
+        })
+    }
+
+    /// Address of the account `private_key` signs for.
+    pub fn sender(&self) -> Result<Address, DeployError> {
+        let signer: PrivateKeySigner = self
+            .private_key
+            .parse()
+            .map_err(|_| DeployError::Tool("the private key does not parse".to_string()))?;
+        Ok(signer.address())
+    }
+
+    /// `contract` is a `forge create` identifier, relative to `contracts/mixer`.
+    pub fn forge_create(&self, contract: &str) -> Result<Created, DeployError> {
+        let output = Command::new("forge")
+            .arg("create")
+            .arg(contract)
+            .args(["--rpc-url", &self.rpc_url])
+            .args(["--private-key", &self.private_key])
+            .args(["--broadcast", "--json"])
+            .current_dir(self.root.join("contracts/mixer"))
+            .output()
+            .map_err(|err| DeployError::Tool(format!("cannot run forge: {err}")))?;
+        let stdout = checked_stdout("forge create", output)?;
+        parse_forge_create(&stdout)
+            .ok_or_else(|| DeployError::Tool(format!("forge create printed no address:\n{stdout}")))
+    }
+
+    /// `crate_dir` and `wasm` are relative to the repository root.
+    pub fn stylus_deploy(
+        &self,
+        crate_dir: &str,
+        wasm: &str,
+        constructor_args: &[String],
+    ) -> Result<Created, DeployError> {
+        let output = Command::new("cargo")
+            .args(["stylus", "deploy", "--no-verify"])
+            .args(["--endpoint", &self.rpc_url])
+            .args(["--private-key", &self.private_key])
+            .arg("--wasm-file")
+            .arg(self.root.join(wasm))
+            .args(["--deployer-address", &self.stylus_deployer.to_string()])
+            .arg("--constructor-args")
+            .args(constructor_args)
+            .current_dir(self.root.join(crate_dir))
+            .output()
+            .map_err(|err| DeployError::Tool(format!("cannot run cargo stylus: {err}")))?;
+        let stdout = checked_stdout("cargo stylus deploy", output)?;
+        parse_stylus_deploy(&stdout).ok_or_else(|| {
+            DeployError::Tool(format!("cargo stylus deploy printed no address:\n{stdout}"))
+        })
+    }
+
+    /// A fresh pool on the pinned verifier for `depth`, administered by the sender, so
+    /// nothing but the caller's deposits ends up in its tree. Returns the `Mixer`.
+    pub fn deploy_pool(&self, depth: u32, denomination: U256) -> Result<Address, DeployError> {
+        let circuit = withdraw_circuit(depth)
+            .ok_or_else(|| DeployError::Tool(format!("no withdraw circuit for depth {depth}")))?;
+        let vk_hash = vk_hash_from_file(self.root.join(circuit.vk))?;
+        let verifier = self.forge_create(circuit.verifier)?.address;
+        let imt = self
+            .stylus_deploy("contracts/imt", IMT_WASM, &[depth.to_string()])?
+            .address;
+
+        let args = mixer_args(&MixerArgs {
+            verifier,
+            imt,
+            admin: self.sender()?,
+            vk_hash,
+            proof_size: circuit.proof_size,
+            circuit_depth: depth,
+            denomination,
+        });
+        Ok(self
+            .stylus_deploy("contracts/mixer", MIXER_WASM, &args)?
+            .address)
+    }
+}
+
+/// What the `Mixer` constructor takes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MixerArgs {
+    pub verifier: Address,
+    pub imt: Address,
+    pub admin: Address,
+    pub vk_hash: B256,
+    pub proof_size: u32,
+    pub circuit_depth: u32,
+    pub denomination: U256,
+}
+
+/// `--constructor-args` for `cargo stylus deploy`, in the constructor's order.
+pub fn mixer_args(args: &MixerArgs) -> Vec<String> {
+    vec![
+        args.verifier.to_string(),
+        args.imt.to_string(),
+        args.admin.to_string(),
+        args.vk_hash.to_string(),
+        args.proof_size.to_string(),
+        args.circuit_depth.to_string(),
+        args.denomination.to_string(),
+    ]
+}
+
+/// Stdout of a finished command, or its stderr as the error if it failed.
+pub fn checked_stdout(what: &str, output: Output) -> Result<String, DeployError> {
+    if !output.status.success() {
+        return Err(DeployError::Tool(format!(
+            "{what} exited with {}: {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr)
+        )));
+    }
+    String::from_utf8(output.stdout)
+        .map_err(|_| DeployError::Tool(format!("{what} printed invalid UTF-8")))
+}
+
+/// Reads a build output, relative to the repository root.
+pub fn read_artifact(root: &Path, path: &str) -> Result<Vec<u8>, DeployError> {
+    std::fs::read(root.join(path)).map_err(|_| {
+        DeployError::Tool(format!(
+            "cannot read {path}, run scripts/build_circuits.sh and build the contracts first"
+        ))
+    })
+}
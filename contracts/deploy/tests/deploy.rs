@@ -0,0 +1,72 @@
+#![cfg(feature = "e2e")]
+
+//! Runs `mixer-deploy` against the nitro dev node at `RPC_URL` (http://localhost:8547 by
+//! default). Needs the wasm builds, `scripts/build_circuits.sh` output, forge and cargo-stylus.
+
+use std::{
+    path::{Path, PathBuf},
+    process::{Command, Output},
+};
+
+use alloy::{primitives::uint, providers::ProviderBuilder};
+use eyre::Result;
+use mixer_deploy::{Deployments, DEV_PRIVATE_KEY, DEV_RPC_URL};
+use stylus_mixer_bindings::mixer::IMixer;
+
+fn rpc_url() -> String {
+    std::env::var("RPC_URL").unwrap_or_else(|_| DEV_RPC_URL.to_string())
+}
+
+fn repo_root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .ancestors()
+        .nth(2)
+        .unwrap()
+        .to_path_buf()
+}
+
+fn mixer_deploy(out: &Path) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_mixer-deploy"))
+        .arg(Path::new(env!("CARGO_MANIFEST_DIR")).join("mixer.toml"))
+        .arg("--out")
+        .arg(out)
+        .arg("--root")
+        .arg(repo_root())
+        .args(["--rpc-url", &rpc_url()])
+        .args(["--private-key", DEV_PRIVATE_KEY])
+        .output()
+        .expect("mixer-deploy runs")
+}
+
+#[tokio::test]
+async fn deploys_every_pool_then_skips_them() -> Result<()> {
+    let out = std::env::temp_dir().join(format!("deployments-{}.json", std::process::id()));
+    let _ = std::fs::remove_file(&out);
+
+    let first = mixer_deploy(&out);
+    assert!(
+        first.status.success(),
+        "{}",
+        String::from_utf8_lossy(&first.stderr)
+    );
+    let deployed: Deployments = serde_json::from_str(&std::fs::read_to_string(&out)?)?;
+    assert!(deployed.verifier.is_some());
+    assert_eq!(deployed.pools.len(), 2);
+
+    let provider = ProviderBuilder::new().on_http(rpc_url().parse()?);
+    let pool = &deployed.pools["eth-0.1"];
+    let mixer = IMixer::new(pool.mixer.unwrap().address, &provider);
+    let IMixer::denominationReturn { _0: amount } = mixer.denomination().call().await?;
+    assert_eq!(amount, uint!(100_000_000_000_000_000_U256));
+    let IMixer::imtReturn { _0: imt } = mixer.imt().call().await?;
+    assert_eq!(imt, pool.imt.unwrap().address);
+
+    /* nothing changed, so nothing is sent */
+    let second = mixer_deploy(&out);
+    assert!(second.status.success());
+    let stdout = String::from_utf8_lossy(&second.stdout);
+    assert_eq!(stdout.matches("up to date").count(), 3, "{stdout}");
+    let redeployed: Deployments = serde_json::from_str(&std::fs::read_to_string(&out)?)?;
+    assert_eq!(redeployed, deployed);
+    Ok(())
+}
@@ -0,0 +1,160 @@
+use alloy::primitives::{address, b256, keccak256, uint, B256};
+use mixer_deploy::{
+    inputs_hash, parse_forge_create, parse_stylus_deploy, Created, DeployError, Deployed,
+    Deployments, PoolDeployment,
+};
+
+const CREATED: Created = Created {
+    address: address!("5FbDB2315678afecb367f032d93F642f64180aa3"),
+    tx_hash: b256!("1111111111111111111111111111111111111111111111111111111111111111"),
+};
+
+fn scratch(name: &str) -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join(format!("mixer-deploy-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    dir.join(name)
+}
+
+#[test]
+fn inputs_hash_covers_artifact_and_arguments() {
+    let args = vec!["15".to_string()];
+    let hash = inputs_hash(b"wasm", &args);
+
+    assert_eq!(hash, inputs_hash(b"wasm", &args));
+    assert_ne!(hash, inputs_hash(b"other wasm", &args));
+    assert_ne!(hash, inputs_hash(b"wasm", &["20".to_string()]));
+    /* argument boundaries count, "1" "5" is not "15" */
+    assert_ne!(
+        hash,
+        inputs_hash(b"wasm", &["1".to_string(), "5".to_string()])
+    );
+}
+
+#[test]
+fn deployed_is_current_only_for_the_same_code_and_inputs() {
+    let inputs = inputs_hash(b"wasm", &[]);
+    let deployed = Deployed::new(CREATED, b"code", inputs);
+    assert_eq!(deployed.code_hash, keccak256(b"code"));
+
+    assert!(deployed.is_current(b"code", inputs));
+    assert!(!deployed.is_current(b"other code", inputs));
+    assert!(!deployed.is_current(b"", inputs));
+    assert!(!deployed.is_current(b"code", B256::ZERO));
+}
+
+#[test]
+fn deployments_round_trip() {
+    let path = scratch("round-trip.json");
+    let mut deployments = Deployments::new(412346);
+    let deployed = Deployed::new(CREATED, b"code", B256::ZERO);
+    deployments.verifier = Some(deployed);
+    deployments.pools.insert(
+        "eth-1".to_string(),
+        PoolDeployment {
+            denomination: uint!(1_000_000_000_000_000_000_U256),
+            imt: Some(deployed),
+            mixer: None,
+            previous: Vec::new(),
+        },
+    );
+    deployments.save(&path).unwrap();
+
+    assert_eq!(
+        Deployments::load_or_new(&path, 412346).unwrap(),
+        deployments
+    );
+}
+
+#[test]
+fn replaced_pools_are_kept() {
+    let pool = |seed: u8, mixer: bool| {
+        let deployed = Deployed::new(CREATED, &[seed], B256::ZERO);
+        PoolDeployment {
+            denomination: uint!(1_000_000_000_000_000_000_U256),
+            imt: Some(deployed),
+            mixer: mixer.then_some(deployed),
+            previous: Vec::new(),
+        }
+    };
+    let mut deployments = Deployments::new(412346);
+    deployments.replace_pool("eth-1", pool(1, true));
+    deployments.replace_pool("eth-1", pool(2, true));
+    /* an IMT whose Mixer never got deployed is not worth keeping */
+    deployments.replace_pool("eth-1", pool(3, false));
+    deployments.replace_pool("eth-1", pool(4, true));
+
+    let current = &deployments.pools["eth-1"];
+    assert_eq!(current.imt, pool(4, true).imt);
+    assert_eq!(current.previous, vec![pool(1, true), pool(2, true)]);
+
+    let path = scratch("replaced.json");
+    deployments.save(&path).unwrap();
+    assert_eq!(
+        Deployments::load_or_new(&path, 412346).unwrap(),
+        deployments
+    );
+}
+
+#[test]
+fn deployments_without_history_load() {
+    let path = scratch("no-history.json");
+    let mut deployments = Deployments::new(412346);
+    deployments.replace_pool("eth-1", PoolDeployment::default());
+    deployments.save(&path).unwrap();
+    assert!(!std::fs::read_to_string(&path).unwrap().contains("previous"));
+
+    assert_eq!(
+        Deployments::load_or_new(&path, 412346).unwrap(),
+        deployments
+    );
+}
+
+#[test]
+fn missing_deployments_start_empty() {
+    let deployments = Deployments::load_or_new(&scratch("missing.json"), 412346).unwrap();
+    assert_eq!(deployments, Deployments::new(412346));
+}
+
+#[test]
+fn deployments_from_another_chain_are_refused() {
+    let path = scratch("other-chain.json");
+    Deployments::new(1).save(&path).unwrap();
+
+    let err = Deployments::load_or_new(&path, 412346).unwrap_err();
+    assert!(matches!(
+        err,
+        DeployError::WrongChain {
+            expected: 412346,
+            actual: 1
+        }
+    ));
+}
+
+#[test]
+fn parses_forge_create_json() {
+    let stdout = r#"{"deployer":"0x3f1Eae7D46d88F08fc2F8ed27FCb2AB183EB2d0E","deployedTo":"0x5FbDB2315678afecb367f032d93F642f64180aa3","transactionHash":"0x1111111111111111111111111111111111111111111111111111111111111111"}"#;
+    assert_eq!(parse_forge_create(stdout), Some(CREATED));
+}
+
+#[test]
+fn parses_forge_create_text() {
+    let stdout = "\
+Deployer: 0x3f1Eae7D46d88F08fc2F8ed27FCb2AB183EB2d0E
+Deployed to: 0x5FbDB2315678afecb367f032d93F642f64180aa3
+Transaction hash: 0x1111111111111111111111111111111111111111111111111111111111111111
+";
+    assert_eq!(parse_forge_create(stdout), Some(CREATED));
+    assert_eq!(parse_forge_create("Compiler run failed"), None);
+}
+
+#[test]
+fn parses_colored_stylus_deploy_output() {
+    let stdout = "\
+stripped custom section from user wasm to remove any sensitive data
+contract size: \x1b[1;32m8.1 KB\x1b[0m
+deployed code at address: \x1b[1;94m0x5fbdb2315678afecb367f032d93f642f64180aa3\x1b[0m
+deployment tx hash: \x1b[1;94m0x1111111111111111111111111111111111111111111111111111111111111111\x1b[0m
+";
+    assert_eq!(parse_stylus_deploy(stdout), Some(CREATED));
+    assert_eq!(parse_stylus_deploy("error: insufficient funds"), None);
+}
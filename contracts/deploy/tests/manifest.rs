@@ -0,0 +1,187 @@
+use std::path::Path;
+
+use alloy::primitives::{address, uint};
+use mixer_deploy::{DeployError, Manifest, VerifierArtifact};
+use stylus_common::circuits::ULTRA_HONK_PROOF_SIZE;
+
+fn parse(manifest: &str) -> Result<Manifest, DeployError> {
+    manifest.parse()
+}
+
+#[test]
+fn example_manifest_parses() {
+    let manifest =
+        Manifest::load(&Path::new(env!("CARGO_MANIFEST_DIR")).join("mixer.toml")).unwrap();
+
+    assert_eq!(manifest.tree_depth, 15);
+    assert_eq!(manifest.admin, None);
+    assert_eq!(manifest.pools.len(), 2);
+    assert_eq!(manifest.pools[0].name, "eth-1");
+    assert_eq!(
+        manifest.pools[0].denomination,
+        uint!(1_000_000_000_000_000_000_U256)
+    );
+    assert_eq!(manifest.pools[1].name, "eth-0.1");
+    assert_eq!(
+        manifest.pools[1].denomination,
+        uint!(100_000_000_000_000_000_U256)
+    );
+}
+
+#[test]
+fn verifier_defaults_to_the_pinned_one_for_the_depth() {
+    let manifest = parse(
+        r#"
+        tree_depth = 20
+
+        [[pools]]
+        name = "eth-1"
+        denomination = "1"
+        "#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        manifest.verifier,
+        VerifierArtifact {
+            contract: "src/verifiers/depth_20/PinnedVerifier.sol:PinnedHonkVerifier".to_string(),
+            vk: "circuits/target/depth_20/vk".to_string(),
+            proof_size: ULTRA_HONK_PROOF_SIZE,
+        }
+    );
+    assert_eq!(
+        manifest.verifier_source(),
+        "src/verifiers/depth_20/PinnedVerifier.sol"
+    );
+}
+
+#[test]
+fn verifier_and_admin_can_be_set() {
+    let manifest = parse(
+        r#"
+        tree_depth = 15
+        admin = "0x5FbDB2315678afecb367f032d93F642f64180aa3"
+
+        [verifier]
+        contract = "src/PinnedVerifier.sol:PinnedHonkVerifier"
+        vk = "circuits/target/vk"
+
+        [[pools]]
+        name = "eth-1"
+        denomination = "1"
+        "#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        manifest.admin,
+        Some(address!("5FbDB2315678afecb367f032d93F642f64180aa3"))
+    );
+    assert_eq!(
+        manifest.verifier.contract,
+        "src/PinnedVerifier.sol:PinnedHonkVerifier"
+    );
+    assert_eq!(manifest.verifier.vk, "circuits/target/vk");
+    assert_eq!(manifest.verifier.proof_size, ULTRA_HONK_PROOF_SIZE);
+}
+
+#[test]
+fn verifier_proof_size_can_be_set() {
+    let manifest = parse(
+        r#"
+        tree_depth = 15
+
+        [verifier]
+        contract = "src/Groth16Verifier.sol:Groth16Verifier"
+        vk = "circuits/target/groth16.vk"
+        proof_size = 256
+
+        [[pools]]
+        name = "eth-1"
+        denomination = "1"
+        "#,
+    )
+    .unwrap();
+
+    assert_eq!(manifest.verifier.proof_size, 256);
+}
+
+#[test]
+fn rejects_unsupported_depth() {
+    let err = parse(
+        r#"
+        tree_depth = 12
+
+        [[pools]]
+        name = "eth-1"
+        denomination = "1"
+        "#,
+    )
+    .unwrap_err();
+    assert!(matches!(err, DeployError::Manifest(_)));
+}
+
+#[test]
+fn rejects_missing_and_duplicate_pools() {
+    assert!(matches!(
+        parse("tree_depth = 15"),
+        Err(DeployError::Manifest(_))
+    ));
+
+    let err = parse(
+        r#"
+        tree_depth = 15
+
+        [[pools]]
+        name = "eth-1"
+        denomination = "1"
+
+        [[pools]]
+        name = "eth-1"
+        denomination = "0.1"
+        "#,
+    )
+    .unwrap_err();
+    assert!(matches!(err, DeployError::Manifest(reason) if reason.contains("twice")));
+}
+
+#[test]
+fn rejects_bad_denominations() {
+    let zero = parse(
+        r#"
+        tree_depth = 15
+
+        [[pools]]
+        name = "eth-0"
+        denomination = "0"
+        "#,
+    );
+    assert!(matches!(zero, Err(DeployError::Manifest(_))));
+
+    let garbage = parse(
+        r#"
+        tree_depth = 15
+
+        [[pools]]
+        name = "eth-1"
+        denomination = "one"
+        "#,
+    );
+    assert!(matches!(garbage, Err(DeployError::Toml(_))));
+}
+
+#[test]
+fn rejects_unknown_keys() {
+    let err = parse(
+        r#"
+        tree_depth = 15
+        depth = 15
+
+        [[pools]]
+        name = "eth-1"
+        denomination = "1"
+        "#,
+    )
+    .unwrap_err();
+    assert!(matches!(err, DeployError::Toml(_)));
+}
@@ -0,0 +1,178 @@
+#![cfg_attr(not(any(test, feature = "export-abi")), no_main)]
+extern crate alloc;
+
+use alloc::vec::Vec;
+use stylus_common::{errors::Groth16Errors, vk::vk_hash};
+use stylus_sdk::{
+    abi::Bytes as AbiBytes,
+    alloy_primitives::{address, uint, Address, FixedBytes, U256},
+    prelude::*,
+    storage::{StorageU256, StorageVec},
+};
+
+/* BN254 precompiles (EIP-196 / EIP-197) */
+const EC_ADD: Address = address!("0x0000000000000000000000000000000000000006");
+const EC_MUL: Address = address!("0x0000000000000000000000000000000000000007");
+const EC_PAIRING: Address = address!("0x0000000000000000000000000000000000000008");
+
+/* base field modulus, used to negate A */
+const BASE_FIELD_MODULUS: U256 =
+    uint!(21888242871839275222246405745257275088696311157297823662689037894645226208583_U256);
+/* scalar field modulus, every public input must be below it */
+const SCALAR_FIELD_MODULUS: U256 =
+    uint!(21888242871839275222246405745257275088548364400416034343698204186575808495617_U256);
+
+const G1_WORDS: usize = 2;
+const G2_WORDS: usize = 4;
+
+/*
+ * verifying key layout, one uint256 per word, G2 coordinates in EIP-197 order (imaginary first):
+ *   alpha (G1) || beta (G2) || gamma (G2) || delta (G2) || IC[0] (G1) || ... || IC[n] (G1)
+ */
+const VK_ALPHA: usize = 0;
+const VK_BETA: usize = VK_ALPHA + G1_WORDS;
+const VK_GAMMA: usize = VK_BETA + G2_WORDS;
+const VK_DELTA: usize = VK_GAMMA + G2_WORDS;
+const VK_IC: usize = VK_DELTA + G2_WORDS;
+
+/* proof layout: A (G1) || B (G2, EIP-197 order) || C (G1) */
+const PROOF_WORDS: usize = G1_WORDS + G2_WORDS + G1_WORDS;
+
+#[entrypoint]
+#[storage]
+pub struct Groth16Verifier {
+    vk: StorageVec<StorageU256>,
+}
+
+/* ======================================================================
+ *                               Contract
+ * ====================================================================== */
+#[public]
+impl Groth16Verifier {
+    #[constructor]
+    fn initialize(&mut self, vk: Vec<U256>) -> Result<(), Groth16Errors> {
+        /* at least IC[0], plus a whole number of G1 points */
+        if vk.len() < VK_IC + G1_WORDS || (vk.len() - VK_IC) % G1_WORDS != 0 {
+            return Err(Groth16Errors::invalid_verifying_key());
+        }
+        for word in vk {
+            self.vk.push(word);
+        }
+        Ok(())
+    }
+
+    fn verify(
+        &self,
+        proof: AbiBytes,
+        public_inputs: Vec<FixedBytes<32>>,
+    ) -> Result<bool, Groth16Errors> {
+        if proof.len() != PROOF_WORDS * 32 {
+            return Err(Groth16Errors::invalid_proof_length());
+        }
+        if public_inputs.len() + 1 != (self.vk.len() - VK_IC) / G1_WORDS {
+            return Err(Groth16Errors::invalid_public_inputs_length());
+        }
+
+        /* vk_x = IC[0] + sum(input_i * IC[i + 1]) */
+        let mut vk_x = self.vk_words::<G1_WORDS>(VK_IC);
+        for (i, input) in public_inputs.iter().enumerate() {
+            let scalar = U256::from_be_bytes(input.0);
+            if scalar >= SCALAR_FIELD_MODULUS {
+                return Err(Groth16Errors::public_input_not_in_field());
+            }
+            let ic = self.vk_words::<G1_WORDS>(VK_IC + (i + 1) * G1_WORDS);
+            let term = self.ec_mul(ic, scalar)?;
+            vk_x = self.ec_add(vk_x, term)?;
+        }
+
+        let proof_words: Vec<U256> = proof
+            .as_slice()
+            .chunks(32)
+            .map(U256::from_be_slice)
+            .collect();
+        let a = [proof_words[0], proof_words[1]];
+        let b = &proof_words[G1_WORDS..G1_WORDS + G2_WORDS];
+        let c = &proof_words[G1_WORDS + G2_WORDS..];
+
+        /* e(-A, B) * e(alpha, beta) * e(vk_x, gamma) * e(C, delta) == 1 */
+        let mut input: Vec<U256> = Vec::with_capacity(4 * (G1_WORDS + G2_WORDS));
+        input.extend_from_slice(&negate(a));
+        input.extend_from_slice(b);
+        input.extend_from_slice(&self.vk_words::<G1_WORDS>(VK_ALPHA));
+        input.extend_from_slice(&self.vk_words::<G2_WORDS>(VK_BETA));
+        input.extend_from_slice(&vk_x);
+        input.extend_from_slice(&self.vk_words::<G2_WORDS>(VK_GAMMA));
+        input.extend_from_slice(c);
+        input.extend_from_slice(&self.vk_words::<G2_WORDS>(VK_DELTA));
+
+        let output = self.precompile(EC_PAIRING, &input)?;
+        Ok(output.len() == 32 && U256::from_be_slice(&output) == U256::from(1))
+    }
+
+    /* hash of the constructor's vk words, each as 32 big-endian bytes */
+    fn vk_hash(&self) -> FixedBytes<32> {
+        let mut encoded: Vec<u8> = Vec::with_capacity(self.vk.len() * 32);
+        for i in 0..self.vk.len() {
+            let word = self.vk.get(i).expect("vk index out of bounds");
+            encoded.extend_from_slice(&word.to_be_bytes::<32>());
+        }
+        vk_hash(&encoded)
+    }
+}
+
+/* ======================================================================
+ *                         CONTRACT INTERNAL
+ * ====================================================================== */
+impl Groth16Verifier {
+    fn vk_words<const N: usize>(&self, offset: usize) -> [U256; N] {
+        core::array::from_fn(|i| self.vk.get(offset + i).expect("vk index out of bounds"))
+    }
+
+    fn ec_add(
+        &self,
+        p: [U256; G1_WORDS],
+        q: [U256; G1_WORDS],
+    ) -> Result<[U256; G1_WORDS], Groth16Errors> {
+        let output = self.precompile(EC_ADD, &[p[0], p[1], q[0], q[1]])?;
+        decode_g1(&output)
+    }
+
+    fn ec_mul(
+        &self,
+        p: [U256; G1_WORDS],
+        scalar: U256,
+    ) -> Result<[U256; G1_WORDS], Groth16Errors> {
+        let output = self.precompile(EC_MUL, &[p[0], p[1], scalar])?;
+        decode_g1(&output)
+    }
+
+    fn precompile(&self, precompile: Address, words: &[U256]) -> Result<Vec<u8>, Groth16Errors> {
+        let mut calldata: Vec<u8> = Vec::with_capacity(words.len() * 32);
+        for word in words {
+            calldata.extend_from_slice(&word.to_be_bytes::<32>());
+        }
+        self.vm()
+            .static_call(&self, precompile, &calldata)
+            .map_err(|_| Groth16Errors::precompile_failed())
+    }
+}
+
+fn decode_g1(output: &[u8]) -> Result<[U256; G1_WORDS], Groth16Errors> {
+    if output.len() != G1_WORDS * 32 {
+        return Err(Groth16Errors::precompile_failed());
+    }
+    Ok([
+        U256::from_be_slice(&output[..32]),
+        U256::from_be_slice(&output[32..]),
+    ])
+}
+
+fn negate(point: [U256; G1_WORDS]) -> [U256; G1_WORDS] {
+    if point[0].is_zero() && point[1].is_zero() {
+        return point;
+    }
+    [
+        point[0],
+        BASE_FIELD_MODULUS - (point[1] % BASE_FIELD_MODULUS),
+    ]
+}
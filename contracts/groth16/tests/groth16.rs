@@ -0,0 +1,140 @@
+#![cfg(feature = "e2e")]
+
+use alloy_primitives::{hex, uint, Address, FixedBytes, U256};
+use e2e::{constructor, Account, Revert};
+use eyre::Result;
+use stylus_common::vk::vk_hash;
+use stylus_mixer_bindings::groth16::{self, IGroth16Verifier};
+
+/*
+ * vk and proof for a toy statement with two public inputs (5, 7), built from known scalars:
+ * alpha = 11, beta = 13, gamma = 17, delta = 19, IC = [23, 29, 31], A = 37 G1, B = 41 G2 and
+ * C = (ab - alpha beta - x gamma) / delta G1 with x = 23 + 5 * 29 + 7 * 31
+ */
+const VK: [U256; 20] = [
+    uint!(0x2a14705537b009189da8808651eecdb82482477fe92ac12ca8b71f80fc3d49ef_U256),
+    uint!(0x2df7ee7f243ea8b38e1ddf14029258877a618c779fd4717db6177e19ea67ec38_U256),
+    uint!(0x009edaf0698a8c56f51139588acc094cee3c37d427bb6d2eab830aae529097d1_U256),
+    uint!(0x23ad66f3a7cca9dc75049635faebd124316244b91de5fb2764cd151572a905f7_U256),
+    uint!(0x2700e8a29b7bb45f3022a18a07bdc66d0254559e17cce64e3b4ad21578fcf410_U256),
+    uint!(0x1ad4f87d3b4375a39988ac099b042b1e7c0c715678e4c2bea8905f607cf950f8_U256),
+    uint!(0x227071bba5ff3b47ed8b504bb5b215bc701d7a3259b933bff1a4164eae499c2c_U256),
+    uint!(0x0c51a367b61d3119677b29739ddccbb78002b5558d8f49ff16e299c1b41f8098_U256),
+    uint!(0x08bb188b2a6187bb1e87834c85a6a917763d65b98febf2c45ea339dd77fac415_U256),
+    uint!(0x18fd2fd13be8494c39e8a91325d1ef3ba7d1a205d10788e38bc9e09d9be87769_U256),
+    uint!(0x25407be35f18c6594174374841311466c0e66ff003762448c06bca4fa5e9c54e_U256),
+    uint!(0x15cbba9ab73bc73d0ba4ad132a15cb0c73107a9c19b040c4c73d89f6bf75404d_U256),
+    uint!(0x1edef86c1a42fa85ab6ae8d268a7e9b46890b2130dd83b91c86c504cf1f93fbf_U256),
+    uint!(0x2c750c045112e4ab07f18b12475309cebdcb726bda1ca9948bacd498a28cf411_U256),
+    uint!(0x1e28260f0ee971dec1e84cf81ff2776ad314d2cfb9ef81d4c970620c29b811f1_U256),
+    uint!(0x28fc8a72d4ff12654c3c39dab54eaef9638d28de738959779fcd3e7ac918b396_U256),
+    uint!(0x1605ffc1ea2e1aef15d774d3207176420c5cc454b19b55558562b0c7ddf00a7d_U256),
+    uint!(0x0cf605873faa8028df38ec2d0800d5ddc67f1776338d675491fe87f6bb7354b3_U256),
+    uint!(0x14b4fa251277a6f4cbbfe379a152a976641f58a4a2bffd3b677ea093bdad853c_U256),
+    uint!(0x28ce094a6d16280abcf8d84efa062c85511819dd87d8da255885ce0580ebee36_U256),
+];
+
+const PROOF: [&str; 8] = [
+    "24f253a56d4badbe5f105ae102f14cf23ecb3a3892640ed1edb49c9d9e45d063",
+    "1392ab50e020ade3c6069f16bf09d1ac4ebe686a3063ce392a0ea2b7ec03f6b1",
+    "112481cb92f08c33f3b41dd04d01cccc74ea26dd31f17f3da66b624dd5ccc074",
+    "14550237c376595b16cb8a6107729731c341634464ba1a15c239e2047f0cc083",
+    "2d6a1707745972d09054b5098b7abe2433d727e336a1f9933f9aa14748ad594e",
+    "255420d4a7200beb753a7ea90439f5790e06d7082c75c77ea40771acdc81643e",
+    "0d88cbd2f5b723a6a7e2bbd7cf92a02eb37fea78074f55627bba66fe93310207",
+    "0a7b6d46de569d9f88a126aa933f69998329c40b33e96c4c1b103a2cd94b237c",
+];
+
+#[e2e::test]
+async fn groth16_verify_accepts_valid_proof(alice: Account) -> Result<()> {
+    let verifier = deploy_verifier(&alice).await?;
+    let contract = IGroth16Verifier::new(verifier, &alice.wallet);
+
+    let IGroth16Verifier::verifyReturn { _0: verified } = contract
+        .verify(proof().into(), public_inputs(&[5, 7]))
+        .call()
+        .await?;
+    assert!(verified);
+
+    Ok(())
+}
+
+#[e2e::test]
+async fn groth16_verify_rejects_wrong_public_input(alice: Account) -> Result<()> {
+    let verifier = deploy_verifier(&alice).await?;
+    let contract = IGroth16Verifier::new(verifier, &alice.wallet);
+
+    let IGroth16Verifier::verifyReturn { _0: verified } = contract
+        .verify(proof().into(), public_inputs(&[5, 8]))
+        .call()
+        .await?;
+    assert!(!verified);
+
+    Ok(())
+}
+
+#[e2e::test]
+async fn groth16_verify_rejects_malformed_inputs(alice: Account) -> Result<()> {
+    let verifier = deploy_verifier(&alice).await?;
+    let contract = IGroth16Verifier::new(verifier, &alice.wallet);
+
+    let mut truncated = proof();
+    truncated.pop();
+    let err = contract
+        .verify(truncated.into(), public_inputs(&[5, 7]))
+        .call()
+        .await
+        .expect_err("should revert");
+    assert!(err.reverted_with(groth16::InvalidProofLength {}));
+
+    let err = contract
+        .verify(proof().into(), public_inputs(&[5]))
+        .call()
+        .await
+        .expect_err("should revert");
+    assert!(err.reverted_with(groth16::InvalidPublicInputsLength {}));
+
+    let err = contract
+        .verify(proof().into(), vec![FixedBytes::from(U256::MAX), FixedBytes::ZERO])
+        .call()
+        .await
+        .expect_err("should revert");
+    assert!(err.reverted_with(groth16::PublicInputNotInField {}));
+
+    Ok(())
+}
+
+#[e2e::test]
+async fn groth16_vk_hash_matches_constructor_key(alice: Account) -> Result<()> {
+    let verifier = deploy_verifier(&alice).await?;
+    let contract = IGroth16Verifier::new(verifier, &alice.wallet);
+
+    let encoded: Vec<u8> = VK.iter().flat_map(|word| word.to_be_bytes::<32>()).collect();
+    let IGroth16Verifier::vkHashReturn { _0: hash } = contract.vkHash().call().await?;
+    assert_eq!(hash, vk_hash(&encoded));
+
+    Ok(())
+}
+
+/* ======================================================================
+ *                               INTERNAL HELPERS
+ * ====================================================================== */
+async fn deploy_verifier(alice: &Account) -> Result<Address> {
+    let rcpt = alice
+        .as_deployer()
+        .with_constructor(constructor!(VK.to_vec()))
+        .deploy()
+        .await?;
+    Ok(rcpt.contract_address)
+}
+
+fn proof() -> Vec<u8> {
+    PROOF.iter().flat_map(|word| hex::decode(word).unwrap()).collect()
+}
+
+fn public_inputs(values: &[u64]) -> Vec<FixedBytes<32>> {
+    values
+        .iter()
+        .map(|value| FixedBytes::from(U256::from(*value)))
+        .collect()
+}
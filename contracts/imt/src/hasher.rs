@@ -0,0 +1,178 @@
+use openzeppelin_poseidon::{hash_two_fixed_bytes, hash_with_domain_fixed_bytes, DOMAIN_NODE};
+use stylus_sdk::alloy_primitives::{fixed_bytes, keccak256, FixedBytes};
+
+/// Two-to-one compression and empty-subtree values the tree is built from.
+pub trait MerkleHasher {
+    fn hash_pair(left: FixedBytes<32>, right: FixedBytes<32>) -> FixedBytes<32>;
+
+    /// Root of an empty subtree of height `level` (`zero(0)` is the empty leaf).
+    fn zero(level: u32) -> FixedBytes<32>;
+}
+
+/* ======================================================================
+ *                               Poseidon2
+ * ====================================================================== */
+/// Matches `poseidon2::Poseidon2::hash([left, right], 2)` in `circuits/src/imt.nr`.
+pub struct Poseidon2Hasher;
+
+/* level i is hash(zero(i - 2), zero(i - 1)) from level 2 up, the same table scripts/js/merkleTree.js uses */
+pub const ZERO_LEAVES: [FixedBytes<32>; 32] = [
+    fixed_bytes!("0x168db4aa1d4e4bf2ee46eb882e1c38a7de1a4da47e17b207a5494a14605ae38e"),
+    fixed_bytes!("0x257a568bdc9cc663b2cf123f7d7b6c5eedd5a312d2792305352e09f1733a56b5"),
+    fixed_bytes!("0x25b9b4ff326c7783ce7a3ae1503dce4552211bdfb510808e215f4227da087023"),
+    fixed_bytes!("0x0aa6931cdcc4482ac0a053cf28a380154ce6500cc02087ea9c8b71ffe597ea59"),
+    fixed_bytes!("0x20cb91532baf018f130fc336438e923c9f2de935efdd4325a8c7eda10d5c5520"),
+    fixed_bytes!("0x1ca38bd416b196d58f59133a826b64ec9f697e854ea8f10b9337c74365e79068"),
+    fixed_bytes!("0x1d09e36bc1db6b3e83298d8045cda770ca55eaeff1da0d44e684647653a1a185"),
+    fixed_bytes!("0x266afaeab47b775c2275cde3248b68503f3079eca6461c1907fec9b979afe9ff"),
+    fixed_bytes!("0x22794d6b26dd7398aa4f3c7d58ed5ea48f698ff4b229d21442846d8cd70959b1"),
+    fixed_bytes!("0x05e208e2e76bcfe61cb39a79c0e263ee7874ba71cd64bc54e8bafd470055c6ef"),
+    fixed_bytes!("0x26c093f627ffb8a25ab933cf64dd4f29dae2b103b48db3bf619f0dc39b298222"),
+    fixed_bytes!("0x058676dab63180e26827fc2d2feccd6b191aa0e6589aa589398addb28e71a011"),
+    fixed_bytes!("0x0f9ba00d2e0001bed485a0a1c2416e1aa2c86bf7c859c6707d0169170678f174"),
+    fixed_bytes!("0x06fa06667c34201bcd5f6334de6b8c0b22b5f6bc57e401ed7660c40afd880b26"),
+    fixed_bytes!("0x26ec3289eb146620b56807d58b3fae45adb7d7dfdc0a65194333e6dc2aa3de9e"),
+    fixed_bytes!("0x2d2f60a05d456896411242de0eff23497c889f762e2eb5db0a07df329f452a92"),
+    fixed_bytes!("0x1ee903a4eac57310c624c0e30f2bd083eb68a595306df83b1111db0fffce45ea"),
+    fixed_bytes!("0x05f96e491710c7e1d65207b36e0031c1de403eb32753de2489e8abce4c2e86ff"),
+    fixed_bytes!("0x2375b170da8f212cf2b23538990cb6a2e319c50eee555a3fcbed25946326be6c"),
+    fixed_bytes!("0x14307dca3f2b6224ff19c5c0a19129c5fa79d48c645ebb1c5302cb41a131e72a"),
+    fixed_bytes!("0x051e91aeea86b05dcd2b5218126fb3cf3990c81d53f0947028a933026eb94b3a"),
+    fixed_bytes!("0x089fcba3da069d909de7d9ea88e8c7bb49d5934947e53a6a9c1f5eb662c27f2e"),
+    fixed_bytes!("0x1f36ef937da9689c0f70364036741031e0f2b87eef22c735019371d4396c6b3f"),
+    fixed_bytes!("0x21ad2f97aebd9fcd4c41433e5db8d4f64c863360ddecc343cd500b62180134ee"),
+    fixed_bytes!("0x0acf88c5a38c8f9279a313844e7af8026eeeaceeabad6b582d9f3fb123b62d70"),
+    fixed_bytes!("0x1175dd405c3e38670903ef572cfa3ccf441a7cf70c88f8fd8be1b46f33915970"),
+    fixed_bytes!("0x1881fadef7baed7dab5ec3999ade81286cd070fdf234fc1b451e34a3bc4231c6"),
+    fixed_bytes!("0x2219be343fd079048377a2560467d13407dd0e99dc810aa82aa014c617dd1b9e"),
+    fixed_bytes!("0x20311205434cb48873977f5a36fc509939eae9e64cf66395df8ea03264c7798b"),
+    fixed_bytes!("0x29084b176141114483fc6c6eb4444a01c644361a2c004a7efd2e252d8cce4e70"),
+    fixed_bytes!("0x0bad0880fc04d5a9b2fccef498448214d50b92a973390a93ca7533ff02fc7721"),
+    fixed_bytes!("0x13b6403089d691e83af7392d8e9bddd76e83d8204b2475fc6c60679bd338dea8"),
+];
+
+impl MerkleHasher for Poseidon2Hasher {
+    fn hash_pair(left: FixedBytes<32>, right: FixedBytes<32>) -> FixedBytes<32> {
+        hash_two_fixed_bytes(left, right)
+    }
+
+    fn zero(level: u32) -> FixedBytes<32> {
+        ZERO_LEAVES
+            .get(level as usize)
+            .copied()
+            .expect("index out of bounds")
+    }
+}
+
+/* ======================================================================
+ *                         Poseidon2 (domain separated)
+ * ====================================================================== */
+/// Poseidon2 with the node domain tag, matching `circuits/domain/src/imt.nr`.
+pub struct Poseidon2DomainHasher;
+
+/* same empty leaf as the Poseidon2 tree, each level is the node-domain hash of the one below */
+pub const DOMAIN_ZERO_LEAVES: [FixedBytes<32>; 32] = [
+    fixed_bytes!("0x168db4aa1d4e4bf2ee46eb882e1c38a7de1a4da47e17b207a5494a14605ae38e"),
+    fixed_bytes!("0x2a7365ec00661af1a2e02ec2d13241b03d1b8515ddcaac1d10afd9f0c106a660"),
+    fixed_bytes!("0x1defa2d3291be09ef0f1ddc440f7040c349e011f67d2336dad69d3cfd7e1076c"),
+    fixed_bytes!("0x21e02ad58040f2110e9aae2178feb19e85641724e5f586fd8be46610f218f0b4"),
+    fixed_bytes!("0x0b88bce5fd7ae3260070547d50279ff62e0ee5eec44f4267fc2fa30c681a7262"),
+    fixed_bytes!("0x1a562d5d8552feeee32f4f77e5f60037f200ce30088b561024f0c51ee0b5f60f"),
+    fixed_bytes!("0x262c1dc1e718923880302c4b6f463a39090483d6e6104c05b01cce9949dbeb92"),
+    fixed_bytes!("0x2ed663eb3a834edfaa6304a8838127e3e9a3980112fbf6dd05b9be4c06fb3cc3"),
+    fixed_bytes!("0x1ccc6cfbad5b0ff8973fa478ea91a94ec955efc8219a8a392e63f6efeedfa5a9"),
+    fixed_bytes!("0x02a00dd5c41ab291a2384117caa2c1e32af33583776d6e0f3ca8d86fd59c6d25"),
+    fixed_bytes!("0x1d543aad8c1fe28e4ec2c6660bcfd1216aeb71be5f249c62b18b78aa6f5e184e"),
+    fixed_bytes!("0x2b6f34edac2971e42b6c3476e9e08671a8598a41e3dd3facad2fa2dc70147b8e"),
+    fixed_bytes!("0x01f8fbf79445c1cce24e5885dcb160d526b2c15b9700d8e44ec0fd9f54174450"),
+    fixed_bytes!("0x275ac4a1738070869d4956577d9347d76209af62fcde38a3367985783820c365"),
+    fixed_bytes!("0x252998f4e55333077f5cadd8b2528bbe22d0e51a4091056afe765e43c968ce49"),
+    fixed_bytes!("0x206dc24bf48cfff3bddc2708882f0f51746c2724d0969cd7510dd3c34ed0e729"),
+    fixed_bytes!("0x25776edfc2e84ff2661bd0d95dfe30fc7a102aaaee521b57f6a7d5801df5ca82"),
+    fixed_bytes!("0x10a837ab83f1d71628b0281141261905e97ef658b04f5d40d90c45a480c8e37d"),
+    fixed_bytes!("0x2876dc8b6c6b1a60ff91fd6c5685420b4332b3de0b3cade918d471a738bbe166"),
+    fixed_bytes!("0x264f5233755a658ff581bba29a15646877af0b2911a284f7c406bbaf4dcb6518"),
+    fixed_bytes!("0x208d6a896176b2480aa69f4c260b31cafc7cc4ece6e3df6dc10b7d639ed57f4b"),
+    fixed_bytes!("0x16cfaae5c35b2496e8638559c1ac9f664d2c5c1508760c2dbcb241eaa0c67836"),
+    fixed_bytes!("0x2edf7ca6d452cc7230caae28aaa1b47fd0282812f5eeb05160374705c84239cc"),
+    fixed_bytes!("0x0a70883ee74dccdeb08cc5e5ace0a1226d90c70e8ed792acee8232f1f458fe7c"),
+    fixed_bytes!("0x28e97aa1bd87c044844c9acf638fca24c59e0a6169a6a901645c4ad1937444b7"),
+    fixed_bytes!("0x230500a029ba9ea44ee971ab67d6ed9c1cfa6e32523fe2e8fa0b15a5a3a8cb9c"),
+    fixed_bytes!("0x2cc0101f74035f692af9fee76783d29277a3bae69756f98b50399168e6570df3"),
+    fixed_bytes!("0x14ae223e6df83054d84a8199c9c17804fea9dac2e763b99fcce2181079963c6b"),
+    fixed_bytes!("0x2ef3632fae4fa49567fbb5bbae12363b8b768391c0c5e5c6ab66460dc5134029"),
+    fixed_bytes!("0x0ec98a23dd2f4f4319c10cbd4caa61340016bffc43e2000c942c75095da09c19"),
+    fixed_bytes!("0x0a8e4837eabb4e590e868e5178de7fcf1c9683a74d4fe5cf0e53244e4fecee37"),
+    fixed_bytes!("0x123b6f0ad4a2da4d128a1265f9c0f96a026319f5ef02a955c1557edb0972972c"),
+];
+
+impl MerkleHasher for Poseidon2DomainHasher {
+    fn hash_pair(left: FixedBytes<32>, right: FixedBytes<32>) -> FixedBytes<32> {
+        hash_with_domain_fixed_bytes(DOMAIN_NODE, &[left, right])
+    }
+
+    fn zero(level: u32) -> FixedBytes<32> {
+        DOMAIN_ZERO_LEAVES
+            .get(level as usize)
+            .copied()
+            .expect("index out of bounds")
+    }
+}
+
+/* ======================================================================
+ *                               Keccak256
+ * ====================================================================== */
+/// `keccak256(left ++ right)`, for circuits that build their tree with Keccak.
+pub struct Keccak256Hasher;
+
+/* same empty leaf as the Poseidon2 tree, each level is keccak256(z ++ z) of the one below */
+pub const KECCAK_ZERO_LEAVES: [FixedBytes<32>; 32] = [
+    fixed_bytes!("0x168db4aa1d4e4bf2ee46eb882e1c38a7de1a4da47e17b207a5494a14605ae38e"),
+    fixed_bytes!("0xe811cb16a1569b607a9a649485dd805c5a82a5288c2d819bd10283e873664d38"),
+    fixed_bytes!("0x0c462e13f32b47a1af38fd797770d7428fe937673b62190560aae57709bad850"),
+    fixed_bytes!("0xa9ec71cfefd51f2db6a472983df4be36f23b2313f312fa564690740c9630167a"),
+    fixed_bytes!("0xa9bc90678b30bbedd581a14c40f75409ebc3f2541849ab4dcfe5f1102e37b4c8"),
+    fixed_bytes!("0xd311b9522bcb117fa898fba0b1087cc2fcf2140446d81996251ab2f69ab63dde"),
+    fixed_bytes!("0xb8f891aeb9d534485f0bac20353f2e7915b5ad70189f1a20420d2feafbcee6c8"),
+    fixed_bytes!("0x652c7031d70c80050fd50bdfdc43f7d7112527ab43b1b3b317c50f17d49ffb26"),
+    fixed_bytes!("0x67537fb89966e106fe16c529d63297b9a63d90fe85cf24d6ed34cc862d31432e"),
+    fixed_bytes!("0x787523ddf9a8a3c4d94089d0dc3f9df7a969a0b7eb98376a7bb7c7f2e8a97023"),
+    fixed_bytes!("0xa839c8d3d68392d5d62c165fd7fb872a4c16d42f1197d7a4f364df9084fd476a"),
+    fixed_bytes!("0x2b4901c8be8f7d5634b93918c8d941752509c536228d68e856d027172a58834f"),
+    fixed_bytes!("0xdd22d0be6752a1ac43d88d5216107b88bc0d95911a36fd402a4e23dde395009e"),
+    fixed_bytes!("0xeac0209ab1d335e4cf3f03ac2101be560fe9c06ceb89e7b5e6b23d04765bc88f"),
+    fixed_bytes!("0x820978a4a51d5644051337a5655a4bc7d5f56ed41b13d401fe485954650b5bf9"),
+    fixed_bytes!("0x5c1b4eced9321421e0b5e2ffdaf2aafeed35611654e01d307929343456c4f915"),
+    fixed_bytes!("0xe103d4d7fa6fd5ab0e85dfdbdef1f979089dbc74a5e62369a14c618081e076ff"),
+    fixed_bytes!("0xa35ced1e112f65ab79a9409f19c8d6da2e4e23acb8838033305f42f868bfe256"),
+    fixed_bytes!("0x5f90eee94f1dedaf087134705e5b11ad2eb1cec1d81604de7812fede6e0426fe"),
+    fixed_bytes!("0x87ce3735e7e7b0ebb5bc3a297d218e31448726765751fc289b1705169f5b3672"),
+    fixed_bytes!("0x0d06c5a1efb653fe454f296e03d21cacd4a042df1541b99614462b61f491eee4"),
+    fixed_bytes!("0xd67a882bd9524b082e39085611ed0bd99b0315bbef220da2cfd0dc5dda5eee08"),
+    fixed_bytes!("0xd800179a037814d1332c3c6c75e22cc00616da5c6424557b1a5228a758ed4268"),
+    fixed_bytes!("0x91aabe397887875c97c92e80fa28fe33ba234b06408125856f6106c89f4ec059"),
+    fixed_bytes!("0x06b5560af9b03ca6aa14ea5e8c51db9760783f8ba1d6ca5b3935783e45d1774a"),
+    fixed_bytes!("0xa7952a19d95a122c80959c631524cf24478df2dd052f234d9a031d7288c536b6"),
+    fixed_bytes!("0xf854e45369f979ca08c43ca61b2841963c6a24a90b81d02a9eead6777d48a89f"),
+    fixed_bytes!("0xd9cb1222efd9a0a67f3513d33b760915e211f4031cf3bafc8f4b21403a2cfb26"),
+    fixed_bytes!("0xd912974e0f9c815cb6c7464ccd37ec437f100e290a1641f7c002ffcff513a355"),
+    fixed_bytes!("0xc6c5de913ed9e11fef07c1058db70cb8a3f05994c8a821af57555a109838d419"),
+    fixed_bytes!("0xa4393de630a4a9589a118de0648f6ddb5f89ca45351972b44a99490348fdf5a0"),
+    fixed_bytes!("0xe0d09d096a946c9c2787e97f3b5519978d871b53e7bae9078581f462ebed5ea6"),
+];
+
+impl MerkleHasher for Keccak256Hasher {
+    fn hash_pair(left: FixedBytes<32>, right: FixedBytes<32>) -> FixedBytes<32> {
+        let mut preimage = [0u8; 64];
+        preimage[..32].copy_from_slice(left.as_slice());
+        preimage[32..].copy_from_slice(right.as_slice());
+        keccak256(preimage)
+    }
+
+    fn zero(level: u32) -> FixedBytes<32> {
+        KECCAK_ZERO_LEAVES
+            .get(level as usize)
+            .copied()
+            .expect("index out of bounds")
+    }
+}
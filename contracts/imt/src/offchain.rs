@@ -0,0 +1,198 @@
+use core::marker::PhantomData;
+use std::collections::{HashMap, VecDeque};
+
+use stylus_sdk::alloy_primitives::FixedBytes;
+
+use crate::hasher::{MerkleHasher, Poseidon2Hasher};
+use crate::{MAX_DEPTH, ROOT_HISTORY_SIZE_U32};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OffchainTreeError {
+    InvalidDepth,
+    TreeIsFull,
+}
+
+/// Authentication path of one leaf, in the shape `circuits/src/main.nr` takes it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MerkleProof {
+    pub leaf: FixedBytes<32>,
+    pub index: u32,
+    pub root: FixedBytes<32>,
+    /// Sibling at each level, leaf level first.
+    pub siblings: Vec<FixedBytes<32>>,
+    /// Whether the path node at each level is the left child.
+    pub is_even: Vec<bool>,
+}
+
+impl MerkleProof {
+    /// Recomputes the root from the leaf and path.
+    pub fn compute_root<H: MerkleHasher>(&self) -> FixedBytes<32> {
+        self.siblings
+            .iter()
+            .zip(self.is_even.iter())
+            .fold(self.leaf, |node, (sibling, is_even)| {
+                if *is_even {
+                    H::hash_pair(node, *sibling)
+                } else {
+                    H::hash_pair(*sibling, node)
+                }
+            })
+    }
+}
+
+/// Host-side copy of the `IMT` contract: same hashing, same empty subtrees, same root history.
+///
+/// Unlike the contract it keeps every node, so it can produce proofs for any inserted leaf.
+pub struct OffchainTree<H: MerkleHasher = Poseidon2Hasher> {
+    depth: u32,
+    /* nodes[level][i] is the i-th non-empty node of that level, leaves at level 0 */
+    nodes: Vec<Vec<FixedBytes<32>>>,
+    indices: HashMap<FixedBytes<32>, u32>,
+    roots: VecDeque<FixedBytes<32>>,
+    _hasher: PhantomData<H>,
+}
+
+impl<H: MerkleHasher> OffchainTree<H> {
+    pub fn new(depth: u32) -> Result<Self, OffchainTreeError> {
+        if depth == 0 || depth > MAX_DEPTH {
+            return Err(OffchainTreeError::InvalidDepth);
+        }
+
+        let mut roots = VecDeque::with_capacity(ROOT_HISTORY_SIZE_U32 as usize);
+        roots.push_back(H::zero(depth));
+
+        Ok(Self {
+            depth,
+            nodes: vec![Vec::new(); depth as usize + 1],
+            indices: HashMap::new(),
+            roots,
+            _hasher: PhantomData,
+        })
+    }
+
+    /// Builds a tree by inserting `leaves` in order.
+    pub fn with_leaves(
+        depth: u32,
+        leaves: impl IntoIterator<Item = FixedBytes<32>>,
+    ) -> Result<Self, OffchainTreeError> {
+        let mut tree = Self::new(depth)?;
+        for leaf in leaves {
+            tree.insert(leaf)?;
+        }
+        Ok(tree)
+    }
+
+    /// Appends `leaf` and returns its index, like `IMT::insert`.
+    pub fn insert(&mut self, leaf: FixedBytes<32>) -> Result<u32, OffchainTreeError> {
+        let index = self.len();
+        if u64::from(index) == 1u64 << self.depth {
+            return Err(OffchainTreeError::TreeIsFull);
+        }
+
+        self.nodes[0].push(leaf);
+        /* a repeated leaf keeps its first index, the contract never accepts one anyway */
+        self.indices.entry(leaf).or_insert(index);
+
+        let mut current_index = index;
+        let mut current_hash = leaf;
+        for level in 0..self.depth {
+            current_hash = if current_index & 1 == 0 {
+                H::hash_pair(current_hash, H::zero(level))
+            } else {
+                H::hash_pair(self.node(level, current_index - 1), current_hash)
+            };
+            current_index >>= 1;
+            self.set_node(level + 1, current_index, current_hash);
+        }
+
+        if self.roots.len() == ROOT_HISTORY_SIZE_U32 as usize {
+            self.roots.pop_front();
+        }
+        self.roots.push_back(current_hash);
+
+        Ok(index)
+    }
+
+    pub fn depth(&self) -> u32 {
+        self.depth
+    }
+
+    /// Number of inserted leaves, which is also the next leaf index.
+    pub fn len(&self) -> u32 {
+        self.nodes[0].len() as u32
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes[0].is_empty()
+    }
+
+    pub fn root(&self) -> FixedBytes<32> {
+        *self.roots.back().expect("root history is never empty")
+    }
+
+    /// Roots the contract still accepts, oldest first.
+    pub fn roots(&self) -> impl Iterator<Item = &FixedBytes<32>> {
+        self.roots.iter()
+    }
+
+    pub fn is_known_root(&self, root: FixedBytes<32>) -> bool {
+        root != FixedBytes::ZERO && self.roots.contains(&root)
+    }
+
+    pub fn leaf(&self, index: u32) -> Option<FixedBytes<32>> {
+        self.nodes[0].get(index as usize).copied()
+    }
+
+    pub fn index_of(&self, leaf: FixedBytes<32>) -> Option<u32> {
+        self.indices.get(&leaf).copied()
+    }
+
+    /// The contract's `cached_subtrees`: the latest left node at each level, zero if none yet.
+    pub fn frontier(&self) -> Vec<FixedBytes<32>> {
+        let Some(last) = self.len().checked_sub(1) else {
+            return vec![FixedBytes::ZERO; self.depth as usize];
+        };
+        (0..self.depth)
+            .map(|level| self.node(level, (last >> level) & !1))
+            .collect()
+    }
+
+    /// Proof against the current root, `None` if nothing was inserted at `index`.
+    pub fn proof(&self, index: u32) -> Option<MerkleProof> {
+        let leaf = self.leaf(index)?;
+
+        let mut siblings = Vec::with_capacity(self.depth as usize);
+        let mut is_even = Vec::with_capacity(self.depth as usize);
+        let mut current_index = index;
+        for level in 0..self.depth {
+            siblings.push(self.node(level, current_index ^ 1));
+            is_even.push(current_index & 1 == 0);
+            current_index >>= 1;
+        }
+
+        Some(MerkleProof {
+            leaf,
+            index,
+            root: self.root(),
+            siblings,
+            is_even,
+        })
+    }
+
+    /* nodes right of the last insertion are empty subtrees */
+    fn node(&self, level: u32, index: u32) -> FixedBytes<32> {
+        self.nodes[level as usize]
+            .get(index as usize)
+            .copied()
+            .unwrap_or_else(|| H::zero(level))
+    }
+
+    fn set_node(&mut self, level: u32, index: u32, value: FixedBytes<32>) {
+        let nodes = &mut self.nodes[level as usize];
+        if (index as usize) < nodes.len() {
+            nodes[index as usize] = value;
+        } else {
+            nodes.push(value);
+        }
+    }
+}
@@ -1,13 +1,10 @@
-#![cfg(feature = "e2e")]
+#![cfg(all(feature = "e2e", feature = "poseidon2"))]
 
-use alloy_primitives::{hex, uint, FixedBytes};
+use alloy_primitives::{hex, uint, Address, FixedBytes, U256};
 use e2e::{constructor, Account};
 use eyre::Result;
-use serde::Deserialize;
-use std::{path::PathBuf, process::Command};
-
-use crate::abi::IMTAbi;
-mod abi;
+use stylus_mixer_client::{Deployment, Note};
+use stylus_mixer_bindings::imt::IIMT;
 
 #[e2e::test]
 async fn imt_insert_works(alice: Account) -> Result<()> {
@@ -17,13 +14,13 @@ async fn imt_insert_works(alice: Account) -> Result<()> {
         .deploy()
         .await?
         .contract_address;
-    let contract = IMTAbi::new(contract_addr, &alice.wallet);
+    let contract = IIMT::new(contract_addr, &alice.wallet);
 
     /* generate commitment */
     let (commitment, nullifier, secret) = generate_commitment()?;
 
     /* insert commitment */
-    let IMTAbi::insertReturn { _0: index } = contract.insert(commitment).call().await?;
+    let IIMT::insertReturn { _0: index } = contract.insert(commitment).call().await?;
     assert_eq!(index, 0);
     Ok(())
 }
@@ -36,12 +33,12 @@ async fn imt_zeros_match_constants(alice: Account) -> Result<()> {
         .deploy()
         .await?
         .contract_address;
-    let contract = IMTAbi::new(contract_addr, &alice.wallet);
+    let contract = IIMT::new(contract_addr, &alice.wallet);
 
-    let IMTAbi::zerosReturn { z: z0 } = contract.zeros(uint!(0_U256)).call().await?;
-    let IMTAbi::zerosReturn { z: z1 } = contract.zeros(uint!(1_U256)).call().await?;
-    let IMTAbi::zerosReturn { z: z2 } = contract.zeros(uint!(2_U256)).call().await?;
-    let IMTAbi::zerosReturn { z: z10 } = contract.zeros(uint!(10_U256)).call().await?;
+    let IIMT::zerosReturn { _0: z0 } = contract.zeros(uint!(0_U256)).call().await?;
+    let IIMT::zerosReturn { _0: z1 } = contract.zeros(uint!(1_U256)).call().await?;
+    let IIMT::zerosReturn { _0: z2 } = contract.zeros(uint!(2_U256)).call().await?;
+    let IIMT::zerosReturn { _0: z10 } = contract.zeros(uint!(10_U256)).call().await?;
 
     let e0 = {
         let mut arr = [0u8; 32];
@@ -88,57 +85,57 @@ async fn imt_is_known_root_zero_is_false(alice: Account) -> Result<()> {
         .deploy()
         .await?
         .contract_address;
-    let contract = IMTAbi::new(contract_addr, &alice.wallet);
+    let contract = IIMT::new(contract_addr, &alice.wallet);
 
-    let IMTAbi::isKnownRootReturn { known } =
+    let IIMT::isKnownRootReturn { _0: known } =
         contract.isKnownRoot(FixedBytes::<32>::ZERO).call().await?;
     assert!(!known);
 
     Ok(())
 }
 
-/* ======================================================================
- *                               INTERNAL HELPERS
- * ====================================================================== */
-#[derive(Deserialize)]
-struct CommitmentResponse {
-    commitment: String,
-    nullifier: String,
-    secret: String,
-}
+#[e2e::test]
+async fn imt_supports_depth_31(alice: Account) -> Result<()> {
+    let contract_addr = alice
+        .as_deployer()
+        .with_constructor(constructor!(uint!(31_U256)))
+        .deploy()
+        .await?
+        .contract_address;
+    let contract = IIMT::new(contract_addr, &alice.wallet);
 
-fn generate_commitment() -> eyre::Result<(FixedBytes<32>, FixedBytes<32>, FixedBytes<32>)> {
-    let root = repo_root();
-    let script = root.join("scripts/js/generateCommitment.ts");
-    let output = Command::new("npx")
-        .args(["tsx", script.to_str().expect("valid script path")])
-        .current_dir(&root)
-        .output()?;
-
-    let s = String::from_utf8(output.stdout)?;
-    let resp: CommitmentResponse = serde_json::from_str(&s)?;
-    let commitment = hex_to_fixed_bytes(&resp.commitment)?;
-    let nullifier = hex_to_fixed_bytes(&resp.nullifier)?;
-    let secret = hex_to_fixed_bytes(&resp.secret)?;
-
-    Ok((commitment, nullifier, secret))
+    let IIMT::getDepthReturn { _0: depth } = contract.getDepth().call().await?;
+    assert_eq!(depth, 31);
+
+    let IIMT::zerosReturn { _0: z } = contract.zeros(uint!(31_U256)).call().await?;
+    assert_eq!(
+        z,
+        FixedBytes::<32>::from(hex!(
+            "0bad0880fc04d5a9b2fccef498448214d50b92a973390a93ca7533ff02fc7721"
+        ))
+    );
+
+    Ok(())
 }
 
-fn hex_to_fixed_bytes(s: &str) -> eyre::Result<FixedBytes<32>> {
-    let bytes = alloy::hex::decode(s)?;
-    let arr: [u8; 32] = bytes
-        .as_slice()
-        .try_into()
-        .map_err(|_| eyre::eyre!("expected 32-byte hex string"))?;
-    Ok(FixedBytes::<32>::from(arr))
+#[e2e::test]
+async fn imt_rejects_depth_above_31(alice: Account) -> Result<()> {
+    let deployed = alice
+        .as_deployer()
+        .with_constructor(constructor!(uint!(32_U256)))
+        .deploy()
+        .await;
+    assert!(deployed.is_err());
+
+    Ok(())
 }
 
-fn repo_root() -> PathBuf {
-    /* contracts/imt -> project root */
-    let crate_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
-    crate_dir
-        .parent()
-        .and_then(|p| p.parent())
-        .unwrap()
-        .to_path_buf()
+/* ======================================================================
+ *                               INTERNAL HELPERS
+ * ====================================================================== */
+fn generate_commitment() -> eyre::Result<(FixedBytes<32>, FixedBytes<32>, FixedBytes<32>)> {
+    /* only the commitment is used here, so the deployment never has to match */
+    let deployment = Deployment::eth(U256::ZERO, 0, Address::ZERO);
+    let note = Note::random(deployment, &mut rand::thread_rng());
+    Ok((note.commitment(), note.nullifier(), note.secret()))
 }
@@ -0,0 +1,40 @@
+#![cfg(all(feature = "e2e", feature = "poseidon2-domain"))]
+
+use alloy_primitives::{fixed_bytes, uint, FixedBytes, U256};
+use e2e::{constructor, Account};
+use eyre::Result;
+use stylus_mixer_bindings::imt::IIMT;
+
+#[e2e::test]
+async fn imt_domain_zeros_match_constants(alice: Account) -> Result<()> {
+    let contract_addr = alice
+        .as_deployer()
+        .with_constructor(constructor!(uint!(15_U256)))
+        .deploy()
+        .await?
+        .contract_address;
+    let contract = IIMT::new(contract_addr, &alice.wallet);
+
+    /* the empty leaf is shared with the untagged tree, every level above it differs */
+    let expected: [(u32, FixedBytes<32>); 3] = [
+        (
+            0,
+            fixed_bytes!("0x168db4aa1d4e4bf2ee46eb882e1c38a7de1a4da47e17b207a5494a14605ae38e"),
+        ),
+        (
+            1,
+            fixed_bytes!("0x2a7365ec00661af1a2e02ec2d13241b03d1b8515ddcaac1d10afd9f0c106a660"),
+        ),
+        (
+            15,
+            fixed_bytes!("0x206dc24bf48cfff3bddc2708882f0f51746c2724d0969cd7510dd3c34ed0e729"),
+        ),
+    ];
+
+    for (level, value) in expected {
+        let IIMT::zerosReturn { _0: z } = contract.zeros(U256::from(level)).call().await?;
+        assert_eq!(z, value);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,70 @@
+#![cfg(all(feature = "e2e", feature = "keccak"))]
+
+use alloy_primitives::{fixed_bytes, keccak256, uint, FixedBytes, U256};
+use e2e::{constructor, receipt, Account};
+use eyre::Result;
+use stylus_mixer_bindings::imt::IIMT;
+
+const EMPTY_LEAF: FixedBytes<32> =
+    fixed_bytes!("0x168db4aa1d4e4bf2ee46eb882e1c38a7de1a4da47e17b207a5494a14605ae38e");
+
+#[e2e::test]
+async fn imt_keccak_zeros_are_keccak_of_level_below(alice: Account) -> Result<()> {
+    let contract_addr = alice
+        .as_deployer()
+        .with_constructor(constructor!(uint!(15_U256)))
+        .deploy()
+        .await?
+        .contract_address;
+    let contract = IIMT::new(contract_addr, &alice.wallet);
+
+    let mut expected = EMPTY_LEAF;
+    for level in 0..32u32 {
+        let IIMT::zerosReturn { _0: z } = contract.zeros(U256::from(level)).call().await?;
+        assert_eq!(z, expected);
+        expected = hash_pair(expected, expected);
+    }
+
+    Ok(())
+}
+
+#[e2e::test]
+async fn imt_keccak_insert_updates_root(alice: Account) -> Result<()> {
+    let contract_addr = alice
+        .as_deployer()
+        .with_constructor(constructor!(uint!(15_U256)))
+        .deploy()
+        .await?
+        .contract_address;
+    let contract = IIMT::new(contract_addr, &alice.wallet);
+
+    let leaf = keccak256("leaf");
+    receipt!(contract.insert(leaf))?;
+
+    /* first leaf is always the left child, siblings are the empty subtrees */
+    let mut expected = leaf;
+    let mut zero = EMPTY_LEAF;
+    for _ in 0..15 {
+        expected = hash_pair(expected, zero);
+        zero = hash_pair(zero, zero);
+    }
+
+    let IIMT::getRootFromRootIndexReturn { _0: root } =
+        contract.getRootFromRootIndex(1).call().await?;
+    assert_eq!(root, expected);
+
+    let IIMT::isKnownRootReturn { _0: known } = contract.isKnownRoot(expected).call().await?;
+    assert!(known);
+
+    Ok(())
+}
+
+/* ======================================================================
+ *                               INTERNAL HELPERS
+ * ====================================================================== */
+fn hash_pair(left: FixedBytes<32>, right: FixedBytes<32>) -> FixedBytes<32> {
+    let mut preimage = [0u8; 64];
+    preimage[..32].copy_from_slice(left.as_slice());
+    preimage[32..].copy_from_slice(right.as_slice());
+    keccak256(preimage)
+}
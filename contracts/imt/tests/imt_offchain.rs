@@ -0,0 +1,57 @@
+#![cfg(all(feature = "e2e", feature = "poseidon2", feature = "std"))]
+
+use alloy_primitives::{FixedBytes, U256};
+use e2e::{constructor, receipt, Account};
+use eyre::Result;
+use proptest::{
+    prelude::*,
+    strategy::ValueTree,
+    test_runner::{Config, TestRunner},
+};
+use stylus_imt::offchain::OffchainTree;
+use stylus_mixer_bindings::imt::IIMT;
+
+const DEPTH: u32 = 15;
+const CASES: u32 = 4;
+
+/* random insert sequences, checked against the deployed contract after every insert */
+#[e2e::test]
+async fn offchain_tree_roots_match_contract(alice: Account) -> Result<()> {
+    let mut runner = TestRunner::new(Config::with_cases(CASES));
+    let leaves = prop::collection::vec(
+        any::<[u8; 32]>().prop_map(|mut bytes| {
+            bytes[0] &= 0x1f;
+            FixedBytes::<32>::from(bytes)
+        }),
+        1..12,
+    );
+
+    for _ in 0..CASES {
+        let leaves = leaves
+            .new_tree(&mut runner)
+            .map_err(|reason| eyre::eyre!(reason))?
+            .current();
+
+        let contract_addr = alice
+            .as_deployer()
+            .with_constructor(constructor!(U256::from(DEPTH)))
+            .deploy()
+            .await?
+            .contract_address;
+        let contract = IIMT::new(contract_addr, &alice.wallet);
+        let mut tree: OffchainTree = OffchainTree::new(DEPTH).expect("valid depth");
+
+        for leaf in leaves {
+            receipt!(contract.insert(leaf))?;
+            tree.insert(leaf).expect("tree has room");
+
+            let IIMT::getCurrentRootIndexReturn { _0: index } =
+                contract.getCurrentRootIndex().call().await?;
+            let IIMT::getRootFromRootIndexReturn { _0: root } =
+                contract.getRootFromRootIndex(index).call().await?;
+            assert_eq!(root, tree.root());
+        }
+    }
+
+    Ok(())
+}
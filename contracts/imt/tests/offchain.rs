@@ -0,0 +1,151 @@
+#![cfg(all(feature = "std", feature = "poseidon2"))]
+
+use alloy_primitives::FixedBytes;
+use proptest::prelude::*;
+use stylus_imt::{
+    hasher::{MerkleHasher, Poseidon2Hasher},
+    offchain::{OffchainTree, OffchainTreeError},
+};
+
+/* the contract's insert, cached subtrees and all, as the reference model */
+struct ContractModel {
+    depth: u32,
+    next_leaf_index: u32,
+    cached_subtrees: Vec<FixedBytes<32>>,
+    root: FixedBytes<32>,
+}
+
+impl ContractModel {
+    fn new(depth: u32) -> Self {
+        Self {
+            depth,
+            next_leaf_index: 0,
+            cached_subtrees: vec![FixedBytes::ZERO; depth as usize],
+            root: Poseidon2Hasher::zero(depth),
+        }
+    }
+
+    fn insert(&mut self, leaf: FixedBytes<32>) -> u32 {
+        let mut current_index = self.next_leaf_index;
+        let mut current_hash = leaf;
+        for i in 0..self.depth {
+            let (left, right) = if current_index & 1 == 0 {
+                self.cached_subtrees[i as usize] = current_hash;
+                (current_hash, Poseidon2Hasher::zero(i))
+            } else {
+                (self.cached_subtrees[i as usize], current_hash)
+            };
+            current_hash = Poseidon2Hasher::hash_pair(left, right);
+            current_index >>= 1;
+        }
+        self.root = current_hash;
+        self.next_leaf_index += 1;
+        self.next_leaf_index - 1
+    }
+}
+
+/* below 2^253, so every leaf is a canonical field element */
+fn leaf() -> impl Strategy<Value = FixedBytes<32>> {
+    any::<[u8; 32]>().prop_map(|mut bytes| {
+        bytes[0] &= 0x1f;
+        FixedBytes::from(bytes)
+    })
+}
+
+fn depth_and_leaves() -> impl Strategy<Value = (u32, Vec<FixedBytes<32>>)> {
+    (1u32..=6).prop_flat_map(|depth| {
+        let capacity = 1usize << depth;
+        (Just(depth), prop::collection::vec(leaf(), 0..=capacity.min(24)))
+    })
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(32))]
+
+    #[test]
+    fn roots_match_contract(input in depth_and_leaves()) {
+        let (depth, leaves) = input;
+        let mut tree = OffchainTree::<Poseidon2Hasher>::new(depth).unwrap();
+        let mut model = ContractModel::new(depth);
+        prop_assert_eq!(tree.root(), model.root);
+
+        for leaf in leaves {
+            prop_assert_eq!(tree.insert(leaf).unwrap(), model.insert(leaf));
+            prop_assert_eq!(tree.root(), model.root);
+        }
+    }
+
+    #[test]
+    fn frontier_matches_cached_subtrees(input in depth_and_leaves()) {
+        let (depth, leaves) = input;
+        let mut tree = OffchainTree::<Poseidon2Hasher>::new(depth).unwrap();
+        let mut model = ContractModel::new(depth);
+        prop_assert_eq!(tree.frontier(), model.cached_subtrees.clone());
+
+        for leaf in leaves {
+            tree.insert(leaf).unwrap();
+            model.insert(leaf);
+            prop_assert_eq!(tree.frontier(), model.cached_subtrees.clone());
+        }
+    }
+
+    #[test]
+    fn proofs_recompute_the_root(input in depth_and_leaves()) {
+        let (depth, leaves) = input;
+        let tree = OffchainTree::<Poseidon2Hasher>::with_leaves(depth, leaves.clone()).unwrap();
+
+        for (index, leaf) in leaves.iter().enumerate() {
+            let proof = tree.proof(index as u32).unwrap();
+            prop_assert_eq!(proof.leaf, *leaf);
+            prop_assert_eq!(proof.siblings.len(), depth as usize);
+            prop_assert_eq!(proof.compute_root::<Poseidon2Hasher>(), tree.root());
+        }
+        prop_assert!(tree.proof(leaves.len() as u32).is_none());
+    }
+
+    #[test]
+    fn index_of_finds_first_insertion(input in depth_and_leaves()) {
+        let (depth, leaves) = input;
+        let tree = OffchainTree::<Poseidon2Hasher>::with_leaves(depth, leaves.clone()).unwrap();
+
+        for leaf in &leaves {
+            let first = leaves.iter().position(|l| l == leaf).unwrap() as u32;
+            prop_assert_eq!(tree.index_of(*leaf), Some(first));
+        }
+    }
+}
+
+#[test]
+fn root_history_keeps_the_last_30_roots() {
+    let mut tree = OffchainTree::<Poseidon2Hasher>::new(6).unwrap();
+    let mut roots = vec![tree.root()];
+    for i in 0..40u8 {
+        tree.insert(FixedBytes::repeat_byte(i + 1)).unwrap();
+        roots.push(tree.root());
+    }
+
+    assert_eq!(tree.roots().count(), 30);
+    assert!(roots[..roots.len() - 30].iter().all(|root| !tree.is_known_root(*root)));
+    assert!(roots[roots.len() - 30..].iter().all(|root| tree.is_known_root(*root)));
+    assert!(!tree.is_known_root(FixedBytes::ZERO));
+}
+
+#[test]
+fn rejects_bad_depth_and_full_tree() {
+    assert_eq!(
+        OffchainTree::<Poseidon2Hasher>::new(0).err(),
+        Some(OffchainTreeError::InvalidDepth)
+    );
+    assert_eq!(
+        OffchainTree::<Poseidon2Hasher>::new(32).err(),
+        Some(OffchainTreeError::InvalidDepth)
+    );
+
+    let mut tree = OffchainTree::<Poseidon2Hasher>::new(1).unwrap();
+    tree.insert(FixedBytes::repeat_byte(1)).unwrap();
+    tree.insert(FixedBytes::repeat_byte(2)).unwrap();
+    assert_eq!(
+        tree.insert(FixedBytes::repeat_byte(3)),
+        Err(OffchainTreeError::TreeIsFull)
+    );
+}
@@ -0,0 +1,88 @@
+use core::fmt;
+use std::io;
+
+use alloy::{contract, sol_types, transports::TransportError};
+use stylus_imt::offchain::OffchainTreeError;
+
+#[derive(Debug)]
+pub enum IndexerError {
+    Transport(TransportError),
+    Contract(contract::Error),
+    Decode(sol_types::Error),
+    Io(io::Error),
+    Json(serde_json::Error),
+    Tree(OffchainTreeError),
+    /// A `Deposit` index is not the next leaf, some logs were skipped or the pool is not a mixer.
+    Gap { expected: u32, actual: u32 },
+    /// The cache file was written for another chain, pool or depth.
+    CacheMismatch(&'static str),
+    /// The cached leaves do not rebuild the cached root and frontier.
+    CorruptedCache,
+    /// The node does not know a block the indexer asked for.
+    MissingBlock(u64),
+    /// A log without a block number or hash, only pending logs look like this.
+    PendingLog,
+    /// Blocks up to this one kept changing while their logs were read.
+    UnstableChain(u64),
+}
+
+impl fmt::Display for IndexerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Transport(err) => write!(f, "rpc error: {err}"),
+            Self::Contract(err) => write!(f, "contract call failed: {err}"),
+            Self::Decode(err) => write!(f, "cannot decode Deposit log: {err}"),
+            Self::Io(err) => write!(f, "cache file: {err}"),
+            Self::Json(err) => write!(f, "malformed cache file: {err}"),
+            Self::Tree(err) => write!(f, "cannot insert leaf: {err:?}"),
+            Self::Gap { expected, actual } => {
+                write!(f, "expected deposit {expected}, got deposit {actual}")
+            }
+            Self::CacheMismatch(field) => write!(f, "cache file is for another {field}"),
+            Self::CorruptedCache => write!(f, "cached leaves do not match the cached root"),
+            Self::MissingBlock(number) => write!(f, "node does not have block {number}"),
+            Self::PendingLog => write!(f, "got a log without a block"),
+            Self::UnstableChain(number) => {
+                write!(f, "chain kept reorging while reading up to block {number}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for IndexerError {}
+
+impl From<TransportError> for IndexerError {
+    fn from(err: TransportError) -> Self {
+        Self::Transport(err)
+    }
+}
+
+impl From<contract::Error> for IndexerError {
+    fn from(err: contract::Error) -> Self {
+        Self::Contract(err)
+    }
+}
+
+impl From<sol_types::Error> for IndexerError {
+    fn from(err: sol_types::Error) -> Self {
+        Self::Decode(err)
+    }
+}
+
+impl From<io::Error> for IndexerError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<serde_json::Error> for IndexerError {
+    fn from(err: serde_json::Error) -> Self {
+        Self::Json(err)
+    }
+}
+
+impl From<OffchainTreeError> for IndexerError {
+    fn from(err: OffchainTreeError) -> Self {
+        Self::Tree(err)
+    }
+}
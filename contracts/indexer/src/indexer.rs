@@ -0,0 +1,234 @@
+use core::marker::PhantomData;
+use std::path::PathBuf;
+
+use alloy::{
+    eips::BlockId,
+    primitives::Address,
+    providers::Provider,
+    rpc::types::{BlockTransactionsKind, Filter},
+    sol_types::SolEvent,
+    transports::Transport,
+};
+
+use stylus_mixer_bindings::mixer::{self, IMixer};
+
+use crate::{BlockRef, IndexedDeposit, IndexedTree, IndexerError};
+
+/// Blocks per `eth_getLogs` request, below the range limit of common RPC providers.
+pub const DEFAULT_PAGE_SIZE: u64 = 10_000;
+
+/* reads of one page in a row that a reorg can invalidate before `sync` gives up */
+const PAGE_ATTEMPTS: usize = 5;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SyncReport {
+    pub new_deposits: usize,
+    /// Deposits dropped because the blocks they were in got reorged out.
+    pub reorged_deposits: usize,
+    pub synced_to: BlockRef,
+}
+
+/// Keeps an [`IndexedTree`] in step with a pool and persists it to `path` after every page.
+pub struct Indexer<P, T> {
+    provider: P,
+    path: PathBuf,
+    tree: IndexedTree,
+    page_size: u64,
+    _transport: PhantomData<T>,
+}
+
+impl<P, T> Indexer<P, T>
+where
+    P: Provider<T>,
+    T: Transport + Clone,
+{
+    /// Resumes from the cache at `path` if there is one, otherwise starts at `start_block`.
+    /// An existing cache keeps the start block it was created with.
+    pub async fn open(
+        provider: P,
+        pool: Address,
+        start_block: u64,
+        path: impl Into<PathBuf>,
+    ) -> Result<Self, IndexerError> {
+        let path = path.into();
+        let chain_id = provider.get_chain_id().await?;
+        let IMixer::circuitDepthReturn { _0: depth } =
+            IMixer::new(pool, &provider).circuitDepth().call().await?;
+
+        let tree = if path.exists() {
+            let tree = IndexedTree::load(&path)?;
+            if tree.chain_id() != chain_id {
+                return Err(IndexerError::CacheMismatch("chain"));
+            }
+            if tree.pool() != pool {
+                return Err(IndexerError::CacheMismatch("pool"));
+            }
+            if tree.depth() != depth {
+                return Err(IndexerError::CacheMismatch("depth"));
+            }
+            tree
+        } else {
+            IndexedTree::new(chain_id, pool, depth, start_block)?
+        };
+
+        Ok(Self {
+            provider,
+            path,
+            tree,
+            page_size: DEFAULT_PAGE_SIZE,
+            _transport: PhantomData,
+        })
+    }
+
+    pub fn with_page_size(mut self, page_size: u64) -> Self {
+        self.page_size = page_size.max(1);
+        self
+    }
+
+    pub fn tree(&self) -> &IndexedTree {
+        &self.tree
+    }
+
+    /// Rewinds past any reorg, then pages through new `Deposit` logs up to the current head.
+    pub async fn sync(&mut self) -> Result<SyncReport, IndexerError> {
+        let before = self.tree.deposits().len();
+        let mut reorged_deposits = self.rewind_fork().await?;
+
+        let head = self.provider.get_block_number().await?;
+        let mut attempts = 0;
+        let mut from = self.tree.next_block();
+        while from <= head {
+            let to = head.min(from.saturating_add(self.page_size - 1));
+            if self.sync_page(from, to).await? {
+                attempts = 0;
+            } else {
+                /* the chain moved while the page was read, nothing of it was kept */
+                attempts += 1;
+                if attempts == PAGE_ATTEMPTS {
+                    return Err(IndexerError::UnstableChain(to));
+                }
+                reorged_deposits += self.rewind_fork().await?;
+            }
+            from = self.tree.next_block();
+        }
+
+        let synced_to = match self.tree.synced_to() {
+            Some(block) => block,
+            /* nothing to scan yet, the start block is still ahead of the head */
+            None => self.block(head).await?,
+        };
+        self.tree.save(&self.path)?;
+
+        Ok(SyncReport {
+            new_deposits: self.tree.deposits().len() + reorged_deposits - before,
+            reorged_deposits,
+            synced_to,
+        })
+    }
+
+    /*
+     * false, with the tree untouched, if a reorg could have happened while the page was read:
+     * the end block or the last synced one changed, or a log is from a block no longer canonical
+     */
+    async fn sync_page(&mut self, from: u64, to: u64) -> Result<bool, IndexerError> {
+        let filter = Filter::new()
+            .address(self.tree.pool())
+            .event_signature(mixer::Deposit::SIGNATURE_HASH)
+            .from_block(from)
+            .to_block(to);
+
+        let end = self.block(to).await?;
+        let mut logs = self.provider.get_logs(&filter).await?;
+        logs.sort_by_key(|log| (log.block_number, log.log_index));
+
+        if self.block(to).await? != end {
+            return Ok(false);
+        }
+        if let Some(synced_to) = self.tree.synced_to() {
+            if !self.is_canonical(synced_to).await? {
+                return Ok(false);
+            }
+        }
+
+        let mut deposits = Vec::with_capacity(logs.len());
+        for log in logs {
+            let (Some(number), Some(hash)) = (log.block_number, log.block_hash) else {
+                return Err(IndexerError::PendingLog);
+            };
+            let block = BlockRef { number, hash };
+            let checked = deposits
+                .last()
+                .is_some_and(|last: &IndexedDeposit| last.block == block);
+            if !checked && !self.is_canonical(block).await? {
+                return Ok(false);
+            }
+
+            let event = log.log_decode::<mixer::Deposit>()?.inner.data;
+            deposits.push(IndexedDeposit {
+                index: event.index,
+                commitment: event.commitment,
+                block,
+            });
+        }
+
+        for deposit in deposits {
+            self.tree.push(deposit)?;
+        }
+        self.tree.mark_synced(end);
+        self.tree.save(&self.path)?;
+        Ok(true)
+    }
+
+    /* drops the deposits of blocks a reorg replaced, returns how many */
+    async fn rewind_fork(&mut self) -> Result<usize, IndexerError> {
+        match self.find_fork().await? {
+            Some(fork) => self.tree.rewind(fork),
+            None => Ok(0),
+        }
+    }
+
+    /*
+     * Some(fork) if the synced block is no longer canonical: the newest block that still is,
+     * among the ones deposits came from, or None inside Some if none of them survived
+     */
+    async fn find_fork(&self) -> Result<Option<Option<BlockRef>>, IndexerError> {
+        let Some(synced_to) = self.tree.synced_to() else {
+            return Ok(None);
+        };
+        if self.is_canonical(synced_to).await? {
+            return Ok(None);
+        }
+
+        let mut checked = None;
+        for deposit in self.tree.deposits().iter().rev() {
+            if checked == Some(deposit.block.number) {
+                continue;
+            }
+            if self.is_canonical(deposit.block).await? {
+                return Ok(Some(Some(deposit.block)));
+            }
+            checked = Some(deposit.block.number);
+        }
+        Ok(Some(None))
+    }
+
+    async fn is_canonical(&self, block: BlockRef) -> Result<bool, IndexerError> {
+        let current = self
+            .provider
+            .get_block(BlockId::number(block.number), BlockTransactionsKind::Hashes)
+            .await?;
+        Ok(current.is_some_and(|current| current.header.hash == block.hash))
+    }
+
+    async fn block(&self, number: u64) -> Result<BlockRef, IndexerError> {
+        let block = self
+            .provider
+            .get_block(BlockId::number(number), BlockTransactionsKind::Hashes)
+            .await?
+            .ok_or(IndexerError::MissingBlock(number))?;
+        Ok(BlockRef {
+            number,
+            hash: block.header.hash,
+        })
+    }
+}
@@ -0,0 +1,9 @@
+//! Follows a mixer's `Deposit` logs and keeps a local, reorg-aware copy of its tree.
+
+mod error;
+mod indexer;
+mod tree;
+
+pub use error::IndexerError;
+pub use indexer::{Indexer, SyncReport, DEFAULT_PAGE_SIZE};
+pub use tree::{BlockRef, IndexedDeposit, IndexedTree};
@@ -0,0 +1,172 @@
+use std::{fs, path::Path};
+
+use alloy::primitives::{Address, FixedBytes, B256};
+use serde::{Deserialize, Serialize};
+use stylus_imt::offchain::OffchainTree;
+
+use crate::IndexerError;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockRef {
+    pub number: u64,
+    pub hash: B256,
+}
+
+/// One `Deposit` log and the block it was mined in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IndexedDeposit {
+    pub index: u32,
+    pub commitment: FixedBytes<32>,
+    pub block: BlockRef,
+}
+
+/// A pool's tree as of `synced_to`, and the blocks its leaves came from.
+pub struct IndexedTree {
+    chain_id: u64,
+    pool: Address,
+    depth: u32,
+    start_block: u64,
+    synced_to: Option<BlockRef>,
+    deposits: Vec<IndexedDeposit>,
+    tree: OffchainTree,
+}
+
+/* on-disk form, root and frontier are only kept to catch a corrupted file */
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CacheFile {
+    chain_id: u64,
+    pool: Address,
+    depth: u32,
+    start_block: u64,
+    synced_to: Option<BlockRef>,
+    deposits: Vec<IndexedDeposit>,
+    root: FixedBytes<32>,
+    frontier: Vec<FixedBytes<32>>,
+}
+
+impl IndexedTree {
+    pub fn new(
+        chain_id: u64,
+        pool: Address,
+        depth: u32,
+        start_block: u64,
+    ) -> Result<Self, IndexerError> {
+        Ok(Self {
+            chain_id,
+            pool,
+            depth,
+            start_block,
+            synced_to: None,
+            deposits: Vec::new(),
+            tree: OffchainTree::new(depth)?,
+        })
+    }
+
+    pub fn load(path: &Path) -> Result<Self, IndexerError> {
+        let cache: CacheFile = serde_json::from_str(&fs::read_to_string(path)?)?;
+
+        let mut tree = Self::new(cache.chain_id, cache.pool, cache.depth, cache.start_block)?;
+        for deposit in cache.deposits {
+            tree.push(deposit)?;
+        }
+        tree.synced_to = cache.synced_to;
+
+        if tree.tree.root() != cache.root || tree.tree.frontier() != cache.frontier {
+            return Err(IndexerError::CorruptedCache);
+        }
+        Ok(tree)
+    }
+
+    /// Writes to a sibling file first, so a crash never leaves a half-written cache.
+    pub fn save(&self, path: &Path) -> Result<(), IndexerError> {
+        let cache = CacheFile {
+            chain_id: self.chain_id,
+            pool: self.pool,
+            depth: self.depth,
+            start_block: self.start_block,
+            synced_to: self.synced_to,
+            deposits: self.deposits.clone(),
+            root: self.tree.root(),
+            frontier: self.tree.frontier(),
+        };
+        let tmp = path.with_extension("tmp");
+        fs::write(&tmp, serde_json::to_string_pretty(&cache)?)?;
+        fs::rename(&tmp, path)?;
+        Ok(())
+    }
+
+    /// Appends the next deposit, its index has to be the next leaf index.
+    pub fn push(&mut self, deposit: IndexedDeposit) -> Result<(), IndexerError> {
+        let expected = self.tree.len();
+        if deposit.index != expected {
+            return Err(IndexerError::Gap {
+                expected,
+                actual: deposit.index,
+            });
+        }
+        self.tree.insert(deposit.commitment)?;
+        self.deposits.push(deposit);
+        Ok(())
+    }
+
+    pub fn mark_synced(&mut self, block: BlockRef) {
+        self.synced_to = Some(block);
+    }
+
+    /// Forgets everything after `block`, or everything if `None`, and returns how many deposits
+    /// were dropped.
+    pub fn rewind(&mut self, block: Option<BlockRef>) -> Result<usize, IndexerError> {
+        let keep = match block {
+            Some(block) => self
+                .deposits
+                .iter()
+                .take_while(|deposit| deposit.block.number <= block.number)
+                .count(),
+            None => 0,
+        };
+        let dropped = self.deposits.len() - keep;
+
+        self.deposits.truncate(keep);
+        self.tree = OffchainTree::with_leaves(
+            self.depth,
+            self.deposits.iter().map(|deposit| deposit.commitment),
+        )?;
+        self.synced_to = block;
+        Ok(dropped)
+    }
+
+    pub fn chain_id(&self) -> u64 {
+        self.chain_id
+    }
+
+    pub fn pool(&self) -> Address {
+        self.pool
+    }
+
+    pub fn depth(&self) -> u32 {
+        self.depth
+    }
+
+    pub fn start_block(&self) -> u64 {
+        self.start_block
+    }
+
+    pub fn synced_to(&self) -> Option<BlockRef> {
+        self.synced_to
+    }
+
+    /// First block not scanned yet.
+    pub fn next_block(&self) -> u64 {
+        self.synced_to
+            .map_or(self.start_block, |block| block.number + 1)
+    }
+
+    pub fn deposits(&self) -> &[IndexedDeposit] {
+        &self.deposits
+    }
+
+    pub fn tree(&self) -> &OffchainTree {
+        &self.tree
+    }
+}
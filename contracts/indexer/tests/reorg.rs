@@ -0,0 +1,214 @@
+use std::{
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
+
+use alloy::{
+    primitives::{address, Address, FixedBytes, B256, U256},
+    providers::{ProviderBuilder, RootProvider},
+    transports::http::{Client, Http},
+};
+use axum::{extract::State, routing::post, Json, Router};
+use serde_json::{json, Value};
+use stylus_mixer_bindings::mixer;
+use stylus_mixer_indexer::Indexer;
+
+const POOL: Address = address!("5FbDB2315678afecb367f032d93F642f64180aa3");
+const DEPTH: u32 = 10;
+
+/* a chain the mock node serves, blocks from `fork_at` on are its own */
+struct Chain {
+    head: u64,
+    fork_at: u64,
+    tag: u8,
+    /* (block, index, commitment) */
+    deposits: Vec<(u64, u32, u8)>,
+}
+
+impl Chain {
+    fn hash(&self, number: u64) -> B256 {
+        let mut hash = B256::ZERO;
+        hash[0] = if number < self.fork_at { 0 } else { self.tag };
+        hash[24..].copy_from_slice(&number.to_be_bytes());
+        hash
+    }
+}
+
+/* blocks 0..=8, deposit 1 sits in block 5 */
+fn fork_a() -> Chain {
+    Chain {
+        head: 8,
+        fork_at: 4,
+        tag: 0xa,
+        deposits: vec![(3, 0, 1), (5, 1, 2)],
+    }
+}
+
+/* replaces fork A from block 4 on, moving deposit 1 to block 6 with another note */
+fn fork_b() -> Chain {
+    Chain {
+        head: 8,
+        fork_at: 4,
+        tag: 0xb,
+        deposits: vec![(3, 0, 1), (6, 1, 3)],
+    }
+}
+
+struct Node {
+    chain: Chain,
+    /* served in place of `chain` right after the next eth_getLogs */
+    next: Option<Chain>,
+}
+
+type Shared = Arc<Mutex<Node>>;
+
+async fn rpc(State(node): State<Shared>, Json(request): Json<Value>) -> Json<Value> {
+    let mut node = node.lock().unwrap();
+    let params = &request["params"];
+    let result = match request["method"].as_str().unwrap() {
+        "eth_chainId" => json!("0x64aba"),
+        "eth_blockNumber" => json!(format!("{:#x}", node.chain.head)),
+        "eth_call" => json!(B256::from(U256::from(DEPTH))),
+        "eth_getBlockByNumber" => {
+            let number = quantity(&params[0]);
+            if number <= node.chain.head {
+                block(&node.chain, number)
+            } else {
+                Value::Null
+            }
+        }
+        "eth_getLogs" => {
+            let from = quantity(&params[0]["fromBlock"]);
+            let to = quantity(&params[0]["toBlock"]);
+            let logs = logs(&node.chain, from, to);
+            if let Some(next) = node.next.take() {
+                node.chain = next;
+            }
+            logs
+        }
+        method => panic!("unexpected {method}"),
+    };
+    Json(json!({ "jsonrpc": "2.0", "id": request["id"], "result": result }))
+}
+
+fn quantity(value: &Value) -> u64 {
+    u64::from_str_radix(value.as_str().unwrap().trim_start_matches("0x"), 16).unwrap()
+}
+
+fn block(chain: &Chain, number: u64) -> Value {
+    let parent = chain.hash(number.saturating_sub(1));
+    json!({
+        "hash": chain.hash(number),
+        "parentHash": parent,
+        "sha3Uncles": B256::ZERO,
+        "miner": Address::ZERO,
+        "stateRoot": B256::ZERO,
+        "transactionsRoot": B256::ZERO,
+        "receiptsRoot": B256::ZERO,
+        "logsBloom": format!("0x{}", "00".repeat(256)),
+        "difficulty": "0x0",
+        "number": format!("{number:#x}"),
+        "gasLimit": "0x0",
+        "gasUsed": "0x0",
+        "timestamp": format!("{number:#x}"),
+        "extraData": "0x",
+        "mixHash": B256::ZERO,
+        "nonce": "0x0000000000000000",
+        "uncles": [],
+        "transactions": [],
+    })
+}
+
+fn logs(chain: &Chain, from: u64, to: u64) -> Value {
+    chain
+        .deposits
+        .iter()
+        .filter(|(number, ..)| (from..=to).contains(number))
+        .map(|&(number, index, commitment)| {
+            let event = mixer::Deposit {
+                commitment: FixedBytes::repeat_byte(commitment),
+                index,
+                timestamp: U256::from(number),
+            };
+            let data = alloy::sol_types::SolEvent::encode_log_data(&event);
+            json!({
+                "address": POOL,
+                "topics": data.topics(),
+                "data": data.data,
+                "blockNumber": format!("{number:#x}"),
+                "blockHash": chain.hash(number),
+                "transactionHash": B256::with_last_byte(index as u8),
+                "transactionIndex": "0x0",
+                "logIndex": "0x0",
+                "removed": false,
+            })
+        })
+        .collect()
+}
+
+async fn serve(chain: Chain) -> (Shared, RootProvider<Http<Client>>) {
+    let node = Arc::new(Mutex::new(Node { chain, next: None }));
+    let app = Router::new().route("/", post(rpc)).with_state(node.clone());
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+    let provider = ProviderBuilder::new().on_http(url.parse().unwrap());
+    (node, provider)
+}
+
+fn cache_path(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("indexer-{}-{name}.json", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    path
+}
+
+fn commitments(indexer: &Indexer<RootProvider<Http<Client>>, Http<Client>>) -> Vec<u8> {
+    indexer
+        .tree()
+        .deposits()
+        .iter()
+        .map(|deposit| deposit.commitment[0])
+        .collect()
+}
+
+#[tokio::test]
+async fn sync_drops_a_page_reorged_while_read() {
+    let (node, provider) = serve(fork_a()).await;
+    node.lock().unwrap().next = Some(fork_b());
+
+    let mut indexer = Indexer::open(provider, POOL, 0, cache_path("page-reorg"))
+        .await
+        .unwrap();
+    let report = indexer.sync().await.unwrap();
+
+    /* fork A's logs were read, but block 8 changed before they were kept */
+    assert_eq!(commitments(&indexer), vec![1, 3]);
+    assert_eq!(indexer.tree().deposits()[1].block.hash, fork_b().hash(6));
+    assert_eq!(report.new_deposits, 2);
+    assert_eq!(report.reorged_deposits, 0);
+    assert_eq!(report.synced_to.hash, fork_b().hash(8));
+}
+
+#[tokio::test]
+async fn sync_rewinds_a_reorg_between_syncs() {
+    let (node, provider) = serve(fork_a()).await;
+    let path = cache_path("sync-reorg");
+
+    let mut indexer = Indexer::open(provider.clone(), POOL, 0, &path)
+        .await
+        .unwrap();
+    indexer.sync().await.unwrap();
+    assert_eq!(commitments(&indexer), vec![1, 2]);
+
+    node.lock().unwrap().chain = fork_b();
+    /* the start block of an existing cache is kept */
+    let mut indexer = Indexer::open(provider, POOL, 2, &path).await.unwrap();
+    let report = indexer.sync().await.unwrap();
+
+    assert_eq!(commitments(&indexer), vec![1, 3]);
+    assert_eq!(indexer.tree().start_block(), 0);
+    assert_eq!(report.reorged_deposits, 1);
+    assert_eq!(report.new_deposits, 1);
+    assert_eq!(report.synced_to.hash, fork_b().hash(8));
+}
@@ -0,0 +1,127 @@
+use std::path::PathBuf;
+
+use alloy::primitives::{address, Address, FixedBytes, B256};
+use stylus_imt::offchain::OffchainTree;
+use stylus_mixer_indexer::{BlockRef, IndexedDeposit, IndexedTree, IndexerError};
+
+const POOL: Address = address!("5FbDB2315678afecb367f032d93F642f64180aa3");
+
+fn block(number: u64) -> BlockRef {
+    BlockRef {
+        number,
+        hash: B256::with_last_byte(number as u8),
+    }
+}
+
+fn deposit(index: u32, number: u64) -> IndexedDeposit {
+    IndexedDeposit {
+        index,
+        commitment: FixedBytes::repeat_byte(index as u8 + 1),
+        block: block(number),
+    }
+}
+
+/* deposits 0 and 1 in block 10, 2 in block 12, 3 in block 15 */
+fn sample() -> IndexedTree {
+    let mut tree = IndexedTree::new(412346, POOL, 10, 5).unwrap();
+    for (index, number) in [(0, 10), (1, 10), (2, 12), (3, 15)] {
+        tree.push(deposit(index, number)).unwrap();
+    }
+    tree.mark_synced(block(20));
+    tree
+}
+
+fn cache_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("indexer-{}-{name}.json", std::process::id()))
+}
+
+#[test]
+fn push_rejects_gaps_and_repeats() {
+    let mut tree = sample();
+    assert!(matches!(
+        tree.push(deposit(5, 21)),
+        Err(IndexerError::Gap {
+            expected: 4,
+            actual: 5
+        })
+    ));
+    assert!(matches!(
+        tree.push(deposit(3, 21)),
+        Err(IndexerError::Gap {
+            expected: 4,
+            actual: 3
+        })
+    ));
+    assert_eq!(tree.deposits().len(), 4);
+}
+
+#[test]
+fn tree_follows_deposits() {
+    let tree = sample();
+    let expected: OffchainTree =
+        OffchainTree::with_leaves(10, tree.deposits().iter().map(|d| d.commitment)).unwrap();
+    assert_eq!(tree.tree().root(), expected.root());
+    assert_eq!(tree.next_block(), 21);
+}
+
+#[test]
+fn rewind_drops_deposits_after_the_fork() {
+    let mut tree = sample();
+    let root_after_two = tree.tree().roots().nth(2).copied().unwrap();
+
+    assert_eq!(tree.rewind(Some(block(11))).unwrap(), 2);
+    assert_eq!(tree.deposits().len(), 2);
+    assert_eq!(tree.tree().root(), root_after_two);
+    assert_eq!(tree.synced_to(), Some(block(11)));
+    assert_eq!(tree.next_block(), 12);
+
+    /* the next deposit is index 2 again */
+    tree.push(deposit(2, 13)).unwrap();
+}
+
+#[test]
+fn rewind_to_none_starts_over() {
+    let mut tree = sample();
+    assert_eq!(tree.rewind(None).unwrap(), 4);
+    assert!(tree.deposits().is_empty());
+    let empty: OffchainTree = OffchainTree::new(10).unwrap();
+    assert_eq!(tree.tree().root(), empty.root());
+    assert_eq!(tree.next_block(), 5);
+}
+
+#[test]
+fn cache_roundtrips() {
+    let path = cache_path("roundtrip");
+    let tree = sample();
+    tree.save(&path).unwrap();
+
+    let loaded = IndexedTree::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(loaded.deposits(), tree.deposits());
+    assert_eq!(loaded.synced_to(), tree.synced_to());
+    assert_eq!(loaded.tree().root(), tree.tree().root());
+    assert_eq!(loaded.chain_id(), 412346);
+    assert_eq!(loaded.pool(), POOL);
+    assert_eq!(loaded.start_block(), 5);
+}
+
+#[test]
+fn tampered_cache_is_rejected() {
+    let path = cache_path("tampered");
+    sample().save(&path).unwrap();
+
+    /* swap one cached commitment, the stored root no longer matches */
+    let json = std::fs::read_to_string(&path).unwrap();
+    let tampered = json.replacen(
+        &FixedBytes::<32>::repeat_byte(2).to_string(),
+        &FixedBytes::<32>::repeat_byte(9).to_string(),
+        1,
+    );
+    assert_ne!(json, tampered);
+    std::fs::write(&path, tampered).unwrap();
+
+    let loaded = IndexedTree::load(&path);
+    std::fs::remove_file(&path).unwrap();
+    assert!(matches!(loaded, Err(IndexerError::CorruptedCache)));
+}
@@ -0,0 +1,7 @@
+// Events of `Mixer`, also read by stylus-mixer-bindings.
+
+event Deposit(bytes32 indexed commitment, uint32 index, uint256 timestamp);
+event Withdrawal(address indexed recipient, bytes32 indexed nullifier_hash);
+event VerifierRegistered(uint32 indexed version, address verifier, uint32 proof_size, bytes32 vk_hash);
+event VerifierDeprecated(uint32 indexed version);
+event BatchVerifierSet(uint32 indexed version, address verifier, uint32 proof_size, bytes32 vk_hash, bytes32 key_hash);
@@ -4,21 +4,37 @@ extern crate alloc;
 pub mod interface;
 
 use crate::interface::VerifierInterface;
-use stylus_common::errors::MixerErrors;
+use stylus_common::{
+    circuits::SUPPORTED_DEPTHS, errors::MixerErrors, public_inputs::WithdrawPublicInputs,
+};
 use stylus_imt::interface::IMTInterface;
 use stylus_sdk::{
     abi::Bytes as AbiBytes,
-    alloy_primitives::{uint, Address, Bytes as AlloyBytes, FixedBytes, U256},
+    alloy_primitives::{Address, Bytes as AlloyBytes, FixedBytes, U256, U32},
     alloy_sol_types::sol,
     prelude::*,
-    storage::{StorageAddress, StorageBool, StorageGuard, StorageMap},
+    storage::{
+        StorageAddress, StorageBool, StorageFixedBytes, StorageGuard, StorageMap, StorageU256,
+        StorageU32,
+    },
 };
 
-const DENOMINATION: U256 = uint!(1_000_000_000_000_000_000_U256);
+/* version the constructor's verifier is registered under */
+const INITIAL_CIRCUIT_VERSION: u32 = 1;
+
+/* withdraw proofs folded into one aggregated proof, fixed by circuits/aggregate */
+const BATCH_SIZE: u32 = 4;
+
+/* export-abi leaves events out, stylus-mixer-bindings reads them from this file */
+sol!("src/events.sol");
 
 sol! {
-    event Deposit(bytes32 indexed commitment, uint32 index, uint256 timestamp);
-    event Withdrawal(address indexed recipient, bytes32 indexed nullifier_hash);
+    #[derive(AbiType)]
+    struct WithdrawRequest {
+        bytes32 root;
+        bytes32 nullifier_hash;
+        address recipient;
+    }
 }
 
 #[entrypoint]
@@ -27,7 +43,35 @@ pub struct Mixer {
     commitments: StorageMap<FixedBytes<32>, StorageBool>,
     nullifier_hashes: StorageMap<FixedBytes<32>, StorageBool>,
     imt: StorageAddress,
+    admin: StorageAddress,
+    circuit_depth: StorageU32,
+    /* wei per deposit, fixed at construction */
+    denomination: StorageU256,
+    verifiers: StorageMap<U32, VerifierEntry>,
+    batch: BatchVerifier,
+}
+
+/* one registered circuit version, every version proves the same withdraw statement, so withdraw
+ * hands each verifier the fixed WithdrawPublicInputs layout and only the proof system varies */
+#[storage]
+pub struct VerifierEntry {
+    verifier: StorageAddress,
+    proof_size: StorageU32,
+    vk_hash: StorageFixedBytes<32>,
+    deprecated: StorageBool,
+}
+
+/* verifier of circuits/aggregate, pinned to the withdraw circuit's verification key */
+#[storage]
+pub struct BatchVerifier {
     verifier: StorageAddress,
+    /* keccak256 of the aggregate circuit's vk, reported by the verifier */
+    vk_hash: StorageFixedBytes<32>,
+    key_hash: StorageFixedBytes<32>,
+    /* bytes in an aggregated proof, set with the verifier like a registry entry's */
+    proof_size: StorageU32,
+    /* registered version the aggregated proofs are proofs of */
+    version: StorageU32,
 }
 
 /* ======================================================================
@@ -36,9 +80,115 @@ pub struct Mixer {
 #[public]
 impl Mixer {
     #[constructor]
-    fn initialize(&mut self, verifier: Address, imt: Address) -> Result<(), MixerErrors> {
-        self.verifier.set(verifier);
+    #[allow(clippy::too_many_arguments)]
+    fn initialize(
+        &mut self,
+        verifier: Address,
+        imt: Address,
+        admin: Address,
+        vk_hash: FixedBytes<32>,
+        proof_size: u32,
+        circuit_depth: u32,
+        denomination: U256,
+    ) -> Result<(), MixerErrors> {
+        if denomination.is_zero() {
+            return Err(MixerErrors::invalid_denomination());
+        }
+
+        /* only depths scripts/build_circuits.sh produces a verifier for */
+        if !SUPPORTED_DEPTHS.contains(&circuit_depth) {
+            return Err(MixerErrors::unsupported_circuit_depth(circuit_depth));
+        }
+
+        /* a shallower or deeper tree accepts deposits the circuit can never prove */
+        let tree_depth = IMTInterface::new(imt)
+            .get_depth(&mut *self)
+            .expect("getDepth call failed");
+        if tree_depth != circuit_depth {
+            return Err(MixerErrors::circuit_depth_mismatch(
+                tree_depth,
+                circuit_depth,
+            ));
+        }
+
         self.imt.set(imt);
+        self.circuit_depth.set(U32::from(circuit_depth));
+        self.denomination.set(denomination);
+        self.admin.set(admin);
+        self.register(INITIAL_CIRCUIT_VERSION, verifier, proof_size, vk_hash)
+    }
+
+    /* ADMIN */
+    fn register_verifier(
+        &mut self,
+        version: u32,
+        verifier: Address,
+        proof_size: u32,
+        vk_hash: FixedBytes<32>,
+    ) -> Result<(), MixerErrors> {
+        self.only_admin()?;
+        self.register(version, verifier, proof_size, vk_hash)
+    }
+
+    fn deprecate_verifier(&mut self, version: u32) -> Result<(), MixerErrors> {
+        self.only_admin()?;
+
+        {
+            let mut entry = self.verifiers.setter(U32::from(version));
+            if entry.verifier.get() == Address::ZERO {
+                return Err(MixerErrors::unknown_circuit_version(version));
+            }
+            entry.deprecated.set(true);
+        }
+
+        log(self.vm(), VerifierDeprecated { version });
+        Ok(())
+    }
+
+    fn set_batch_verifier(
+        &mut self,
+        version: u32,
+        verifier: Address,
+        proof_size: u32,
+        vk_hash: FixedBytes<32>,
+        key_hash: FixedBytes<32>,
+    ) -> Result<(), MixerErrors> {
+        self.only_admin()?;
+
+        {
+            let entry = self.verifiers.getter(U32::from(version));
+            if entry.verifier.get() == Address::ZERO {
+                return Err(MixerErrors::unknown_circuit_version(version));
+            }
+            if entry.deprecated.get() {
+                return Err(MixerErrors::circuit_version_deprecated(version));
+            }
+        }
+
+        /* same pinning as register, against the aggregate circuit's key */
+        let actual = VerifierInterface::new(verifier)
+            .vk_hash(&mut *self)
+            .expect("vkHash call failed");
+        if actual != vk_hash {
+            return Err(MixerErrors::verification_key_mismatch(vk_hash, actual));
+        }
+
+        self.batch.verifier.set(verifier);
+        self.batch.vk_hash.set(vk_hash);
+        self.batch.key_hash.set(key_hash);
+        self.batch.proof_size.set(U32::from(proof_size));
+        self.batch.version.set(U32::from(version));
+
+        log(
+            self.vm(),
+            BatchVerifierSet {
+                version,
+                verifier,
+                proof_size,
+                vk_hash,
+                key_hash,
+            },
+        );
         Ok(())
     }
 
@@ -52,7 +202,7 @@ impl Mixer {
 
         /* check if amount sent is the same as the denomination value for the mixer */
         let amount = self.vm().msg_value();
-        if amount < DENOMINATION {
+        if amount < self.denomination.get() {
             return Err(MixerErrors::invalid_denomination());
         }
 
@@ -79,30 +229,50 @@ impl Mixer {
         root: FixedBytes<32>,
         nullifier_hash: FixedBytes<32>,
         recipient: Address,
+        circuit_version: u32,
     ) -> Result<(), MixerErrors> {
+        /* resolve the verifier for the requested circuit version */
+        let (verifier, expected_proof_size) = {
+            let entry = self.verifiers.getter(U32::from(circuit_version));
+            if entry.verifier.get() == Address::ZERO {
+                return Err(MixerErrors::unknown_circuit_version(circuit_version));
+            }
+            if entry.deprecated.get() {
+                return Err(MixerErrors::circuit_version_deprecated(circuit_version));
+            }
+            (
+                entry.verifier.get(),
+                u32::from_be_bytes(entry.proof_size.get().to_be_bytes::<4>()),
+            )
+        };
+
+        /* reject malformed envelopes before paying for any external call */
+        if proof.len() != expected_proof_size as usize {
+            return Err(MixerErrors::malformed_proof(U256::from(proof.len())));
+        }
+
+        /* prepare public inputs for the verifier */
+        let public_inputs = WithdrawPublicInputs {
+            root,
+            nullifier_hash,
+            recipient,
+            chain_id: self.vm().chain_id(),
+            pool: self.vm().contract_address(),
+        }
+        .to_verifier_inputs();
+
         /* check if nullifier hash has already been used */
         if self.nullifier_hashes.getter(nullifier_hash).get() {
             return Err(MixerErrors::nullifier_hash_already_used());
         }
 
         /* check if root is known */
-        let known = IMTInterface::new(self.imt.get())
-            .is_known_root(&mut *self, root)
-            .expect("isKnownRoot call failed");
-        if !known {
+        if !self.is_known_root(root) {
             return Err(MixerErrors::invalid_root());
         }
 
-        let bytes_recipient: FixedBytes<32> = recipient.into_word();
-
-        /* prepare public inputs for the verifier */
-        let mut public_inputs: Vec<FixedBytes<32>> = Vec::with_capacity(3);
-        public_inputs.push(root);
-        public_inputs.push(nullifier_hash);
-        public_inputs.push(bytes_recipient);
-
         /* verify proof */
-        let verified = VerifierInterface::new(self.verifier.get())
+        let verified = VerifierInterface::new(verifier)
             .verify(
                 &mut *self,
                 AlloyBytes::copy_from_slice(proof.as_slice()),
@@ -116,9 +286,177 @@ impl Mixer {
         /* insert nullifier hash */
         self.nullifier_hashes.insert(nullifier_hash, true);
 
+        self.pay_out(recipient, nullifier_hash)
+    }
+
+    fn batch_withdraw(
+        &mut self,
+        aggregated_proof: AbiBytes,
+        requests: Vec<WithdrawRequest>,
+    ) -> Result<(), MixerErrors> {
+        let verifier = self.batch.verifier.get();
+        if verifier == Address::ZERO {
+            return Err(MixerErrors::batch_withdraw_disabled());
+        }
+
+        /* deprecating the withdraw circuit also retires batches of its proofs */
+        let version = u32::from_be_bytes(self.batch.version.get().to_be_bytes::<4>());
+        if self.verifiers.getter(U32::from(version)).deprecated.get() {
+            return Err(MixerErrors::circuit_version_deprecated(version));
+        }
+        if requests.len() != BATCH_SIZE as usize {
+            return Err(MixerErrors::invalid_batch_size(
+                BATCH_SIZE,
+                requests.len() as u32,
+            ));
+        }
+        if aggregated_proof.len() != self.batch_proof_size() as usize {
+            return Err(MixerErrors::malformed_proof(U256::from(
+                aggregated_proof.len(),
+            )));
+        }
+
+        /* key hash first, then every request's withdraw inputs in order */
+        let chain_id = self.vm().chain_id();
+        let pool = self.vm().contract_address();
+        let mut public_inputs: Vec<FixedBytes<32>> =
+            Vec::with_capacity(1 + requests.len() * WithdrawPublicInputs::LEN);
+        public_inputs.push(self.batch.key_hash.get());
+
+        /* spending up front also catches a nullifier repeated inside the batch, any error reverts it all */
+        for request in requests.iter() {
+            if self.nullifier_hashes.getter(request.nullifier_hash).get() {
+                return Err(MixerErrors::nullifier_hash_already_used());
+            }
+            self.nullifier_hashes.insert(request.nullifier_hash, true);
+
+            if !self.is_known_root(request.root) {
+                return Err(MixerErrors::invalid_root());
+            }
+
+            public_inputs.extend(
+                WithdrawPublicInputs {
+                    root: request.root,
+                    nullifier_hash: request.nullifier_hash,
+                    recipient: request.recipient,
+                    chain_id,
+                    pool,
+                }
+                .to_verifier_inputs(),
+            );
+        }
+
+        let verified = VerifierInterface::new(verifier)
+            .verify(
+                &mut *self,
+                AlloyBytes::copy_from_slice(aggregated_proof.as_slice()),
+                public_inputs,
+            )
+            .expect("verify call failed");
+        if !verified {
+            return Err(MixerErrors::invalid_proof());
+        }
+
+        for request in requests {
+            self.pay_out(request.recipient, request.nullifier_hash)?;
+        }
+        Ok(())
+    }
+
+    /* GETTERS/VIEW FUNCTIONS */
+    fn admin(&self) -> Address {
+        self.admin.get()
+    }
+
+    fn imt(&self) -> Address {
+        self.imt.get()
+    }
+
+    fn is_spent(&self, nullifier_hash: FixedBytes<32>) -> bool {
+        self.nullifier_hashes.getter(nullifier_hash).get()
+    }
+
+    fn circuit_depth(&self) -> u32 {
+        u32::from_be_bytes(self.circuit_depth.get().to_be_bytes::<4>())
+    }
+
+    fn denomination(&self) -> U256 {
+        self.denomination.get()
+    }
+
+    fn batch_size(&self) -> u32 {
+        BATCH_SIZE
+    }
+
+    fn batch_verifier(&self) -> Address {
+        self.batch.verifier.get()
+    }
+
+    fn batch_vk_hash(&self) -> FixedBytes<32> {
+        self.batch.vk_hash.get()
+    }
+
+    fn batch_key_hash(&self) -> FixedBytes<32> {
+        self.batch.key_hash.get()
+    }
+
+    fn batch_proof_size(&self) -> u32 {
+        u32::from_be_bytes(self.batch.proof_size.get().to_be_bytes::<4>())
+    }
+
+    fn batch_circuit_version(&self) -> u32 {
+        u32::from_be_bytes(self.batch.version.get().to_be_bytes::<4>())
+    }
+
+    fn verifier(&self, version: u32) -> Address {
+        self.verifiers.getter(U32::from(version)).verifier.get()
+    }
+
+    fn proof_size(&self, version: u32) -> u32 {
+        u32::from_be_bytes(
+            self.verifiers
+                .getter(U32::from(version))
+                .proof_size
+                .get()
+                .to_be_bytes::<4>(),
+        )
+    }
+
+    fn vk_hash(&self, version: u32) -> FixedBytes<32> {
+        self.verifiers.getter(U32::from(version)).vk_hash.get()
+    }
+
+    fn is_deprecated(&self, version: u32) -> bool {
+        self.verifiers.getter(U32::from(version)).deprecated.get()
+    }
+}
+
+/* ======================================================================
+ *                         CONTRACT INTERNAL
+ * ====================================================================== */
+impl Mixer {
+    fn only_admin(&self) -> Result<(), MixerErrors> {
+        if self.vm().msg_sender() != self.admin.get() {
+            return Err(MixerErrors::unauthorized());
+        }
+        Ok(())
+    }
+
+    fn is_known_root(&mut self, root: FixedBytes<32>) -> bool {
+        IMTInterface::new(self.imt.get())
+            .is_known_root(&mut *self, root)
+            .expect("isKnownRoot call failed")
+    }
+
+    fn pay_out(
+        &mut self,
+        recipient: Address,
+        nullifier_hash: FixedBytes<32>,
+    ) -> Result<(), MixerErrors> {
         /* transfer funds to recipient */
+        let amount = self.denomination.get();
         self.vm()
-            .transfer_eth(recipient, DENOMINATION)
+            .transfer_eth(recipient, amount)
             .map_err(|_| MixerErrors::invalid_denomination())?;
 
         log(
@@ -130,4 +468,42 @@ impl Mixer {
         );
         Ok(())
     }
+
+    fn register(
+        &mut self,
+        version: u32,
+        verifier: Address,
+        proof_size: u32,
+        vk_hash: FixedBytes<32>,
+    ) -> Result<(), MixerErrors> {
+        if self.verifiers.getter(U32::from(version)).verifier.get() != Address::ZERO {
+            return Err(MixerErrors::verifier_already_registered(version));
+        }
+
+        /* the verifier must be built from the key the caller expects */
+        let actual = VerifierInterface::new(verifier)
+            .vk_hash(&mut *self)
+            .expect("vkHash call failed");
+        if actual != vk_hash {
+            return Err(MixerErrors::verification_key_mismatch(vk_hash, actual));
+        }
+
+        {
+            let mut entry = self.verifiers.setter(U32::from(version));
+            entry.verifier.set(verifier);
+            entry.proof_size.set(U32::from(proof_size));
+            entry.vk_hash.set(vk_hash);
+        }
+
+        log(
+            self.vm(),
+            VerifierRegistered {
+                version,
+                verifier,
+                proof_size,
+                vk_hash,
+            },
+        );
+        Ok(())
+    }
 }
@@ -1,17 +1,21 @@
 #![cfg(feature = "e2e")]
 
-use alloy::{eips::BlockId, providers::Provider, rpc::types::BlockTransactionsKind};
+use alloy::{
+    eips::BlockId, providers::Provider, rpc::types::BlockTransactionsKind,
+    signers::local::PrivateKeySigner,
+};
 use alloy_primitives::{uint, Address, FixedBytes, U256};
 use e2e::{constructor, receipt, send, Account, Revert};
-use eyre::{Result, WrapErr};
+use eyre::Result;
 use serde::Deserialize;
-use std::str::FromStr;
+use mixer_deploy::{Tools, IMT_WASM, MIXER_WASM};
+use stylus_common::{circuits::ULTRA_HONK_PROOF_SIZE, vk::vk_hash_from_file};
+use stylus_mixer_client::{Deployment, Note};
 use std::{path::PathBuf, process::Command};
-
-mod abi;
-use abi::{IMTAbi, MixerAbi};
+use stylus_mixer_bindings::{imt::IIMT, mixer::{self, IMixer}};
 
 const DENOMINATION: U256 = uint!(1_000_000_000_000_000_000_U256);
+const CIRCUIT_VERSION: u32 = 1;
 
 /* ======================================================================
  *                      generate commmitment and proof
@@ -24,9 +28,14 @@ async fn generate_commitment_and_proof_works(alice: Account) -> Result<()> {
     assert!(secret != FixedBytes::ZERO);
     let leaves = vec![commitment];
     let recipient = alice.address();
-    let (proof, public_inputs) = generate_proof(nullifier, secret, recipient, leaves)?;
+    let chain_id = chain_id(&alice).await?;
+    let pool = Address::repeat_byte(0x11);
+    let (proof, public_inputs) =
+        generate_proof(nullifier, secret, recipient, chain_id, pool, leaves)?;
     assert!(proof.len() > 0);
-    assert!(public_inputs.len() > 0);
+    assert_eq!(public_inputs.len(), 5);
+    assert_eq!(U256::from_be_bytes(public_inputs[3].0), U256::from(chain_id));
+    assert_eq!(Address::from_word(public_inputs[4]), pool);
     Ok(())
 }
 /* ======================================================================
@@ -37,7 +46,7 @@ async fn mixer_deposit_works(alice: Account) -> Result<()> {
     let deployments = deploy_all_contracts(&alice).await?;
 
     let mixer_addr = deployments.mixer;
-    let mixer = MixerAbi::new(mixer_addr, &alice.wallet);
+    let mixer = IMixer::new(mixer_addr, &alice.wallet);
 
     /* generate commitment */
     let (commitment, _nullifier, _secret) = generate_commitment()?;
@@ -49,7 +58,7 @@ async fn mixer_deposit_works(alice: Account) -> Result<()> {
 
     let raw_log = rcpt.inner.as_receipt().unwrap().logs.first().unwrap();
     let decoded = raw_log
-        .log_decode::<MixerAbi::Deposit>()
+        .log_decode::<mixer::Deposit>()
         .expect("decode deposit event");
 
     let event = &decoded.inner.data;
@@ -64,14 +73,14 @@ async fn mixer_deposit_rejects_invalid_denomination(alice: Account) -> Result<()
     let deployments = deploy_all_contracts(&alice).await?;
 
     let mixer_addr = deployments.mixer;
-    let mixer = MixerAbi::new(mixer_addr, &alice.wallet);
+    let mixer = IMixer::new(mixer_addr, &alice.wallet);
 
     /* generate commitment */
     let (commitment, _nullifier, _secret) = generate_commitment()?;
 
     /* call deposit with zero value -> expect revert */
     let err = send!(mixer.deposit(commitment).value(U256::ZERO)).expect_err("should revert");
-    assert!(err.reverted_with(MixerAbi::InvalidDenomination {}));
+    assert!(err.reverted_with(mixer::InvalidDenomination {}));
     Ok(())
 }
 
@@ -79,14 +88,14 @@ async fn mixer_deposit_rejects_invalid_denomination(alice: Account) -> Result<()
 async fn mixer_deposit_rejects_duplicate_commitment(alice: Account) -> Result<()> {
     let deployments = deploy_all_contracts(&alice).await?;
     let mixer_addr = deployments.mixer;
-    let mixer = MixerAbi::new(mixer_addr, &alice.wallet);
+    let mixer = IMixer::new(mixer_addr, &alice.wallet);
 
     /* generate commitment */
     let (commitment, _nullifier, _secret) = generate_commitment()?;
 
     receipt!(mixer.deposit(commitment).value(DENOMINATION))?;
     let err = send!(mixer.deposit(commitment).value(DENOMINATION)).expect_err("should revert");
-    assert!(err.reverted_with(MixerAbi::CommitmentAlreadyExists {}));
+    assert!(err.reverted_with(mixer::CommitmentAlreadyExists {}));
     Ok(())
 }
 
@@ -100,8 +109,8 @@ async fn mixer_withdraw_works(alice: Account) -> Result<()> {
     let verifier_addr = deployments.verifier;
     let mixer_addr = deployments.mixer;
 
-    let mixer = MixerAbi::new(mixer_addr, &alice.wallet);
-    let imt = IMTAbi::new(imt_addr, &alice.wallet);
+    let mixer = IMixer::new(mixer_addr, &alice.wallet);
+    let imt = IIMT::new(imt_addr, &alice.wallet);
 
     /* generate commitment */
     let (commitment, nullifier, secret) = generate_commitment()?;
@@ -111,17 +120,794 @@ async fn mixer_withdraw_works(alice: Account) -> Result<()> {
     let leaves = vec![commitment];
 
     /* generate proof */
-    let (proof, public_inputs) = generate_proof(nullifier, secret, alice.address(), leaves)?;
+    let (proof, public_inputs) = generate_proof(
+        nullifier,
+        secret,
+        alice.address(),
+        chain_id(&alice).await?,
+        mixer_addr,
+        leaves,
+    )?;
 
-    let IMTAbi::isKnownRootReturn { known } = imt.isKnownRoot(public_inputs[0]).call().await?;
+    let IIMT::isKnownRootReturn { _0: known } = imt.isKnownRoot(public_inputs[0]).call().await?;
     assert!(known, "proof root not found in IMT");
+    let IMixer::imtReturn { _0: mixer_imt } = mixer.imt().call().await?;
+    assert_eq!(mixer_imt, imt_addr);
+    let IMixer::isSpentReturn { _0: spent } = mixer.isSpent(public_inputs[1]).call().await?;
+    assert!(!spent);
 
     receipt!(mixer.withdraw(
         proof.into(),
         public_inputs[0],
         public_inputs[1],
-        Address::from_word(public_inputs[2])
+        Address::from_word(public_inputs[2]),
+        CIRCUIT_VERSION
+    ))?;
+
+    let IMixer::isSpentReturn { _0: spent } = mixer.isSpent(public_inputs[1]).call().await?;
+    assert!(spent);
+    Ok(())
+}
+
+#[e2e::test]
+async fn mixer_withdraw_rejects_deprecated_circuit_version(alice: Account) -> Result<()> {
+    let deployments = deploy_all_contracts(&alice).await?;
+    let mixer = IMixer::new(deployments.mixer, &alice.wallet);
+
+    let (commitment, nullifier, secret) = generate_commitment()?;
+    receipt!(mixer.deposit(commitment).value(DENOMINATION))?;
+    let (proof, public_inputs) = generate_proof(
+        nullifier,
+        secret,
+        alice.address(),
+        chain_id(&alice).await?,
+        deployments.mixer,
+        vec![commitment],
+    )?;
+
+    receipt!(mixer.deprecateVerifier(CIRCUIT_VERSION))?;
+
+    let err = send!(mixer.withdraw(
+        proof.into(),
+        public_inputs[0],
+        public_inputs[1],
+        Address::from_word(public_inputs[2]),
+        CIRCUIT_VERSION
+    ))
+    .expect_err("should revert");
+    assert!(err.reverted_with(mixer::CircuitVersionDeprecated {
+        version: CIRCUIT_VERSION
+    }));
+    Ok(())
+}
+
+#[e2e::test]
+async fn mixer_withdraw_rejects_unknown_circuit_version(alice: Account) -> Result<()> {
+    let deployments = deploy_all_contracts(&alice).await?;
+    let mixer = IMixer::new(deployments.mixer, &alice.wallet);
+
+    let err = send!(mixer.withdraw(
+        vec![0u8; ULTRA_HONK_PROOF_SIZE as usize].into(),
+        FixedBytes::ZERO,
+        FixedBytes::ZERO,
+        alice.address(),
+        7
+    ))
+    .expect_err("should revert");
+    assert!(err.reverted_with(mixer::UnknownCircuitVersion { version: 7 }));
+    Ok(())
+}
+
+#[e2e::test]
+async fn mixer_withdraw_rejects_truncated_proof(alice: Account) -> Result<()> {
+    let deployments = deploy_all_contracts(&alice).await?;
+    let mixer = IMixer::new(deployments.mixer, &alice.wallet);
+
+    let (commitment, nullifier, secret) = generate_commitment()?;
+    receipt!(mixer.deposit(commitment).value(DENOMINATION))?;
+    let (mut proof, public_inputs) = generate_proof(
+        nullifier,
+        secret,
+        alice.address(),
+        chain_id(&alice).await?,
+        deployments.mixer,
+        vec![commitment],
+    )?;
+    assert_eq!(proof.len(), ULTRA_HONK_PROOF_SIZE as usize);
+
+    proof.truncate(proof.len() - 32);
+    let length = U256::from(proof.len());
+
+    let err = send!(mixer.withdraw(
+        proof.into(),
+        public_inputs[0],
+        public_inputs[1],
+        Address::from_word(public_inputs[2]),
+        CIRCUIT_VERSION
+    ))
+    .expect_err("should revert");
+    assert!(err.reverted_with(mixer::MalformedProof { length }));
+    Ok(())
+}
+
+#[e2e::test]
+async fn mixer_withdraw_rejects_padded_proof(alice: Account) -> Result<()> {
+    let deployments = deploy_all_contracts(&alice).await?;
+    let mixer = IMixer::new(deployments.mixer, &alice.wallet);
+
+    let (commitment, nullifier, secret) = generate_commitment()?;
+    receipt!(mixer.deposit(commitment).value(DENOMINATION))?;
+    let (mut proof, public_inputs) = generate_proof(
+        nullifier,
+        secret,
+        alice.address(),
+        chain_id(&alice).await?,
+        deployments.mixer,
+        vec![commitment],
+    )?;
+
+    proof.extend_from_slice(&[0u8; 32]);
+    let length = U256::from(proof.len());
+
+    let err = send!(mixer.withdraw(
+        proof.into(),
+        public_inputs[0],
+        public_inputs[1],
+        Address::from_word(public_inputs[2]),
+        CIRCUIT_VERSION
+    ))
+    .expect_err("should revert");
+    assert!(err.reverted_with(mixer::MalformedProof { length }));
+    Ok(())
+}
+
+#[e2e::test]
+async fn mixer_withdraw_rejects_empty_proof(alice: Account) -> Result<()> {
+    let deployments = deploy_all_contracts(&alice).await?;
+    let mixer = IMixer::new(deployments.mixer, &alice.wallet);
+
+    let err = send!(mixer.withdraw(
+        Vec::<u8>::new().into(),
+        FixedBytes::ZERO,
+        FixedBytes::ZERO,
+        alice.address(),
+        CIRCUIT_VERSION
+    ))
+    .expect_err("should revert");
+    assert!(err.reverted_with(mixer::MalformedProof { length: U256::ZERO }));
+    Ok(())
+}
+
+#[e2e::test]
+async fn mixer_reports_circuit_depth(alice: Account) -> Result<()> {
+    let deployments = deploy_all_contracts(&alice).await?;
+    let mixer = IMixer::new(deployments.mixer, &alice.wallet);
+
+    let IMixer::circuitDepthReturn { _0: depth } = mixer.circuitDepth().call().await?;
+    assert_eq!(depth, 15);
+    Ok(())
+}
+
+#[e2e::test]
+async fn mixer_reports_denomination(alice: Account) -> Result<()> {
+    let deployments = deploy_all_contracts(&alice).await?;
+    let mixer = IMixer::new(deployments.mixer, &alice.wallet);
+
+    let IMixer::denominationReturn { _0: amount } = mixer.denomination().call().await?;
+    assert_eq!(amount, DENOMINATION);
+    Ok(())
+}
+
+#[e2e::test]
+async fn mixer_rejects_zero_denomination(alice: Account) -> Result<()> {
+    let imt = deploy_imt(&alice, uint!(15_U256)).await?;
+    let verifier = deploy_verifier()?;
+
+    let deployed = deploy_mixer(&alice, verifier, imt, withdraw_vk_hash()?, 15, U256::ZERO).await;
+    assert!(deployed.is_err(), "mixer should not deploy with a zero denomination");
+    Ok(())
+}
+
+#[e2e::test]
+async fn mixer_deposit_enforces_configured_denomination(alice: Account) -> Result<()> {
+    let imt = deploy_imt(&alice, uint!(15_U256)).await?;
+    let verifier = deploy_verifier()?;
+    let denomination = uint!(100_000_000_000_000_000_U256);
+    let mixer_addr =
+        deploy_mixer(&alice, verifier, imt, withdraw_vk_hash()?, 15, denomination).await?;
+    let mixer = IMixer::new(mixer_addr, &alice.wallet);
+
+    let (commitment, _nullifier, _secret) = generate_commitment()?;
+    let err = send!(mixer.deposit(commitment).value(denomination - U256::from(1)))
+        .expect_err("should revert");
+    assert!(err.reverted_with(mixer::InvalidDenomination {}));
+
+    receipt!(mixer.deposit(commitment).value(denomination))?;
+    Ok(())
+}
+
+#[e2e::test]
+async fn mixer_rejects_tree_with_different_depth(alice: Account) -> Result<()> {
+    let imt = deploy_imt(&alice, uint!(10_U256)).await?;
+    let verifier = deploy_verifier()?;
+
+    let err = deploy_mixer(&alice, verifier, imt, withdraw_vk_hash()?, 15, DENOMINATION)
+        .await
+        .expect_err("mixer should not deploy on a depth-10 tree");
+    assert!(err.reverted_with(mixer::CircuitDepthMismatch {
+        tree: 10,
+        circuit: 15,
+    }));
+    Ok(())
+}
+
+#[e2e::test]
+async fn mixer_rejects_unsupported_circuit_depth(alice: Account) -> Result<()> {
+    let imt = deploy_imt(&alice, uint!(12_U256)).await?;
+    let verifier = deploy_verifier()?;
+
+    let err = deploy_mixer(&alice, verifier, imt, withdraw_vk_hash()?, 12, DENOMINATION)
+        .await
+        .expect_err("no circuit is built for depth 12");
+    assert!(err.reverted_with(mixer::UnsupportedCircuitDepth { depth: 12 }));
+    Ok(())
+}
+
+#[e2e::test]
+async fn mixer_withdraw_rejects_proof_for_another_pool(alice: Account) -> Result<()> {
+    let deployments = deploy_all_contracts(&alice).await?;
+    let mixer = IMixer::new(deployments.mixer, &alice.wallet);
+    let other = deploy_all_contracts(&alice).await?;
+    let other_mixer = IMixer::new(other.mixer, &alice.wallet);
+
+    /* same note in both pools, proof bound to the other one */
+    let (commitment, nullifier, secret) = generate_commitment()?;
+    receipt!(mixer.deposit(commitment).value(DENOMINATION))?;
+    receipt!(other_mixer.deposit(commitment).value(DENOMINATION))?;
+    let (proof, public_inputs) = generate_proof(
+        nullifier,
+        secret,
+        alice.address(),
+        chain_id(&alice).await?,
+        other.mixer,
+        vec![commitment],
+    )?;
+
+    send!(mixer.withdraw(
+        proof.clone().into(),
+        public_inputs[0],
+        public_inputs[1],
+        Address::from_word(public_inputs[2]),
+        CIRCUIT_VERSION
+    ))
+    .expect_err("proof for another pool must not verify");
+
+    /* the pool it was made for still accepts it */
+    receipt!(other_mixer.withdraw(
+        proof.into(),
+        public_inputs[0],
+        public_inputs[1],
+        Address::from_word(public_inputs[2]),
+        CIRCUIT_VERSION
+    ))?;
+    Ok(())
+}
+
+#[e2e::test]
+async fn mixer_withdraw_rejects_proof_for_another_chain(alice: Account) -> Result<()> {
+    let deployments = deploy_all_contracts(&alice).await?;
+    let mixer = IMixer::new(deployments.mixer, &alice.wallet);
+
+    let (commitment, nullifier, secret) = generate_commitment()?;
+    receipt!(mixer.deposit(commitment).value(DENOMINATION))?;
+    let (proof, public_inputs) = generate_proof(
+        nullifier,
+        secret,
+        alice.address(),
+        chain_id(&alice).await? + 1,
+        deployments.mixer,
+        vec![commitment],
+    )?;
+
+    send!(mixer.withdraw(
+        proof.into(),
+        public_inputs[0],
+        public_inputs[1],
+        Address::from_word(public_inputs[2]),
+        CIRCUIT_VERSION
+    ))
+    .expect_err("proof for another chain must not verify");
+    Ok(())
+}
+
+/* ======================================================================
+ *                           batchWithdraw()
+ * ====================================================================== */
+const BATCH_SIZE: usize = 4;
+
+/* deposits BATCH_SIZE notes, each paying out to a fresh recipient */
+async fn deposit_batch(
+    account: &Account,
+    mixer_addr: Address,
+) -> Result<Vec<(FixedBytes<32>, FixedBytes<32>, Address, FixedBytes<32>)>> {
+    let mixer = IMixer::new(mixer_addr, &account.wallet);
+    let mut notes = Vec::with_capacity(BATCH_SIZE);
+    for _ in 0..BATCH_SIZE {
+        let (commitment, nullifier, secret) = generate_commitment()?;
+        receipt!(mixer.deposit(commitment).value(DENOMINATION))?;
+        let recipient = PrivateKeySigner::random().address();
+        notes.push((nullifier, secret, recipient, commitment));
+    }
+    Ok(notes)
+}
+
+fn withdraw_requests(public_inputs: &[FixedBytes<32>]) -> Vec<IMixer::WithdrawRequest> {
+    /* skip the key hash, then root || nullifier_hash || recipient || chain_id || pool per note */
+    public_inputs[1..]
+        .chunks(5)
+        .map(|inputs| IMixer::WithdrawRequest {
+            root: inputs[0],
+            nullifier_hash: inputs[1],
+            recipient: Address::from_word(inputs[2]),
+        })
+        .collect()
+}
+
+#[e2e::test]
+async fn mixer_batch_withdraw_pays_every_recipient(alice: Account) -> Result<()> {
+    let deployments = deploy_all_contracts(&alice).await?;
+    let mixer = IMixer::new(deployments.mixer, &alice.wallet);
+
+    let notes = deposit_batch(&alice, deployments.mixer).await?;
+    let leaves: Vec<_> = notes.iter().map(|note| note.3).collect();
+    let batch: Vec<_> = notes.iter().map(|note| (note.0, note.1, note.2)).collect();
+    let (proof, public_inputs, key_hash) =
+        generate_aggregate_proof(&batch, chain_id(&alice).await?, deployments.mixer, leaves)?;
+
+    let aggregate_verifier = deploy_aggregate_verifier()?;
+    let rcpt = receipt!(mixer.setBatchVerifier(
+        CIRCUIT_VERSION,
+        aggregate_verifier,
+        ULTRA_HONK_PROOF_SIZE,
+        aggregate_vk_hash()?,
+        key_hash
+    ))?;
+    assert!(rcpt.emits(mixer::BatchVerifierSet {
+        version: CIRCUIT_VERSION,
+        verifier: aggregate_verifier,
+        proof_size: ULTRA_HONK_PROOF_SIZE,
+        vk_hash: aggregate_vk_hash()?,
+        key_hash,
+    }));
+    let IMixer::batchProofSizeReturn { _0: size } = mixer.batchProofSize().call().await?;
+    assert_eq!(size, ULTRA_HONK_PROOF_SIZE);
+
+    let requests = withdraw_requests(&public_inputs);
+    let rcpt = receipt!(mixer.batchWithdraw(proof.into(), requests.clone()))?;
+    for request in requests {
+        assert!(rcpt.emits(mixer::Withdrawal {
+            recipient: request.recipient,
+            nullifier_hash: request.nullifier_hash,
+        }));
+        let balance = alice.wallet.get_balance(request.recipient).await?;
+        assert_eq!(balance, DENOMINATION);
+    }
+    Ok(())
+}
+
+#[e2e::test]
+async fn mixer_batch_withdraw_reverts_if_any_nullifier_is_spent(alice: Account) -> Result<()> {
+    let deployments = deploy_all_contracts(&alice).await?;
+    let mixer = IMixer::new(deployments.mixer, &alice.wallet);
+    let chain_id = chain_id(&alice).await?;
+
+    let notes = deposit_batch(&alice, deployments.mixer).await?;
+    let leaves: Vec<_> = notes.iter().map(|note| note.3).collect();
+    let batch: Vec<_> = notes.iter().map(|note| (note.0, note.1, note.2)).collect();
+    let (proof, public_inputs, key_hash) =
+        generate_aggregate_proof(&batch, chain_id, deployments.mixer, leaves.clone())?;
+    receipt!(mixer.setBatchVerifier(
+        CIRCUIT_VERSION,
+        deploy_aggregate_verifier()?,
+        ULTRA_HONK_PROOF_SIZE,
+        aggregate_vk_hash()?,
+        key_hash
+    ))?;
+
+    /* spend the last note on its own first */
+    let (nullifier, secret, recipient, _) = notes[BATCH_SIZE - 1];
+    let (single_proof, single_inputs) = generate_proof(
+        nullifier,
+        secret,
+        recipient,
+        chain_id,
+        deployments.mixer,
+        leaves,
+    )?;
+    receipt!(mixer.withdraw(
+        single_proof.into(),
+        single_inputs[0],
+        single_inputs[1],
+        recipient,
+        CIRCUIT_VERSION
+    ))?;
+
+    let requests = withdraw_requests(&public_inputs);
+    let err = send!(mixer.batchWithdraw(proof.into(), requests.clone()))
+        .expect_err("should revert");
+    assert!(err.reverted_with(mixer::NullifierHashAlreadyUsed {}));
+
+    /* nothing from the batch was paid out */
+    for request in &requests[..BATCH_SIZE - 1] {
+        let balance = alice.wallet.get_balance(request.recipient).await?;
+        assert_eq!(balance, U256::ZERO);
+    }
+    Ok(())
+}
+
+#[e2e::test]
+async fn mixer_batch_withdraw_rejects_duplicate_nullifier(alice: Account) -> Result<()> {
+    let deployments = deploy_all_contracts(&alice).await?;
+    let mixer = IMixer::new(deployments.mixer, &alice.wallet);
+    receipt!(mixer.setBatchVerifier(
+        CIRCUIT_VERSION,
+        deployments.verifier,
+        ULTRA_HONK_PROOF_SIZE,
+        withdraw_vk_hash()?,
+        FixedBytes::ZERO
+    ))?;
+
+    let (commitment, _, _) = generate_commitment()?;
+    receipt!(mixer.deposit(commitment).value(DENOMINATION))?;
+    let imt = IIMT::new(deployments.imt, &alice.wallet);
+    let IIMT::getCurrentRootIndexReturn { _0: root_index } =
+        imt.getCurrentRootIndex().call().await?;
+    let IIMT::getRootFromRootIndexReturn { _0: root } =
+        imt.getRootFromRootIndex(root_index).call().await?;
+
+    let request = IMixer::WithdrawRequest {
+        root,
+        nullifier_hash: FixedBytes::repeat_byte(0x01),
+        recipient: alice.address(),
+    };
+    let err = send!(mixer.batchWithdraw(
+        vec![0u8; ULTRA_HONK_PROOF_SIZE as usize].into(),
+        vec![request; BATCH_SIZE]
+    ))
+    .expect_err("should revert");
+    assert!(err.reverted_with(mixer::NullifierHashAlreadyUsed {}));
+    Ok(())
+}
+
+#[e2e::test]
+async fn mixer_batch_withdraw_is_disabled_by_default(alice: Account) -> Result<()> {
+    let deployments = deploy_all_contracts(&alice).await?;
+    let mixer = IMixer::new(deployments.mixer, &alice.wallet);
+
+    let IMixer::batchVerifierReturn { _0: verifier } = mixer.batchVerifier().call().await?;
+    assert_eq!(verifier, Address::ZERO);
+
+    let err = send!(mixer.batchWithdraw(
+        vec![0u8; ULTRA_HONK_PROOF_SIZE as usize].into(),
+        vec![]
+    ))
+    .expect_err("should revert");
+    assert!(err.reverted_with(mixer::BatchWithdrawDisabled {}));
+    Ok(())
+}
+
+#[e2e::test]
+async fn mixer_batch_withdraw_rejects_wrong_batch_size(alice: Account) -> Result<()> {
+    let deployments = deploy_all_contracts(&alice).await?;
+    let mixer = IMixer::new(deployments.mixer, &alice.wallet);
+    receipt!(mixer.setBatchVerifier(
+        CIRCUIT_VERSION,
+        deployments.verifier,
+        ULTRA_HONK_PROOF_SIZE,
+        withdraw_vk_hash()?,
+        FixedBytes::ZERO
+    ))?;
+
+    let IMixer::batchSizeReturn { _0: size } = mixer.batchSize().call().await?;
+    assert_eq!(size as usize, BATCH_SIZE);
+
+    let request = IMixer::WithdrawRequest {
+        root: FixedBytes::ZERO,
+        nullifier_hash: FixedBytes::ZERO,
+        recipient: alice.address(),
+    };
+    let err = send!(mixer.batchWithdraw(
+        vec![0u8; ULTRA_HONK_PROOF_SIZE as usize].into(),
+        vec![request; BATCH_SIZE - 1]
+    ))
+    .expect_err("should revert");
+    assert!(err.reverted_with(mixer::InvalidBatchSize {
+        expected: BATCH_SIZE as u32,
+        actual: BATCH_SIZE as u32 - 1,
+    }));
+    Ok(())
+}
+
+#[e2e::test]
+async fn mixer_batch_withdraw_rejects_wrong_proof_size(alice: Account) -> Result<()> {
+    let deployments = deploy_all_contracts(&alice).await?;
+    let mixer = IMixer::new(deployments.mixer, &alice.wallet);
+    /* the batch verifier keeps its own size, not the one of the withdraw verifier */
+    let proof_size = ULTRA_HONK_PROOF_SIZE + 32;
+    receipt!(mixer.setBatchVerifier(
+        CIRCUIT_VERSION,
+        deployments.verifier,
+        proof_size,
+        withdraw_vk_hash()?,
+        FixedBytes::ZERO
+    ))?;
+
+    let request = IMixer::WithdrawRequest {
+        root: FixedBytes::ZERO,
+        nullifier_hash: FixedBytes::ZERO,
+        recipient: alice.address(),
+    };
+    let err = send!(mixer.batchWithdraw(
+        vec![0u8; ULTRA_HONK_PROOF_SIZE as usize].into(),
+        vec![request; BATCH_SIZE]
+    ))
+    .expect_err("should revert");
+    assert!(err.reverted_with(mixer::MalformedProof {
+        length: U256::from(ULTRA_HONK_PROOF_SIZE),
+    }));
+    Ok(())
+}
+
+#[e2e::test]
+async fn mixer_set_batch_verifier_only_admin(alice: Account, bob: Account) -> Result<()> {
+    let deployments = deploy_all_contracts(&alice).await?;
+    let mixer = IMixer::new(deployments.mixer, &bob.wallet);
+
+    let err = send!(mixer.setBatchVerifier(
+        CIRCUIT_VERSION,
+        deployments.verifier,
+        ULTRA_HONK_PROOF_SIZE,
+        withdraw_vk_hash()?,
+        FixedBytes::ZERO
+    ))
+    .expect_err("should revert");
+    assert!(err.reverted_with(mixer::Unauthorized {}));
+    Ok(())
+}
+
+#[e2e::test]
+async fn mixer_set_batch_verifier_requires_live_circuit_version(alice: Account) -> Result<()> {
+    let deployments = deploy_all_contracts(&alice).await?;
+    let mixer = IMixer::new(deployments.mixer, &alice.wallet);
+
+    let err = send!(mixer.setBatchVerifier(
+        2,
+        deployments.verifier,
+        ULTRA_HONK_PROOF_SIZE,
+        withdraw_vk_hash()?,
+        FixedBytes::ZERO
+    ))
+    .expect_err("should revert");
+    assert!(err.reverted_with(mixer::UnknownCircuitVersion { version: 2 }));
+
+    receipt!(mixer.deprecateVerifier(CIRCUIT_VERSION))?;
+    let err = send!(mixer.setBatchVerifier(
+        CIRCUIT_VERSION,
+        deployments.verifier,
+        ULTRA_HONK_PROOF_SIZE,
+        withdraw_vk_hash()?,
+        FixedBytes::ZERO
+    ))
+    .expect_err("should revert");
+    assert!(err.reverted_with(mixer::CircuitVersionDeprecated {
+        version: CIRCUIT_VERSION
+    }));
+    Ok(())
+}
+
+#[e2e::test]
+async fn mixer_set_batch_verifier_rejects_wrong_vk_hash(alice: Account) -> Result<()> {
+    let deployments = deploy_all_contracts(&alice).await?;
+    let mixer = IMixer::new(deployments.mixer, &alice.wallet);
+
+    let expected = FixedBytes::repeat_byte(0x01);
+    let err = send!(mixer.setBatchVerifier(
+        CIRCUIT_VERSION,
+        deployments.verifier,
+        ULTRA_HONK_PROOF_SIZE,
+        expected,
+        FixedBytes::ZERO
+    ))
+    .expect_err("should revert");
+    assert!(err.reverted_with(mixer::VerificationKeyMismatch {
+        expected,
+        actual: withdraw_vk_hash()?,
+    }));
+
+    let IMixer::batchVerifierReturn { _0: verifier } = mixer.batchVerifier().call().await?;
+    assert_eq!(verifier, Address::ZERO);
+    Ok(())
+}
+
+#[e2e::test]
+async fn mixer_batch_withdraw_rejects_deprecated_circuit_version(alice: Account) -> Result<()> {
+    let deployments = deploy_all_contracts(&alice).await?;
+    let mixer = IMixer::new(deployments.mixer, &alice.wallet);
+
+    let notes = deposit_batch(&alice, deployments.mixer).await?;
+    let leaves: Vec<_> = notes.iter().map(|note| note.3).collect();
+    let batch: Vec<_> = notes.iter().map(|note| (note.0, note.1, note.2)).collect();
+    let (proof, public_inputs, key_hash) =
+        generate_aggregate_proof(&batch, chain_id(&alice).await?, deployments.mixer, leaves)?;
+    receipt!(mixer.setBatchVerifier(
+        CIRCUIT_VERSION,
+        deploy_aggregate_verifier()?,
+        ULTRA_HONK_PROOF_SIZE,
+        aggregate_vk_hash()?,
+        key_hash
+    ))?;
+
+    let IMixer::batchCircuitVersionReturn { _0: version } =
+        mixer.batchCircuitVersion().call().await?;
+    assert_eq!(version, CIRCUIT_VERSION);
+
+    /* a valid aggregated proof of the retired circuit no longer pays out */
+    receipt!(mixer.deprecateVerifier(CIRCUIT_VERSION))?;
+    let requests = withdraw_requests(&public_inputs);
+    let err = send!(mixer.batchWithdraw(proof.into(), requests.clone()))
+        .expect_err("should revert");
+    assert!(err.reverted_with(mixer::CircuitVersionDeprecated {
+        version: CIRCUIT_VERSION
+    }));
+
+    for request in requests {
+        let IMixer::isSpentReturn { _0: spent } =
+            mixer.isSpent(request.nullifier_hash).call().await?;
+        assert!(!spent);
+    }
+    Ok(())
+}
+
+/* ======================================================================
+ *                           verifier registry
+ * ====================================================================== */
+#[e2e::test]
+async fn mixer_registers_initial_verifier(alice: Account) -> Result<()> {
+    let deployments = deploy_all_contracts(&alice).await?;
+    let mixer = IMixer::new(deployments.mixer, &alice.wallet);
+
+    let IMixer::adminReturn { _0: admin } = mixer.admin().call().await?;
+    assert_eq!(admin, alice.address());
+
+    let IMixer::verifierReturn { _0: verifier } = mixer.verifier(CIRCUIT_VERSION).call().await?;
+    assert_eq!(verifier, deployments.verifier);
+
+    let IMixer::proofSizeReturn { _0: size } = mixer.proofSize(CIRCUIT_VERSION).call().await?;
+    assert_eq!(size, ULTRA_HONK_PROOF_SIZE);
+
+    let IMixer::isDeprecatedReturn { _0: deprecated } =
+        mixer.isDeprecated(CIRCUIT_VERSION).call().await?;
+    assert!(!deprecated);
+    Ok(())
+}
+
+#[e2e::test]
+async fn mixer_register_and_deprecate_verifier_emit_events(alice: Account) -> Result<()> {
+    let deployments = deploy_all_contracts(&alice).await?;
+    let mixer = IMixer::new(deployments.mixer, &alice.wallet);
+
+    let rcpt = receipt!(mixer.registerVerifier(
+        2,
+        deployments.verifier,
+        ULTRA_HONK_PROOF_SIZE,
+        withdraw_vk_hash()?
     ))?;
+    let raw_log = rcpt.inner.as_receipt().unwrap().logs.first().unwrap();
+    let decoded = raw_log
+        .log_decode::<mixer::VerifierRegistered>()
+        .expect("decode verifier registered event");
+    assert_eq!(
+        decoded.inner.data,
+        mixer::VerifierRegistered {
+            version: 2,
+            verifier: deployments.verifier,
+            proof_size: ULTRA_HONK_PROOF_SIZE,
+            vk_hash: withdraw_vk_hash()?,
+        }
+    );
+
+    let rcpt = receipt!(mixer.deprecateVerifier(2))?;
+    let raw_log = rcpt.inner.as_receipt().unwrap().logs.first().unwrap();
+    let decoded = raw_log
+        .log_decode::<mixer::VerifierDeprecated>()
+        .expect("decode verifier deprecated event");
+    assert_eq!(decoded.inner.data, mixer::VerifierDeprecated { version: 2 });
+
+    let IMixer::isDeprecatedReturn { _0: deprecated } = mixer.isDeprecated(2).call().await?;
+    assert!(deprecated);
+    Ok(())
+}
+
+#[e2e::test]
+async fn mixer_register_verifier_rejects_duplicate_version(alice: Account) -> Result<()> {
+    let deployments = deploy_all_contracts(&alice).await?;
+    let mixer = IMixer::new(deployments.mixer, &alice.wallet);
+
+    let err = send!(mixer.registerVerifier(
+        CIRCUIT_VERSION,
+        deployments.verifier,
+        ULTRA_HONK_PROOF_SIZE,
+        withdraw_vk_hash()?
+    ))
+    .expect_err("should revert");
+    assert!(err.reverted_with(mixer::VerifierAlreadyRegistered {
+        version: CIRCUIT_VERSION
+    }));
+    Ok(())
+}
+
+#[e2e::test]
+async fn mixer_registry_is_admin_only(alice: Account, bob: Account) -> Result<()> {
+    let deployments = deploy_all_contracts(&alice).await?;
+    let mixer = IMixer::new(deployments.mixer, &bob.wallet);
+
+    let err = send!(mixer.registerVerifier(
+        2,
+        deployments.verifier,
+        ULTRA_HONK_PROOF_SIZE,
+        withdraw_vk_hash()?
+    ))
+    .expect_err("should revert");
+    assert!(err.reverted_with(mixer::Unauthorized {}));
+
+    let err = send!(mixer.deprecateVerifier(CIRCUIT_VERSION)).expect_err("should revert");
+    assert!(err.reverted_with(mixer::Unauthorized {}));
+    Ok(())
+}
+
+#[e2e::test]
+async fn mixer_pins_verifier_vk_hash(alice: Account) -> Result<()> {
+    let deployments = deploy_all_contracts(&alice).await?;
+    let mixer = IMixer::new(deployments.mixer, &alice.wallet);
+
+    let IMixer::vkHashReturn { _0: hash } = mixer.vkHash(CIRCUIT_VERSION).call().await?;
+    assert_eq!(hash, withdraw_vk_hash()?);
+    Ok(())
+}
+
+#[e2e::test]
+async fn mixer_rejects_verifier_with_wrong_vk_hash(alice: Account) -> Result<()> {
+    let imt = deploy_imt(&alice, uint!(15_U256)).await?;
+    let verifier = deploy_verifier()?;
+
+    let expected = FixedBytes::repeat_byte(0x01);
+    let err = deploy_mixer(&alice, verifier, imt, expected, 15, DENOMINATION)
+        .await
+        .expect_err("mixer should not deploy with a mismatched vk hash");
+    assert!(err.reverted_with(mixer::VerificationKeyMismatch {
+        expected,
+        actual: withdraw_vk_hash()?,
+    }));
+    Ok(())
+}
+
+#[e2e::test]
+async fn mixer_register_verifier_rejects_wrong_vk_hash(alice: Account) -> Result<()> {
+    let deployments = deploy_all_contracts(&alice).await?;
+    let mixer = IMixer::new(deployments.mixer, &alice.wallet);
+
+    let expected = FixedBytes::repeat_byte(0x01);
+    let err = send!(mixer.registerVerifier(
+        2,
+        deployments.verifier,
+        ULTRA_HONK_PROOF_SIZE,
+        expected
+    ))
+    .expect_err("should revert");
+    assert!(err.reverted_with(mixer::VerificationKeyMismatch {
+        expected,
+        actual: withdraw_vk_hash()?,
+    }));
     Ok(())
 }
 
@@ -136,10 +922,12 @@ struct ProofResponse {
 }
 
 #[derive(Deserialize)]
-struct CommitmentResponse {
-    commitment: String,
-    nullifier: String,
-    secret: String,
+struct AggregateProofResponse {
+    proof: String,
+    #[serde(rename = "publicInputs")]
+    public_inputs: Vec<String>,
+    #[serde(rename = "keyHash")]
+    key_hash: String,
 }
 
 struct DeployedContracts {
@@ -149,27 +937,18 @@ struct DeployedContracts {
 }
 
 fn generate_commitment() -> eyre::Result<(FixedBytes<32>, FixedBytes<32>, FixedBytes<32>)> {
-    let root = repo_root();
-    let script = root.join("scripts/js/generateCommitment.ts");
-
-    let mut args: Vec<String> = vec![
-        "tsx".to_string(),
-        script.to_str().expect("valid script path").to_string(),
-    ];
-    let output = Command::new("npx").args(args).current_dir(&root).output()?;
-    let s = String::from_utf8(output.stdout)?;
-    let resp: CommitmentResponse = serde_json::from_str(&s)?;
-    let commitment = hex_to_fixed_bytes(&resp.commitment)?;
-    let nullifier = hex_to_fixed_bytes(&resp.nullifier)?;
-    let secret = hex_to_fixed_bytes(&resp.secret)?;
-
-    Ok((commitment, nullifier, secret))
+    /* only the commitment is used here, so the deployment never has to match */
+    let deployment = Deployment::eth(U256::ZERO, 0, Address::ZERO);
+    let note = Note::random(deployment, &mut rand::thread_rng());
+    Ok((note.commitment(), note.nullifier(), note.secret()))
 }
 
 fn generate_proof(
     nullifier: FixedBytes<32>,
     secret: FixedBytes<32>,
     recipient: Address,
+    chain_id: u64,
+    pool: Address,
     leaves: Vec<FixedBytes<32>>,
 ) -> eyre::Result<(Vec<u8>, Vec<FixedBytes<32>>)> {
     let root = repo_root();
@@ -181,6 +960,8 @@ fn generate_proof(
         nullifier.to_string(),
         secret.to_string(),
         recipient.into_word().to_string(),
+        chain_id.to_string(),
+        pool.into_word().to_string(),
     ];
     for leaf in &leaves {
         args.push(leaf.to_string());
@@ -199,90 +980,80 @@ fn generate_proof(
     Ok((proof, public_inputs))
 }
 
-fn mixer_wasm_path() -> eyre::Result<PathBuf> {
+/* notes are (nullifier, secret, recipient), returns (proof, public inputs, withdraw key hash) */
+fn generate_aggregate_proof(
+    notes: &[(FixedBytes<32>, FixedBytes<32>, Address)],
+    chain_id: u64,
+    pool: Address,
+    leaves: Vec<FixedBytes<32>>,
+) -> eyre::Result<(Vec<u8>, Vec<FixedBytes<32>>, FixedBytes<32>)> {
     let root = repo_root();
-    let file = "stylus_mixer.wasm";
-    let path = root
-        .join("contracts/mixer/target/wasm32-unknown-unknown/release")
-        .join(file);
-    Ok(path)
+    let script = root.join("scripts/js/generateAggregateProof.ts");
+
+    let mut args: Vec<String> = vec![
+        "tsx".to_string(),
+        script.to_str().expect("valid script path").to_string(),
+        chain_id.to_string(),
+        pool.into_word().to_string(),
+        notes.len().to_string(),
+    ];
+    for (nullifier, secret, recipient) in notes {
+        args.push(nullifier.to_string());
+        args.push(secret.to_string());
+        args.push(recipient.into_word().to_string());
+    }
+    for leaf in &leaves {
+        args.push(leaf.to_string());
+    }
+
+    let output = Command::new("npx").args(args).current_dir(&root).output()?;
+    let s = String::from_utf8(output.stdout)?;
+    let resp: AggregateProofResponse = serde_json::from_str(&s)?;
+    let proof = hex_to_vec(&resp.proof)?;
+    let public_inputs = resp
+        .public_inputs
+        .iter()
+        .map(|s| hex_to_fixed_bytes(s))
+        .collect::<eyre::Result<Vec<_>>>()?;
+    let key_hash = hex_to_fixed_bytes(&resp.key_hash)?;
+
+    Ok((proof, public_inputs, key_hash))
+}
+
+fn mixer_wasm_path() -> eyre::Result<PathBuf> {
+    Ok(repo_root().join(MIXER_WASM))
 }
 
 fn imt_wasm_path() -> eyre::Result<PathBuf> {
-    let root = repo_root();
-    let file = "stylus_imt.wasm";
-    let path = root
-        .join("contracts/imt/target/wasm32-unknown-unknown/release")
-        .join(file);
-    Ok(path)
+    Ok(repo_root().join(IMT_WASM))
 }
 
-#[derive(Deserialize)]
-struct ForgeCreateOutput {
-    #[serde(rename = "deployedTo")]
-    deployed_to: String,
+fn withdraw_vk_hash() -> eyre::Result<FixedBytes<32>> {
+    Ok(vk_hash_from_file(repo_root().join("circuits/target/vk"))?)
 }
 
-fn deploy_verifier() -> eyre::Result<Address> {
-    let root = repo_root();
-    let mixer_dir = root.join("contracts/mixer");
-
-    let build = Command::new("forge")
-        .args(["build", "--sizes"])
-        .current_dir(&mixer_dir)
-        .output()
-        .wrap_err("forge build failed")?;
-    if !build.status.success() {
-        return Err(eyre::eyre!(format!(
-            "forge build exited with status {}: {}",
-            build.status,
-            String::from_utf8_lossy(&build.stderr)
-        )));
-    }
+fn aggregate_vk_hash() -> eyre::Result<FixedBytes<32>> {
+    Ok(vk_hash_from_file(repo_root().join("circuits/aggregate/target/vk"))?)
+}
 
-    let output = Command::new("forge")
-        .args([
-            "create",
-            "src/Verifier.sol:HonkVerifier",
-            "--rpc-url",
-            "http://localhost:8547",
-            "--private-key",
-            "0xb6b15c8cb491557369f3c7d2c287b053eb229daa9c22138887752191c9520659",
-            "--broadcast",
-        ])
-        .current_dir(&mixer_dir)
-        .output()
-        .wrap_err("forge create failed")?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(eyre::eyre!(format!(
-            "forge create failed with status {status}: {stderr}",
-            status = output.status
-        )));
-    }
+/* scripts/build_circuits.sh writes both, each pinned to the vk it was generated from */
+fn deploy_verifier() -> eyre::Result<Address> {
+    deploy_solidity("src/PinnedVerifier.sol:PinnedHonkVerifier")
+}
 
-    let stdout = String::from_utf8(output.stdout)?;
-    let deployed_to = if let Ok(parsed) = serde_json::from_str::<ForgeCreateOutput>(&stdout) {
-        parsed.deployed_to
-    } else {
-        stdout
-            .lines()
-            .find_map(|line| {
-                line.split_once("Deployed to:")
-                    .map(|(_, rhs)| rhs.trim().to_string())
-            })
-            .ok_or_else(|| eyre::eyre!("forge create output missing deployment address"))?
-    };
+fn deploy_aggregate_verifier() -> eyre::Result<Address> {
+    deploy_solidity("src/aggregate/PinnedVerifier.sol:PinnedHonkVerifier")
+}
 
-    let address = Address::from_str(&deployed_to).wrap_err("invalid verifier address")?;
-    Ok(address)
+fn deploy_solidity(contract: &str) -> eyre::Result<Address> {
+    Ok(Tools::dev()?.forge_create(contract)?.address)
 }
-async fn deploy_imt(alice: &Account) -> Result<Address> {
+
+async fn deploy_imt(alice: &Account, depth: U256) -> Result<Address> {
     let imt_wasm = imt_wasm_path()?;
     let imt_rcpt = alice
         .as_deployer()
-        .with_constructor(constructor!(uint!(15_U256)))
+        .with_constructor(constructor!(depth))
         .deploy_wasm(&imt_wasm)
         .await?;
     Ok(imt_rcpt.contract_address)
@@ -292,20 +1063,31 @@ async fn deploy_mixer(
     alice: &Account,
     verifier_addr: Address,
     imt_addr: Address,
+    vk_hash: FixedBytes<32>,
+    circuit_depth: u32,
+    denomination: U256,
 ) -> Result<Address> {
     let mixer_wasm = mixer_wasm_path()?;
     let mixer_rcpt = alice
         .as_deployer()
-        .with_constructor(constructor!(verifier_addr, imt_addr))
+        .with_constructor(constructor!(
+            verifier_addr,
+            imt_addr,
+            alice.address(),
+            vk_hash,
+            ULTRA_HONK_PROOF_SIZE,
+            circuit_depth,
+            denomination
+        ))
         .deploy_wasm(&mixer_wasm)
         .await?;
     Ok(mixer_rcpt.contract_address)
 }
 
 async fn deploy_all_contracts(alice: &Account) -> Result<DeployedContracts> {
-    let imt = deploy_imt(alice).await?;
+    let imt = deploy_imt(alice, uint!(15_U256)).await?;
     let verifier = deploy_verifier()?;
-    let mixer = deploy_mixer(alice, verifier, imt).await?;
+    let mixer = deploy_mixer(alice, verifier, imt, withdraw_vk_hash()?, 15, DENOMINATION).await?;
     Ok(DeployedContracts {
         imt,
         mixer,
@@ -323,6 +1105,10 @@ fn repo_root() -> PathBuf {
         .to_path_buf()
 }
 
+async fn chain_id(account: &Account) -> eyre::Result<u64> {
+    Ok(account.wallet.get_chain_id().await?)
+}
+
 async fn block_timestamp(account: &Account) -> eyre::Result<u64> {
     let timestamp = account
         .wallet
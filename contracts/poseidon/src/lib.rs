@@ -2,8 +2,15 @@
 extern crate alloc;
 
 mod params;
+#[cfg(feature = "poseidon-v1")]
+mod params_v1;
+#[cfg(feature = "poseidon-v1")]
+pub mod poseidon_v1;
 mod sponge;
 
+pub use params::{StylusBN256Params, StylusBN256T2Params, StylusBN256T3Params};
+pub use sponge::{hash, hash_with_domain};
+
 use alloy_primitives::FixedBytes;
 use openzeppelin_crypto::{
     arithmetic::{uint::U256, BigInteger},
@@ -11,22 +18,76 @@ use openzeppelin_crypto::{
 };
 
 #[cfg(feature = "contract")]
-use stylus_sdk::prelude::*;
+use stylus_sdk::{abi::Bytes as AbiBytes, prelude::*};
 
-#[cfg(feature = "contract")]
+/* largest whole number of bytes that always fits below the BN254 modulus */
+const BYTES_PER_FIELD: usize = 31;
+
+/* domain tags for `hash_with_domain_fixed_bytes`, 0 is reserved for the untagged hash */
+pub const DOMAIN_COMMITMENT: u64 = 1;
+pub const DOMAIN_NULLIFIER: u64 = 2;
+pub const DOMAIN_NODE: u64 = 3;
+
+#[cfg(all(feature = "contract", not(feature = "poseidon-v1")))]
 #[entrypoint]
 #[storage]
 struct Poseidon;
 
+/* circomlib-compatible hasher, deployed instead of `Poseidon` when `poseidon-v1` is enabled */
+#[cfg(all(feature = "contract", feature = "poseidon-v1"))]
+#[entrypoint]
+#[storage]
+struct PoseidonV1;
+
 /* =====================================================================
  *                               Contract
  * ====================================================================== */
-#[cfg(feature = "contract")]
+#[cfg(all(feature = "contract", not(feature = "poseidon-v1")))]
 #[public]
 impl Poseidon {
     fn hash(&self, inputs: [alloy_primitives::U256; 2]) -> alloy_primitives::U256 {
         let fp_inputs = inputs.map(|input| FpBN256::from_bigint(U256::from(input)));
-        let hash = sponge::hash(&fp_inputs, fp_inputs.len(), false);
+        let hash = sponge::hash::<StylusBN256Params>(&fp_inputs, fp_inputs.len(), false);
+        hash.into_bigint().into()
+    }
+
+    fn hash_single(&self, input: alloy_primitives::U256) -> alloy_primitives::U256 {
+        let fp_inputs = [FpBN256::from_bigint(U256::from(input))];
+        let hash = sponge::hash::<StylusBN256T2Params>(&fp_inputs, fp_inputs.len(), false);
+        hash.into_bigint().into()
+    }
+
+    fn hash_with_domain(
+        &self,
+        domain: u64,
+        inputs: Vec<alloy_primitives::U256>,
+    ) -> alloy_primitives::U256 {
+        let fp_inputs: Vec<FpBN256> = inputs
+            .into_iter()
+            .map(|input| FpBN256::from_bigint(U256::from(input)))
+            .collect();
+        let hash = sponge::hash_with_domain::<StylusBN256Params>(
+            domain,
+            &fp_inputs,
+            fp_inputs.len(),
+            false,
+        );
+        hash.into_bigint().into()
+    }
+
+    fn hash_bytes(&self, data: AbiBytes) -> alloy_primitives::U256 {
+        let fp_inputs = pack_bytes(data.as_slice());
+        let hash = sponge::hash::<StylusBN256Params>(&fp_inputs, fp_inputs.len(), true);
+        hash.into_bigint().into()
+    }
+}
+
+#[cfg(all(feature = "contract", feature = "poseidon-v1"))]
+#[public]
+impl PoseidonV1 {
+    fn hash(&self, inputs: [alloy_primitives::U256; 2]) -> alloy_primitives::U256 {
+        let fp_inputs = inputs.map(|input| FpBN256::from_bigint(U256::from(input)));
+        let hash = poseidon_v1::hash(fp_inputs);
         hash.into_bigint().into()
     }
 }
@@ -36,7 +97,81 @@ impl Poseidon {
  * ====================================================================== */
 pub fn hash_two_fixed_bytes(left: FixedBytes<32>, right: FixedBytes<32>) -> FixedBytes<32> {
     let inputs = [fixed_bytes_to_fp(left), fixed_bytes_to_fp(right)];
-    fp_to_fixed_bytes(sponge::hash(&inputs, inputs.len(), false))
+    fp_to_fixed_bytes(sponge::hash::<StylusBN256Params>(
+        &inputs,
+        inputs.len(),
+        false,
+    ))
+}
+
+/// Fixed-length Poseidon2 (t = 4) of any number of elements, the same digest as bb.js
+/// `poseidon2Hash(inputs)` and Noir `Poseidon2::hash(inputs, inputs.len())`.
+pub fn hash_fixed_bytes(inputs: &[FixedBytes<32>]) -> FixedBytes<32> {
+    let inputs: Vec<FpBN256> = inputs.iter().copied().map(fixed_bytes_to_fp).collect();
+    fp_to_fixed_bytes(sponge::hash::<StylusBN256Params>(
+        &inputs,
+        inputs.len(),
+        false,
+    ))
+}
+
+/// Hashes a single element with the narrower t = 2 permutation.
+pub fn hash_one_fixed_bytes(value: FixedBytes<32>) -> FixedBytes<32> {
+    let inputs = [fixed_bytes_to_fp(value)];
+    fp_to_fixed_bytes(sponge::hash::<StylusBN256T2Params>(
+        &inputs,
+        inputs.len(),
+        false,
+    ))
+}
+
+/// circomlib `Poseidon(2)` of two field elements.
+#[cfg(feature = "poseidon-v1")]
+pub fn hash_two_fixed_bytes_v1(left: FixedBytes<32>, right: FixedBytes<32>) -> FixedBytes<32> {
+    let inputs = [fixed_bytes_to_fp(left), fixed_bytes_to_fp(right)];
+    fp_to_fixed_bytes(poseidon_v1::hash(inputs))
+}
+
+/// Poseidon2 hash with `domain` folded into the sponge IV, so the same inputs hashed
+/// under different roles (commitment, nullifier, tree node) never produce the same digest.
+/// Mirrored by `hash_with_domain` in `circuits/domain/src/domain.nr`.
+pub fn hash_with_domain_fixed_bytes(domain: u64, inputs: &[FixedBytes<32>]) -> FixedBytes<32> {
+    let inputs: Vec<FpBN256> = inputs.iter().copied().map(fixed_bytes_to_fp).collect();
+    fp_to_fixed_bytes(sponge::hash_with_domain::<StylusBN256Params>(
+        domain,
+        &inputs,
+        inputs.len(),
+        false,
+    ))
+}
+
+/// Poseidon2 digest of an arbitrary byte string.
+///
+/// The input is split into 31-byte chunks, each read as a big-endian field element
+/// with the last chunk right-padded with zeros, and the byte length is appended as a
+/// final element so inputs that differ only in trailing zeros do not collide. The
+/// elements are absorbed in variable-length mode (`iv = elements << 64`, then a
+/// trailing `1`). `hash_bytes` in `circuits/src/bytes.nr` recomputes the same digest.
+pub fn hash_bytes(bytes: &[u8]) -> FixedBytes<32> {
+    let inputs = pack_bytes(bytes);
+    fp_to_fixed_bytes(sponge::hash::<StylusBN256Params>(
+        &inputs,
+        inputs.len(),
+        true,
+    ))
+}
+
+fn pack_bytes(bytes: &[u8]) -> Vec<FpBN256> {
+    let mut elements: Vec<FpBN256> = bytes
+        .chunks(BYTES_PER_FIELD)
+        .map(|chunk| {
+            let mut be_bytes = [0u8; 32];
+            be_bytes[1..1 + chunk.len()].copy_from_slice(chunk);
+            fixed_bytes_to_fp(FixedBytes::<32>::from(be_bytes))
+        })
+        .collect();
+    elements.push(FpBN256::from_bigint(U256::from(bytes.len() as u64)));
+    elements
 }
 
 fn fixed_bytes_to_fp(value: FixedBytes<32>) -> FpBN256 {
@@ -0,0 +1,59 @@
+use openzeppelin_crypto::field::instance::FpBN256;
+
+use crate::params_v1::{MDS, ROUNDS_F, ROUNDS_P, ROUND_CONSTANTS, T};
+
+/* circomlib `Poseidon(2)`: state is `[0, left, right]` and the digest is `state[0]` */
+pub fn hash(inputs: [FpBN256; T - 1]) -> FpBN256 {
+    let mut state = [FpBN256::ZERO; T];
+    state[1..].copy_from_slice(&inputs);
+
+    permutation(&mut state);
+
+    state[0]
+}
+
+fn permutation(state: &mut [FpBN256; T]) {
+    let full_rounds_half = ROUNDS_F / 2;
+
+    for (round, constants) in ROUND_CONSTANTS.iter().enumerate() {
+        add_round_constants(state, constants);
+
+        if round < full_rounds_half || round >= full_rounds_half + ROUNDS_P {
+            s_box(state);
+        } else {
+            state[0] = single_box(state[0]);
+        }
+
+        mds_multiplication(state);
+    }
+}
+
+fn add_round_constants(state: &mut [FpBN256; T], constants: &[FpBN256; T]) {
+    for (value, constant) in state.iter_mut().zip(constants.iter()) {
+        *value += *constant;
+    }
+}
+
+fn s_box(state: &mut [FpBN256; T]) {
+    for value in state.iter_mut() {
+        *value = single_box(*value);
+    }
+}
+
+fn single_box(x: FpBN256) -> FpBN256 {
+    let x2 = x * x;
+    let x4 = x2 * x2;
+    x4 * x
+}
+
+fn mds_multiplication(state: &mut [FpBN256; T]) {
+    let mut result = [FpBN256::ZERO; T];
+
+    for (out, row) in result.iter_mut().zip(MDS.iter()) {
+        for (value, entry) in state.iter().zip(row.iter()) {
+            *out += *value * *entry;
+        }
+    }
+
+    *state = result;
+}
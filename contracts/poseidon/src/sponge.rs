@@ -1,20 +1,32 @@
-pub use sponge::hash;
+pub use sponge::{hash, hash_with_domain};
+
+use core::marker::PhantomData;
 
 use openzeppelin_crypto::{
     arithmetic::uint::U256, field::instance::FpBN256, poseidon2::params::PoseidonParams,
 };
 
-use crate::params::{StylusBN256Params, FULL_ROUND_CONSTANTS, PARTIAL_ROUND_CONSTANTS};
-
-const STATE_WIDTH: usize = StylusBN256Params::T;
-const RATE: usize = STATE_WIDTH - StylusBN256Params::CAPACITY;
+use crate::params::MAX_STATE_WIDTH;
 
 mod sponge {
     use super::*;
 
-    pub fn hash(inputs: &[FpBN256], std_input_length: usize, is_variable_length: bool) -> FpBN256 {
-        let iv = generate_iv(inputs.len());
-        let mut sponge = Sponge::new(iv);
+    pub fn hash<P: PoseidonParams<FpBN256>>(
+        inputs: &[FpBN256],
+        std_input_length: usize,
+        is_variable_length: bool,
+    ) -> FpBN256 {
+        hash_with_domain::<P>(0, inputs, std_input_length, is_variable_length)
+    }
+
+    pub fn hash_with_domain<P: PoseidonParams<FpBN256>>(
+        domain: u64,
+        inputs: &[FpBN256],
+        std_input_length: usize,
+        is_variable_length: bool,
+    ) -> FpBN256 {
+        let iv = generate_iv(inputs.len(), domain);
+        let mut sponge = Sponge::<P>::new(iv);
 
         for (index, input) in inputs.iter().copied().enumerate() {
             if index < std_input_length {
@@ -29,29 +41,37 @@ mod sponge {
         sponge.squeeze()
     }
 
-    fn generate_iv(input_length: usize) -> FpBN256 {
+    /* the length sits above bit 64 and the domain tag below it, domain 0 is the plain hash */
+    fn generate_iv(input_length: usize, domain: u64) -> FpBN256 {
         let mut iv = U256::from(input_length as u64);
         iv <<= 64;
-        FpBN256::from_bigint(iv)
+        FpBN256::from_bigint(iv) + FpBN256::from_bigint(U256::from(domain))
     }
 
-    struct Sponge {
-        state: [FpBN256; STATE_WIDTH],
-        cache: [FpBN256; RATE],
+    /* only the first `P::T` (state) and `P::T - P::CAPACITY` (cache) slots are ever used */
+    struct Sponge<P: PoseidonParams<FpBN256>> {
+        state: [FpBN256; MAX_STATE_WIDTH],
+        cache: [FpBN256; MAX_STATE_WIDTH],
         cache_size: usize,
         squeeze_mode: bool,
+        _params: PhantomData<P>,
     }
 
-    impl Sponge {
+    impl<P: PoseidonParams<FpBN256>> Sponge<P> {
+        const RATE: usize = P::T - P::CAPACITY;
+
         fn new(iv: FpBN256) -> Self {
-            let mut state = [FpBN256::ZERO; STATE_WIDTH];
-            state[RATE] = iv;
+            assert!(P::T <= MAX_STATE_WIDTH, "unsupported state width");
+
+            let mut state = [FpBN256::ZERO; MAX_STATE_WIDTH];
+            state[Self::RATE] = iv;
 
             Self {
                 state,
-                cache: [FpBN256::ZERO; RATE],
+                cache: [FpBN256::ZERO; MAX_STATE_WIDTH],
                 cache_size: 0,
                 squeeze_mode: false,
+                _params: PhantomData,
             }
         }
 
@@ -60,7 +80,7 @@ mod sponge {
                 panic!("cannot absorb while squeezing");
             }
 
-            if self.cache_size == RATE {
+            if self.cache_size == Self::RATE {
                 self.perform_duplex();
                 self.cache[0] = input;
                 self.cache_size = 1;
@@ -72,14 +92,12 @@ mod sponge {
 
         fn squeeze(&mut self) -> FpBN256 {
             if !self.squeeze_mode {
-                let new_elements = self.perform_duplex();
-                self.cache = new_elements;
-                self.cache_size = RATE;
+                self.cache = self.perform_duplex();
+                self.cache_size = Self::RATE;
                 self.squeeze_mode = true;
             } else if self.cache_size == 0 {
-                let new_elements = self.perform_duplex();
-                self.cache = new_elements;
-                self.cache_size = RATE;
+                self.cache = self.perform_duplex();
+                self.cache_size = Self::RATE;
             }
 
             let result = self.cache[0];
@@ -94,57 +112,57 @@ mod sponge {
             result
         }
 
-        fn perform_duplex(&mut self) -> [FpBN256; RATE] {
-            for index in self.cache_size..RATE {
+        fn perform_duplex(&mut self) -> [FpBN256; MAX_STATE_WIDTH] {
+            for index in self.cache_size..Self::RATE {
                 self.cache[index] = FpBN256::ZERO;
             }
 
-            for index in 0..RATE {
+            for index in 0..Self::RATE {
                 self.state[index] += self.cache[index];
             }
 
-            permutation(&mut self.state);
+            permutation::<P>(&mut self.state[..P::T]);
 
-            let mut result = [FpBN256::ZERO; RATE];
-            for index in 0..RATE {
-                result[index] = self.state[index];
-            }
+            let mut result = [FpBN256::ZERO; MAX_STATE_WIDTH];
+            result[..Self::RATE].copy_from_slice(&self.state[..Self::RATE]);
 
             result
         }
     }
 
-    fn permutation(state: &mut [FpBN256; STATE_WIDTH]) {
-        matrix_multiplication_4x4(state);
+    fn permutation<P: PoseidonParams<FpBN256>>(state: &mut [FpBN256]) {
+        external_m_multiplication(state);
 
-        let full_rounds_half = StylusBN256Params::ROUNDS_F / 2;
+        let full_rounds_half = P::ROUNDS_F / 2;
+        let (first_full, rest) = P::ROUND_CONSTANTS.split_at(full_rounds_half);
+        let (partial, last_full) = rest.split_at(P::ROUNDS_P);
 
-        for constants in FULL_ROUND_CONSTANTS[..full_rounds_half].iter() {
+        for constants in first_full.iter() {
             add_round_constants(state, constants);
             s_box(state);
-            matrix_multiplication_4x4(state);
+            external_m_multiplication(state);
         }
 
-        for constant in PARTIAL_ROUND_CONSTANTS.iter() {
-            state[0] += *constant;
+        for constants in partial.iter() {
+            state[0] += constants[0];
             state[0] = single_box(state[0]);
-            internal_m_multiplication(state);
+            internal_m_multiplication::<P>(state);
         }
 
-        for constants in FULL_ROUND_CONSTANTS[full_rounds_half..].iter() {
+        for constants in last_full.iter() {
             add_round_constants(state, constants);
             s_box(state);
-            matrix_multiplication_4x4(state);
+            external_m_multiplication(state);
         }
     }
 
-    fn add_round_constants(state: &mut [FpBN256; STATE_WIDTH], constants: &[FpBN256]) {
+    fn add_round_constants(state: &mut [FpBN256], constants: &[FpBN256]) {
         for (value, constant) in state.iter_mut().zip(constants.iter()) {
             *value += *constant;
         }
     }
 
-    fn s_box(state: &mut [FpBN256; STATE_WIDTH]) {
+    fn s_box(state: &mut [FpBN256]) {
         for value in state.iter_mut() {
             *value = single_box(*value);
         }
@@ -156,22 +174,39 @@ mod sponge {
         x4 * x
     }
 
-    fn internal_m_multiplication(state: &mut [FpBN256; STATE_WIDTH]) {
+    fn internal_m_multiplication<P: PoseidonParams<FpBN256>>(state: &mut [FpBN256]) {
         let sum = state
             .iter()
             .copied()
             .fold(FpBN256::ZERO, |acc, value| acc + value);
 
-        for (value, diagonal) in state
-            .iter_mut()
-            .zip(StylusBN256Params::MAT_INTERNAL_DIAG_M_1.iter())
-        {
+        for (value, diagonal) in state.iter_mut().zip(P::MAT_INTERNAL_DIAG_M_1.iter()) {
             *value *= *diagonal;
             *value += sum;
         }
     }
 
-    fn matrix_multiplication_4x4(state: &mut [FpBN256; STATE_WIDTH]) {
+    /* the external matrix depends only on the width: circ(2, 1) for t = 2, circ(2, 1, 1) for t = 3, M4 for t = 4 */
+    fn external_m_multiplication(state: &mut [FpBN256]) {
+        match state.len() {
+            2 | 3 => matrix_multiplication_circulant(state),
+            4 => matrix_multiplication_4x4(state),
+            _ => panic!("unsupported state width"),
+        }
+    }
+
+    fn matrix_multiplication_circulant(state: &mut [FpBN256]) {
+        let sum = state
+            .iter()
+            .copied()
+            .fold(FpBN256::ZERO, |acc, value| acc + value);
+
+        for value in state.iter_mut() {
+            *value += sum;
+        }
+    }
+
+    fn matrix_multiplication_4x4(state: &mut [FpBN256]) {
         let a = state[0];
         let b = state[1];
         let c = state[2];
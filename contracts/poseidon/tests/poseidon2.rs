@@ -1,18 +1,16 @@
-#![cfg(feature = "e2e")]
+#![cfg(all(feature = "e2e", not(feature = "poseidon-v1")))]
 
 use alloy_primitives::{hex, uint, U256};
 use e2e::Account;
 use eyre::Result;
-
-use crate::abi::PoseidonAbi;
-mod abi;
+use stylus_mixer_bindings::poseidon::IPoseidon;
 
 #[e2e::test]
 async fn poseidon_works(alice: Account) -> Result<()> {
     let contract_addr = alice.as_deployer().deploy().await?.contract_address;
-    let contract = PoseidonAbi::new(contract_addr, &alice.wallet);
+    let contract = IPoseidon::new(contract_addr, &alice.wallet);
 
-    let PoseidonAbi::hashReturn { hash } = contract
+    let IPoseidon::hashReturn { _0: hash } = contract
         .hash([uint!(123_U256), uint!(123456_U256)])
         .call()
         .await?;
@@ -29,7 +27,7 @@ async fn poseidon_works(alice: Account) -> Result<()> {
 #[e2e::test]
 async fn poseidon_known_vector(alice: Account) -> Result<()> {
     let contract_addr = alice.as_deployer().deploy().await?.contract_address;
-    let contract = PoseidonAbi::new(contract_addr, &alice.wallet);
+    let contract = IPoseidon::new(contract_addr, &alice.wallet);
 
     let input_a = U256::from_be_slice(&hex!(
         "29f5cdf6eb8e04fd0f33b56f3c6bac311204572ba750cbeae363d2c6321dbb23"
@@ -38,7 +36,7 @@ async fn poseidon_known_vector(alice: Account) -> Result<()> {
         "1b1d665fb1656592e865b53c2e09b2d88000cb64bb86f09ccf85b75378b7fdf1"
     ));
 
-    let PoseidonAbi::hashReturn { hash } = contract.hash([input_a, input_b]).call().await?;
+    let IPoseidon::hashReturn { _0: hash } = contract.hash([input_a, input_b]).call().await?;
 
     let expected = U256::from_be_slice(&hex!(
         "0fc5f23f3d0c1c4d0c2723d576b84e883e1c4dad6325104f18572c1057017eeb"
@@ -48,3 +46,112 @@ async fn poseidon_known_vector(alice: Account) -> Result<()> {
 
     Ok(())
 }
+
+#[e2e::test]
+async fn poseidon_hash_single_uses_t2_instance(alice: Account) -> Result<()> {
+    let contract_addr = alice.as_deployer().deploy().await?.contract_address;
+    let contract = IPoseidon::new(contract_addr, &alice.wallet);
+
+    let IPoseidon::hashSingleReturn { _0: hash } =
+        contract.hashSingle(uint!(123_U256)).call().await?;
+
+    let expected = U256::from_be_slice(&hex!(
+        "0c4652d51d45a7bac770a52d01f689536fa725e35ee348b20a9aafded3891cde"
+    ));
+
+    assert_eq!(hash, expected);
+
+    Ok(())
+}
+
+#[e2e::test]
+async fn poseidon_hash_bytes_known_vectors(alice: Account) -> Result<()> {
+    let contract_addr = alice.as_deployer().deploy().await?.contract_address;
+    let contract = IPoseidon::new(contract_addr, &alice.wallet);
+
+    let vectors: [(&[u8], [u8; 32]); 3] = [
+        (
+            b"",
+            hex!("057985cb452f268c650a6600d9410f49ebd2fd9756b8c89f38cf80a72e5a4f1a"),
+        ),
+        (
+            b"hello",
+            hex!("261cf87638d5a1ed69fd9523994caa2dfe80517e254893cf2024d59c8e88c8f8"),
+        ),
+        (
+            b"stylus_mixer: hashing a byte string longer than 31 bytes",
+            hex!("13825e2d894c38aa5c9f8920e04317a5254cef8b21859e63fbeb535127692559"),
+        ),
+    ];
+
+    for (data, expected) in vectors {
+        let IPoseidon::hashBytesReturn { _0: hash } =
+            contract.hashBytes(data.to_vec().into()).call().await?;
+        assert_eq!(hash, U256::from_be_slice(&expected));
+    }
+
+    Ok(())
+}
+
+#[e2e::test]
+async fn poseidon_hash_bytes_binds_length(alice: Account) -> Result<()> {
+    let contract_addr = alice.as_deployer().deploy().await?.contract_address;
+    let contract = IPoseidon::new(contract_addr, &alice.wallet);
+
+    let IPoseidon::hashBytesReturn { _0: short } =
+        contract.hashBytes(vec![1u8].into()).call().await?;
+    let IPoseidon::hashBytesReturn { _0: padded } =
+        contract.hashBytes(vec![1u8, 0u8].into()).call().await?;
+
+    assert_ne!(short, padded);
+
+    Ok(())
+}
+
+#[e2e::test]
+async fn poseidon_hash_with_domain_separates_roles(alice: Account) -> Result<()> {
+    let contract_addr = alice.as_deployer().deploy().await?.contract_address;
+    let contract = IPoseidon::new(contract_addr, &alice.wallet);
+
+    let inputs = vec![uint!(123_U256), uint!(123456_U256)];
+
+    /* domain 0 is the untagged hash */
+    let IPoseidon::hashWithDomainReturn { _0: untagged } =
+        contract.hashWithDomain(0, inputs.clone()).call().await?;
+    let IPoseidon::hashReturn { _0: plain } = contract
+        .hash([uint!(123_U256), uint!(123456_U256)])
+        .call()
+        .await?;
+    assert_eq!(untagged, plain);
+
+    let IPoseidon::hashWithDomainReturn { _0: commitment } =
+        contract.hashWithDomain(1, inputs.clone()).call().await?;
+    assert_eq!(
+        commitment,
+        U256::from_be_slice(&hex!(
+            "2fd7f254d6058b02798bcde165e5cdff151981f2514db1b11d94351c664d7443"
+        ))
+    );
+
+    let IPoseidon::hashWithDomainReturn { _0: node } =
+        contract.hashWithDomain(3, inputs).call().await?;
+    assert_eq!(
+        node,
+        U256::from_be_slice(&hex!(
+            "19cb51eda2f63fa218950a0c224a26af1eaa16d16b7cb828a06a4d7d376e0872"
+        ))
+    );
+
+    let IPoseidon::hashWithDomainReturn { _0: nullifier } = contract
+        .hashWithDomain(2, vec![uint!(123_U256)])
+        .call()
+        .await?;
+    assert_eq!(
+        nullifier,
+        U256::from_be_slice(&hex!(
+            "1a01b1acf44263556c8224e51c17ea13e6d169cbbc3131adf4ccdb43fb75908b"
+        ))
+    );
+
+    Ok(())
+}
@@ -0,0 +1,45 @@
+#![cfg(all(feature = "e2e", feature = "poseidon-v1"))]
+
+use alloy_primitives::{hex, uint, U256};
+use e2e::Account;
+use eyre::Result;
+use stylus_mixer_bindings::poseidon::IPoseidon;
+
+/* vectors from circomlib's poseidon test suite */
+#[e2e::test]
+async fn poseidon_v1_matches_circomlib(alice: Account) -> Result<()> {
+    let contract_addr = alice.as_deployer().deploy().await?.contract_address;
+    let contract = IPoseidon::new(contract_addr, &alice.wallet);
+
+    let IPoseidon::hashReturn { _0: hash } = contract
+        .hash([uint!(1_U256), uint!(2_U256)])
+        .call()
+        .await?;
+
+    let expected = U256::from_be_slice(&hex!(
+        "115cc0f5e7d690413df64c6b9662e9cf2a3617f2743245519e19607a4417189a"
+    ));
+
+    assert_eq!(hash, expected);
+
+    Ok(())
+}
+
+#[e2e::test]
+async fn poseidon_v1_known_vector(alice: Account) -> Result<()> {
+    let contract_addr = alice.as_deployer().deploy().await?.contract_address;
+    let contract = IPoseidon::new(contract_addr, &alice.wallet);
+
+    let IPoseidon::hashReturn { _0: hash } = contract
+        .hash([uint!(3_U256), uint!(4_U256)])
+        .call()
+        .await?;
+
+    let expected = U256::from_be_slice(&hex!(
+        "20a3af0435914ccd84b806164531b0cd36e37d4efb93efab76913a93e1f30996"
+    ));
+
+    assert_eq!(hash, expected);
+
+    Ok(())
+}
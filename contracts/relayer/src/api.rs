@@ -0,0 +1,97 @@
+use std::sync::Arc;
+
+use alloy::{
+    primitives::{Address, Bytes, FixedBytes},
+    providers::Provider,
+    transports::http::{Client, Http},
+};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::relayer::{JobId, JobStatus, Rejection, Relayer};
+
+/// `proof.json` from `mixer-cli prove`, plus the circuit version to withdraw under.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WithdrawBody {
+    pub proof: Bytes,
+    pub public_inputs: Vec<FixedBytes<32>>,
+    #[serde(default = "default_circuit_version")]
+    pub circuit_version: u32,
+}
+
+fn default_circuit_version() -> u32 {
+    1
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RelayerStatus {
+    pub address: Address,
+    pub chain_id: u64,
+    pub pools: Vec<Address>,
+    pub in_flight: usize,
+}
+
+pub fn router<P>(relayer: Arc<Relayer<P>>) -> Router
+where
+    P: Provider<Http<Client>> + 'static,
+{
+    Router::new()
+        .route("/withdraw", post(withdraw::<P>))
+        .route("/status", get(status::<P>))
+        .route("/status/:id", get(job_status::<P>))
+        .with_state(relayer)
+}
+
+async fn withdraw<P>(
+    State(relayer): State<Arc<Relayer<P>>>,
+    Json(body): Json<WithdrawBody>,
+) -> Response
+where
+    P: Provider<Http<Client>> + 'static,
+{
+    match relayer
+        .submit(body.proof, &body.public_inputs, body.circuit_version)
+        .await
+    {
+        Ok(id) => (StatusCode::ACCEPTED, Json(json!({ "id": id }))).into_response(),
+        Err(rejection) => {
+            let (status, error) = match rejection {
+                Rejection::Invalid(error) => (StatusCode::BAD_REQUEST, error),
+                Rejection::Duplicate(error) => (StatusCode::CONFLICT, error),
+                Rejection::Upstream(error) => (StatusCode::BAD_GATEWAY, error),
+            };
+            (status, Json(json!({ "error": error }))).into_response()
+        }
+    }
+}
+
+async fn status<P>(State(relayer): State<Arc<Relayer<P>>>) -> Json<RelayerStatus>
+where
+    P: Provider<Http<Client>> + 'static,
+{
+    Json(RelayerStatus {
+        address: relayer.address(),
+        chain_id: relayer.chain_id(),
+        pools: relayer.pools().to_vec(),
+        in_flight: relayer.in_flight(),
+    })
+}
+
+async fn job_status<P>(
+    State(relayer): State<Arc<Relayer<P>>>,
+    Path(id): Path<JobId>,
+) -> Result<Json<JobStatus>, StatusCode>
+where
+    P: Provider<Http<Client>> + 'static,
+{
+    relayer.status(id).map(Json).ok_or(StatusCode::NOT_FOUND)
+}
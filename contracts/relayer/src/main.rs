@@ -0,0 +1,74 @@
+use std::net::SocketAddr;
+
+use alloy::{
+    network::EthereumWallet,
+    primitives::Address,
+    providers::{
+        fillers::{ChainIdFiller, GasFiller},
+        Provider, ProviderBuilder,
+    },
+    signers::local::PrivateKeySigner,
+    transports::http::reqwest::Url,
+};
+use clap::Parser;
+use eyre::{Result, WrapErr};
+use tokio::net::TcpListener;
+
+mod api;
+mod relayer;
+
+use relayer::Relayer;
+
+/* never a flag, command lines end up in shell history and `ps` */
+const PRIVATE_KEY_ENV: &str = "PRIVATE_KEY";
+
+#[derive(Parser)]
+#[command(
+    name = "mixer-relayer",
+    about = "Pays gas for mixer withdrawals",
+    after_help = "Withdrawals are submitted and paid for by the key in PRIVATE_KEY."
+)]
+struct Args {
+    /// JSON-RPC endpoint, a nitro dev node by default.
+    #[arg(long, env = "RPC_URL", default_value = "http://localhost:8547")]
+    rpc_url: Url,
+
+    /// Mixer to relay for, repeat for several pools.
+    #[arg(long = "pool", required = true)]
+    pools: Vec<Address>,
+
+    #[arg(long, env = "LISTEN", default_value = "127.0.0.1:8080")]
+    listen: SocketAddr,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let args = Args::parse();
+    let signer: PrivateKeySigner = std::env::var(PRIVATE_KEY_ENV)
+        .wrap_err_with(|| format!("{PRIVATE_KEY_ENV} is not set"))?
+        .parse()
+        .wrap_err_with(|| format!("{PRIVATE_KEY_ENV} is not a private key"))?;
+
+    let chain_id = ProviderBuilder::new()
+        .on_http(args.rpc_url.clone())
+        .get_chain_id()
+        .await
+        .wrap_err_with(|| format!("cannot reach {}", args.rpc_url))?;
+
+    /* no nonce filler, the queue assigns nonces itself */
+    let address = signer.address();
+    let provider = ProviderBuilder::new()
+        .filler(GasFiller)
+        .filler(ChainIdFiller::new(Some(chain_id)))
+        .wallet(EthereumWallet::from(signer))
+        .on_http(args.rpc_url);
+
+    let (relayer, queue) = Relayer::new(provider, address, chain_id, args.pools);
+    tokio::spawn(relayer::run(relayer.clone(), queue));
+
+    let listener = TcpListener::bind(args.listen).await?;
+    println!("relaying as {address} on chain {chain_id}, listening on {}", args.listen);
+    axum::serve(listener, api::router(relayer)).await?;
+
+    Ok(())
+}
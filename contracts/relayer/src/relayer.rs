@@ -0,0 +1,298 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, Mutex},
+};
+
+use alloy::{
+    primitives::{Address, Bytes, FixedBytes, B256},
+    providers::Provider,
+    transports::http::{Client, Http},
+};
+use serde::Serialize;
+use stylus_common::public_inputs::WithdrawPublicInputs;
+use stylus_mixer_bindings::{imt::IIMT, mixer::IMixer};
+use tokio::sync::mpsc;
+
+pub type JobId = u64;
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(tag = "status", rename_all = "camelCase")]
+pub enum JobStatus {
+    Queued,
+    #[serde(rename_all = "camelCase")]
+    Submitted { tx_hash: B256 },
+    #[serde(rename_all = "camelCase")]
+    Confirmed { tx_hash: B256, block_number: Option<u64> },
+    /// Sent, but the receipt could not be read. The note stays reserved, it may still be paid.
+    #[serde(rename_all = "camelCase")]
+    Unconfirmed { reason: String, tx_hash: B256 },
+    #[serde(rename_all = "camelCase")]
+    Failed { reason: String, tx_hash: Option<B256> },
+}
+
+/// A withdrawal that passed every check and waits for the signer.
+pub struct Job {
+    pub id: JobId,
+    pub proof: Bytes,
+    pub public: WithdrawPublicInputs,
+    pub circuit_version: u32,
+}
+
+/// Why a withdrawal was refused before it was queued.
+#[derive(Debug)]
+pub enum Rejection {
+    Invalid(String),
+    /// Spent on chain, or already queued here.
+    Duplicate(String),
+    /// The node failed to answer, the request itself may be fine.
+    Upstream(String),
+}
+
+#[derive(Default)]
+struct Jobs {
+    next_id: JobId,
+    statuses: HashMap<JobId, JobStatus>,
+    /* nullifier hashes of jobs that have not failed, so a note is never paid twice */
+    nullifiers: HashSet<FixedBytes<32>>,
+}
+
+pub struct Relayer<P> {
+    provider: P,
+    address: Address,
+    chain_id: u64,
+    pools: Vec<Address>,
+    jobs: Mutex<Jobs>,
+    queue: mpsc::UnboundedSender<Job>,
+}
+
+impl<P> Relayer<P>
+where
+    P: Provider<Http<Client>> + 'static,
+{
+    /// Returns the relayer and the receiving end of its queue, to be handed to [`run`].
+    pub fn new(
+        provider: P,
+        address: Address,
+        chain_id: u64,
+        pools: Vec<Address>,
+    ) -> (Arc<Self>, mpsc::UnboundedReceiver<Job>) {
+        let (queue, jobs) = mpsc::unbounded_channel();
+        let relayer = Arc::new(Self {
+            provider,
+            address,
+            chain_id,
+            pools,
+            jobs: Mutex::new(Jobs::default()),
+            queue,
+        });
+        (relayer, jobs)
+    }
+
+    pub fn address(&self) -> Address {
+        self.address
+    }
+
+    pub fn chain_id(&self) -> u64 {
+        self.chain_id
+    }
+
+    pub fn pools(&self) -> &[Address] {
+        &self.pools
+    }
+
+    pub fn status(&self, id: JobId) -> Option<JobStatus> {
+        self.jobs.lock().unwrap().statuses.get(&id).cloned()
+    }
+
+    /// Number of jobs that are queued or submitted but not mined yet.
+    pub fn in_flight(&self) -> usize {
+        self.jobs
+            .lock()
+            .unwrap()
+            .statuses
+            .values()
+            .filter(|status| matches!(status, JobStatus::Queued | JobStatus::Submitted { .. }))
+            .count()
+    }
+
+    /// Runs every check the contract would, then queues the withdrawal.
+    pub async fn submit(
+        &self,
+        proof: Bytes,
+        public_inputs: &[FixedBytes<32>],
+        circuit_version: u32,
+    ) -> Result<JobId, Rejection> {
+        let public = WithdrawPublicInputs::from_verifier_inputs(public_inputs).ok_or_else(|| {
+            Rejection::Invalid("public inputs are not withdraw circuit inputs".to_string())
+        })?;
+        if public.chain_id != self.chain_id {
+            return Err(Rejection::Invalid(format!(
+                "proof is for chain {}, this relayer is on chain {}",
+                public.chain_id, self.chain_id
+            )));
+        }
+        if !self.pools.contains(&public.pool) {
+            return Err(Rejection::Invalid(format!(
+                "pool {} is not served by this relayer",
+                public.pool
+            )));
+        }
+
+        let mixer = IMixer::new(public.pool, &self.provider);
+        let IMixer::isSpentReturn { _0: spent } = mixer
+            .isSpent(public.nullifier_hash)
+            .call()
+            .await
+            .map_err(|err| Rejection::Upstream(err.to_string()))?;
+        if spent {
+            return Err(Rejection::Duplicate("note is already spent".to_string()));
+        }
+
+        let IMixer::imtReturn { _0: imt } = mixer
+            .imt()
+            .call()
+            .await
+            .map_err(|err| Rejection::Upstream(err.to_string()))?;
+        let IIMT::isKnownRootReturn { _0: known } = IIMT::new(imt, &self.provider)
+            .isKnownRoot(public.root)
+            .call()
+            .await
+            .map_err(|err| Rejection::Upstream(err.to_string()))?;
+        if !known {
+            return Err(Rejection::Invalid(format!("unknown root {}", public.root)));
+        }
+
+        /* eth_call as the signer, catches a bad proof or a retired circuit version for free */
+        mixer
+            .withdraw(
+                proof.clone(),
+                public.root,
+                public.nullifier_hash,
+                public.recipient,
+                circuit_version,
+            )
+            .from(self.address)
+            .call()
+            .await
+            .map_err(|err| Rejection::Invalid(format!("withdraw would revert: {err}")))?;
+
+        let mut jobs = self.jobs.lock().unwrap();
+        if !jobs.nullifiers.insert(public.nullifier_hash) {
+            return Err(Rejection::Duplicate("note is already queued".to_string()));
+        }
+        let id = jobs.next_id;
+        jobs.next_id += 1;
+        jobs.statuses.insert(id, JobStatus::Queued);
+        drop(jobs);
+
+        self.queue
+            .send(Job {
+                id,
+                proof,
+                public,
+                circuit_version,
+            })
+            .map_err(|_| Rejection::Upstream("relayer is shutting down".to_string()))?;
+        Ok(id)
+    }
+
+    fn set_status(&self, id: JobId, status: JobStatus) {
+        self.jobs.lock().unwrap().statuses.insert(id, status);
+    }
+
+    /*
+     * a failed job frees its nullifier so the note can be relayed again, only for jobs whose
+     * transaction can no longer be mined: never sent, reverted or dropped
+     */
+    fn fail(&self, job: &Job, reason: String, tx_hash: Option<B256>) {
+        let mut jobs = self.jobs.lock().unwrap();
+        jobs.nullifiers.remove(&job.public.nullifier_hash);
+        jobs.statuses.insert(job.id, JobStatus::Failed { reason, tx_hash });
+    }
+}
+
+/// Sends queued withdrawals one by one, with nonces tracked locally so they never collide.
+pub async fn run<P>(relayer: Arc<Relayer<P>>, mut queue: mpsc::UnboundedReceiver<Job>)
+where
+    P: Provider<Http<Client>> + 'static,
+{
+    let mut nonce = None;
+
+    while let Some(job) = queue.recv().await {
+        let next = match nonce {
+            Some(next) => next,
+            None => match pending_nonce(&relayer).await {
+                Ok(next) => next,
+                Err(reason) => {
+                    relayer.fail(&job, reason, None);
+                    continue;
+                }
+            },
+        };
+
+        let sent = IMixer::new(job.public.pool, &relayer.provider)
+            .withdraw(
+                job.proof.clone(),
+                job.public.root,
+                job.public.nullifier_hash,
+                job.public.recipient,
+                job.circuit_version,
+            )
+            .nonce(next)
+            .send()
+            .await;
+
+        let pending = match sent {
+            Ok(pending) => pending,
+            Err(err) => {
+                /* the node may or may not have taken the nonce, ask again next time */
+                nonce = None;
+                relayer.fail(&job, err.to_string(), None);
+                continue;
+            }
+        };
+        nonce = Some(next + 1);
+
+        let tx_hash = *pending.tx_hash();
+        relayer.set_status(job.id, JobStatus::Submitted { tx_hash });
+
+        let relayer = Arc::clone(&relayer);
+        tokio::spawn(async move {
+            match pending.get_receipt().await {
+                Ok(receipt) if receipt.status() => relayer.set_status(
+                    job.id,
+                    JobStatus::Confirmed {
+                        tx_hash,
+                        block_number: receipt.block_number,
+                    },
+                ),
+                Ok(_) => relayer.fail(&job, "transaction reverted".to_string(), Some(tx_hash)),
+                Err(err) => match relayer.provider.get_transaction_by_hash(tx_hash).await {
+                    /* the node forgot it, it was dropped and can never be mined */
+                    Ok(None) => {
+                        relayer.fail(&job, format!("transaction dropped: {err}"), Some(tx_hash))
+                    }
+                    _ => relayer.set_status(
+                        job.id,
+                        JobStatus::Unconfirmed {
+                            reason: err.to_string(),
+                            tx_hash,
+                        },
+                    ),
+                },
+            }
+        });
+    }
+}
+
+async fn pending_nonce<P>(relayer: &Relayer<P>) -> Result<u64, String>
+where
+    P: Provider<Http<Client>> + 'static,
+{
+    relayer
+        .provider
+        .get_transaction_count(relayer.address)
+        .pending()
+        .await
+        .map_err(|err| format!("cannot read signer nonce: {err}"))
+}
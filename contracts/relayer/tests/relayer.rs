@@ -0,0 +1,222 @@
+#![cfg(feature = "e2e")]
+
+//! Runs `mixer-relayer` against the nitro dev node at `RPC_URL` (http://localhost:8547 by default).
+//! Relaying a real withdrawal also needs the wasm builds, `scripts/build_circuits.sh` output,
+//! forge, cargo-stylus and the node scripts in `scripts/js`.
+
+use std::{
+    net::TcpListener,
+    process::{Child, Command},
+    time::Duration,
+};
+
+use alloy::{
+    network::EthereumWallet,
+    primitives::{address, uint, Address, FixedBytes, U256},
+    providers::{Provider, ProviderBuilder},
+    signers::local::PrivateKeySigner,
+};
+use eyre::Result;
+use mixer_deploy::{checked_stdout, Tools};
+use serde_json::{json, Value};
+use stylus_mixer_bindings::mixer::IMixer;
+use stylus_mixer_client::{Deployment, Note};
+
+const POOL: Address = address!("5FbDB2315678afecb367f032d93F642f64180aa3");
+/* the depth `generateProof.ts` proves without CIRCUIT_DEPTH */
+const TREE_DEPTH: u32 = 15;
+const DENOMINATION: U256 = uint!(1_000_000_000_000_000_000_U256);
+
+struct RelayerProcess {
+    child: Child,
+    url: String,
+}
+
+impl Drop for RelayerProcess {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+    }
+}
+
+/* the dev node and its funded account, unless the environment names others */
+fn tools() -> Tools {
+    Tools::dev().expect("RPC_URL, PRIVATE_KEY and DEPLOYER_ADDRESS are valid")
+}
+
+async fn spawn_relayer(pool: Address) -> Result<RelayerProcess> {
+    let port = TcpListener::bind("127.0.0.1:0")?.local_addr()?.port();
+    let tools = tools();
+    let child = Command::new(env!("CARGO_BIN_EXE_mixer-relayer"))
+        .args(["--rpc-url", &tools.rpc_url])
+        .env("PRIVATE_KEY", &tools.private_key)
+        .args(["--pool", &pool.to_string()])
+        .args(["--listen", &format!("127.0.0.1:{port}")])
+        .spawn()?;
+    let relayer = RelayerProcess {
+        child,
+        url: format!("http://127.0.0.1:{port}"),
+    };
+
+    for _ in 0..50 {
+        if reqwest::get(format!("{}/status", relayer.url)).await.is_ok() {
+            return Ok(relayer);
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    eyre::bail!("relayer did not start")
+}
+
+async fn chain_id() -> Result<u64> {
+    let provider = ProviderBuilder::new().on_http(tools().rpc_url.parse()?);
+    Ok(provider.get_chain_id().await?)
+}
+
+fn public_inputs(chain_id: u64, pool: Address) -> Vec<FixedBytes<32>> {
+    vec![
+        FixedBytes::repeat_byte(1),
+        FixedBytes::repeat_byte(2),
+        address!("70997970C51812dc3A010C7d01b50e0d17dc79C8").into_word(),
+        U256::from(chain_id).into(),
+        pool.into_word(),
+    ]
+}
+
+/* the body `mixer-cli prove` writes to proof.json, for a note alone in its tree */
+fn prove(note: &Note, recipient: Address, chain_id: u64, pool: Address) -> Result<Value> {
+    let root = tools().root;
+    let script = root.join("scripts/js/generateProof.ts");
+    let output = Command::new("npx")
+        .arg("tsx")
+        .arg(script)
+        .arg(note.nullifier().to_string())
+        .arg(note.secret().to_string())
+        .arg(recipient.into_word().to_string())
+        .arg(chain_id.to_string())
+        .arg(pool.into_word().to_string())
+        .arg(note.commitment().to_string())
+        .current_dir(&root)
+        .output()?;
+    Ok(serde_json::from_str(&checked_stdout("generateProof.ts", output)?)?)
+}
+
+async fn post_withdraw(relayer: &RelayerProcess, body: Value) -> Result<(u16, Value)> {
+    let response = reqwest::Client::new()
+        .post(format!("{}/withdraw", relayer.url))
+        .json(&body)
+        .send()
+        .await?;
+    Ok((response.status().as_u16(), response.json().await?))
+}
+
+#[tokio::test]
+async fn status_reports_signer_and_chain() -> Result<()> {
+    let relayer = spawn_relayer(POOL).await?;
+    let status: Value = reqwest::get(format!("{}/status", relayer.url)).await?.json().await?;
+
+    let address: Address = status["address"].as_str().unwrap().parse()?;
+    assert_eq!(address, tools().sender()?);
+    assert_eq!(status["chainId"], chain_id().await?);
+    assert_eq!(status["pools"], json!([POOL]));
+    assert_eq!(status["inFlight"], 0);
+    Ok(())
+}
+
+#[tokio::test]
+async fn unknown_job_is_not_found() -> Result<()> {
+    let relayer = spawn_relayer(POOL).await?;
+    let response = reqwest::get(format!("{}/status/42", relayer.url)).await?;
+    assert_eq!(response.status().as_u16(), 404);
+    Ok(())
+}
+
+#[tokio::test]
+async fn withdraw_rejects_malformed_public_inputs() -> Result<()> {
+    let relayer = spawn_relayer(POOL).await?;
+    let (status, body) = post_withdraw(
+        &relayer,
+        json!({ "proof": "0x00", "publicInputs": [FixedBytes::<32>::ZERO] }),
+    )
+    .await?;
+    assert_eq!(status, 400);
+    assert!(body["error"].as_str().unwrap().contains("withdraw circuit inputs"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn withdraw_rejects_another_chain() -> Result<()> {
+    let relayer = spawn_relayer(POOL).await?;
+    let other_chain = chain_id().await? + 1;
+    let (status, body) = post_withdraw(
+        &relayer,
+        json!({ "proof": "0x00", "publicInputs": public_inputs(other_chain, POOL) }),
+    )
+    .await?;
+    assert_eq!(status, 400);
+    assert!(body["error"].as_str().unwrap().contains("chain"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn withdraw_rejects_unserved_pool() -> Result<()> {
+    let relayer = spawn_relayer(POOL).await?;
+    let other_pool = address!("e7f1725E7734CE288F8367e1Bb143E90bb3F0512");
+    let (status, body) = post_withdraw(
+        &relayer,
+        json!({ "proof": "0x00", "publicInputs": public_inputs(chain_id().await?, other_pool) }),
+    )
+    .await?;
+    assert_eq!(status, 400);
+    assert!(body["error"].as_str().unwrap().contains("not served"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn relays_a_withdrawal_once() -> Result<()> {
+    /* a fresh pool, so the deposited note is the tree's only leaf */
+    let tools = tools();
+    let pool = tools.deploy_pool(TREE_DEPTH, DENOMINATION)?;
+    let chain_id = chain_id().await?;
+    let recipient = PrivateKeySigner::random().address();
+
+    /* deposited before the relayer starts, both send from the dev account */
+    let deployment = Deployment::eth(DENOMINATION, chain_id, pool);
+    let note = Note::random(deployment, &mut rand::thread_rng());
+    let signer: PrivateKeySigner = tools.private_key.parse()?;
+    let provider = ProviderBuilder::new()
+        .with_recommended_fillers()
+        .wallet(EthereumWallet::from(signer))
+        .on_http(tools.rpc_url.parse()?);
+    IMixer::new(pool, &provider)
+        .deposit(note.commitment())
+        .value(DENOMINATION)
+        .send()
+        .await?
+        .get_receipt()
+        .await?;
+
+    let body = prove(&note, recipient, chain_id, pool)?;
+    let relayer = spawn_relayer(pool).await?;
+    let (status, accepted) = post_withdraw(&relayer, body.clone()).await?;
+    assert_eq!(status, 202, "{accepted}");
+
+    /* queued or already spent, the note is never relayed twice */
+    let (status, _) = post_withdraw(&relayer, body.clone()).await?;
+    assert_eq!(status, 409);
+
+    let url = format!("{}/status/{}", relayer.url, accepted["id"]);
+    let mut job: Value = Value::Null;
+    for _ in 0..100 {
+        job = reqwest::get(&url).await?.json().await?;
+        if !matches!(job["status"].as_str(), Some("queued" | "submitted")) {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    assert_eq!(job["status"], "confirmed", "{job}");
+    assert_eq!(provider.get_balance(recipient).await?, DENOMINATION);
+
+    let (status, rejected) = post_withdraw(&relayer, body).await?;
+    assert_eq!(status, 409);
+    assert!(rejected["error"].as_str().unwrap().contains("already spent"));
+    Ok(())
+}
@@ -0,0 +1,94 @@
+use core::ops::{Add, Mul, Neg, Sub};
+use stylus_sdk::alloy_primitives::{uint, FixedBytes, U256};
+
+/// BN254 scalar field modulus.
+pub const MODULUS: U256 =
+    uint!(21888242871839275222246405745257275088548364400416034343698204186575808495617_U256);
+
+/// Element of the BN254 scalar field, always reduced.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Fr(U256);
+
+impl Fr {
+    pub const ZERO: Self = Self(U256::ZERO);
+    pub const ONE: Self = Self(U256::from_limbs([1, 0, 0, 0]));
+
+    /* for constants already below the modulus */
+    pub(crate) const fn from_raw(value: U256) -> Self {
+        Self(value)
+    }
+
+    /// Reduces `value` modulo the field, as the Solidity verifier's `FrLib.from` does.
+    pub fn from_u256(value: U256) -> Self {
+        Self(value.reduce_mod(MODULUS))
+    }
+
+    pub fn from_u64(value: u64) -> Self {
+        Self(U256::from(value))
+    }
+
+    pub fn from_be_slice(bytes: &[u8]) -> Self {
+        Self::from_u256(U256::from_be_slice(bytes))
+    }
+
+    pub fn from_bytes32(bytes: FixedBytes<32>) -> Self {
+        Self::from_u256(U256::from_be_bytes(bytes.0))
+    }
+
+    pub fn into_u256(self) -> U256 {
+        self.0
+    }
+
+    pub fn to_be_bytes(self) -> [u8; 32] {
+        self.0.to_be_bytes::<32>()
+    }
+
+    pub fn is_zero(self) -> bool {
+        self.0.is_zero()
+    }
+
+    pub fn square(self) -> Self {
+        self * self
+    }
+
+    /// Multiplicative inverse, zero has none and maps to zero.
+    pub fn invert(self) -> Self {
+        Self(self.0.inv_mod(MODULUS).unwrap_or_default())
+    }
+}
+
+impl Add for Fr {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self(self.0.add_mod(rhs.0, MODULUS))
+    }
+}
+
+impl Sub for Fr {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        self + -rhs
+    }
+}
+
+impl Mul for Fr {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        Self(self.0.mul_mod(rhs.0, MODULUS))
+    }
+}
+
+impl Neg for Fr {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        if self.0.is_zero() {
+            self
+        } else {
+            Self(MODULUS - self.0)
+        }
+    }
+}
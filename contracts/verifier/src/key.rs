@@ -0,0 +1,76 @@
+use stylus_sdk::alloy_primitives::U256;
+
+use crate::{CONST_PROOF_SIZE_LOG_N, NUMBER_OF_PRECOMPUTED};
+
+/// Affine BN254 G1 point, the point at infinity is `(0, 0)` as for the precompiles.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct G1Point {
+    pub x: U256,
+    pub y: U256,
+}
+
+impl G1Point {
+    pub const GENERATOR: Self = Self {
+        x: U256::from_limbs([1, 0, 0, 0]),
+        y: U256::from_limbs([2, 0, 0, 0]),
+    };
+
+    fn from_be_slice(bytes: &[u8]) -> Self {
+        Self {
+            x: U256::from_be_slice(&bytes[..32]),
+            y: U256::from_be_slice(&bytes[32..64]),
+        }
+    }
+}
+
+/* header of four big-endian u64, then every precomputed commitment as x || y */
+const HEADER_SIZE: usize = 4 * 8;
+
+/// Serialized size of a `bb write_vk --oracle_hash keccak` UltraHonk key.
+pub const VK_SIZE: usize = HEADER_SIZE + NUMBER_OF_PRECOMPUTED * 64;
+
+/// UltraHonk verification key, as `bb write_vk` writes it.
+///
+/// `commitments` follow bb's precomputed entity order: q_m, q_c, q_l, q_r, q_o, q_4, q_lookup,
+/// q_arith, q_delta_range, q_elliptic, q_aux, q_poseidon2_external, q_poseidon2_internal,
+/// sigma_1..4, id_1..4, table_1..4, lagrange_first and lagrange_last.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VerificationKey {
+    pub circuit_size: u64,
+    pub log_circuit_size: u64,
+    pub num_public_inputs: u64,
+    pub pub_inputs_offset: u64,
+    pub commitments: [G1Point; NUMBER_OF_PRECOMPUTED],
+}
+
+impl VerificationKey {
+    /// `None` unless `bytes` is a whole key for a circuit of at most `2^28` gates.
+    pub fn parse(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() != VK_SIZE {
+            return None;
+        }
+        let word = |i: usize| {
+            let mut buf = [0u8; 8];
+            buf.copy_from_slice(&bytes[i * 8..(i + 1) * 8]);
+            u64::from_be_bytes(buf)
+        };
+        let (circuit_size, log_circuit_size) = (word(0), word(1));
+        if log_circuit_size == 0
+            || log_circuit_size > CONST_PROOF_SIZE_LOG_N as u64
+            || circuit_size != 1 << log_circuit_size
+        {
+            return None;
+        }
+
+        let commitments = core::array::from_fn(|i| {
+            G1Point::from_be_slice(&bytes[HEADER_SIZE + i * 64..HEADER_SIZE + (i + 1) * 64])
+        });
+        Some(Self {
+            circuit_size,
+            log_circuit_size,
+            num_public_inputs: word(2),
+            pub_inputs_offset: word(3),
+            commitments,
+        })
+    }
+}
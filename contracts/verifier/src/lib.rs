@@ -0,0 +1,184 @@
+#![cfg_attr(not(any(test, feature = "export-abi")), no_main)]
+extern crate alloc;
+
+pub mod field;
+pub mod key;
+pub mod proof;
+mod relations;
+pub mod shplemini;
+pub mod sumcheck;
+pub mod transcript;
+
+use alloc::vec::Vec;
+use stylus_common::{errors::HonkErrors, vk::vk_hash};
+use stylus_sdk::{
+    abi::Bytes as AbiBytes,
+    alloy_primitives::{address, uint, Address, FixedBytes, U256},
+    prelude::*,
+    storage::StorageBytes,
+};
+
+use crate::{
+    field::Fr,
+    key::{G1Point, VerificationKey},
+    proof::Proof,
+    transcript::{public_inputs_delta, Transcript},
+};
+
+/* proof shape of the UltraHonk flavour `bb` proves with `--oracle_hash keccak` */
+pub const CONST_PROOF_SIZE_LOG_N: usize = 28;
+pub const BATCHED_RELATION_PARTIAL_LENGTH: usize = 8;
+pub const NUMBER_OF_SUBRELATIONS: usize = 26;
+pub const NUMBER_OF_ALPHAS: usize = NUMBER_OF_SUBRELATIONS - 1;
+pub const NUMBER_OF_PRECOMPUTED: usize = 27;
+pub const NUMBER_OF_ENTITIES: usize = 40;
+pub const NUMBER_UNSHIFTED: usize = 35;
+
+/* BN254 precompiles (EIP-196 / EIP-197) */
+const EC_ADD: Address = address!("0x0000000000000000000000000000000000000006");
+const EC_MUL: Address = address!("0x0000000000000000000000000000000000000007");
+const EC_PAIRING: Address = address!("0x0000000000000000000000000000000000000008");
+
+/* base field modulus, used to negate the KZG quotient */
+const BASE_FIELD_MODULUS: U256 =
+    uint!(21888242871839275222246405745257275088696311157297823662689037894645226208583_U256);
+
+/* [1]_2 and [x]_2 of the Aztec ignition SRS, EIP-197 order (imaginary first) */
+const SRS_G2: [U256; 4] = [
+    uint!(0x198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2_U256),
+    uint!(0x1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed_U256),
+    uint!(0x090689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b_U256),
+    uint!(0x12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa_U256),
+];
+const SRS_X_G2: [U256; 4] = [
+    uint!(0x260e01b251f6f1c7e7ff4e580791dee8ea51d87a358e038b4efe30fac09383c1_U256),
+    uint!(0x0118c4d5b837bcc2bc89b5b398b5974e9f5944073b32078b7e231fec938883b0_U256),
+    uint!(0x04fc6369f7110fe3d25156c1bb9a72859cf2a04641f99ba4ee413c80da6a5fe4_U256),
+    uint!(0x22febda3c0c0632a56475b4214e5615e11e6dd3f96e6cea2854a87d4dacc5e55_U256),
+];
+
+#[entrypoint]
+#[storage]
+pub struct HonkVerifier {
+    /* `bb write_vk` output, kept as is so `vkHash()` matches `vk_hash_from_file` */
+    vk: StorageBytes,
+}
+
+/* ======================================================================
+ *                               Contract
+ * ====================================================================== */
+#[public]
+impl HonkVerifier {
+    #[constructor]
+    fn initialize(&mut self, vk: AbiBytes) -> Result<(), HonkErrors> {
+        if VerificationKey::parse(&vk).is_none() {
+            return Err(HonkErrors::invalid_verification_key());
+        }
+        self.vk.set_bytes(vk.as_slice());
+        Ok(())
+    }
+
+    fn verify(
+        &self,
+        proof: AbiBytes,
+        public_inputs: Vec<FixedBytes<32>>,
+    ) -> Result<bool, HonkErrors> {
+        let vk_bytes = self.vk.get_bytes();
+        let vk = VerificationKey::parse(&vk_bytes).expect("vk checked at construction");
+
+        let proof = Proof::new(&proof).ok_or_else(HonkErrors::proof_length_wrong)?;
+        if public_inputs.len() as u64 != vk.num_public_inputs {
+            return Err(HonkErrors::public_inputs_length_wrong());
+        }
+
+        let mut tp = Transcript::new(&proof, &public_inputs, &vk);
+        tp.relation_parameters.public_inputs_delta = public_inputs_delta(
+            &public_inputs,
+            tp.relation_parameters.beta,
+            tp.relation_parameters.gamma,
+            vk.circuit_size,
+            vk.pub_inputs_offset,
+        );
+
+        let log_n = vk.log_circuit_size as usize;
+        if !sumcheck::verify(&proof, &tp, log_n) {
+            return Err(HonkErrors::sumcheck_failed());
+        }
+
+        /* e(P0, [1]_2) * e(-W, [x]_2) == 1 */
+        let opening = shplemini::batch_opening(&proof, &vk, &tp, log_n);
+        let p0 = self.batch_mul(&opening.terms)?;
+        let p1 = negate(opening.kzg_quotient);
+        if !self.pairing(p0, p1)? {
+            return Err(HonkErrors::shplemini_failed());
+        }
+        Ok(true)
+    }
+
+    /* hash of the constructor's vk bytes */
+    fn vk_hash(&self) -> FixedBytes<32> {
+        vk_hash(&self.vk.get_bytes())
+    }
+}
+
+/* ======================================================================
+ *                         CONTRACT INTERNAL
+ * ====================================================================== */
+impl HonkVerifier {
+    fn batch_mul(&self, terms: &[(G1Point, Fr)]) -> Result<G1Point, HonkErrors> {
+        let mut accumulator = G1Point::default();
+        for (point, scalar) in terms {
+            let term = decode_g1(&self.precompile(
+                EC_MUL,
+                &[point.x, point.y, scalar.into_u256()],
+            )?)?;
+            accumulator = decode_g1(&self.precompile(
+                EC_ADD,
+                &[accumulator.x, accumulator.y, term.x, term.y],
+            )?)?;
+        }
+        Ok(accumulator)
+    }
+
+    fn pairing(&self, p0: G1Point, p1: G1Point) -> Result<bool, HonkErrors> {
+        let mut input: Vec<U256> = Vec::with_capacity(12);
+        input.extend_from_slice(&[p0.x, p0.y]);
+        input.extend_from_slice(&SRS_G2);
+        input.extend_from_slice(&[p1.x, p1.y]);
+        input.extend_from_slice(&SRS_X_G2);
+
+        let output = self.precompile(EC_PAIRING, &input)?;
+        Ok(output.len() == 32 && U256::from_be_slice(&output) == U256::from(1))
+    }
+
+    /* a failing precompile means a commitment is not on the curve */
+    fn precompile(&self, precompile: Address, words: &[U256]) -> Result<Vec<u8>, HonkErrors> {
+        let mut calldata: Vec<u8> = Vec::with_capacity(words.len() * 32);
+        for word in words {
+            calldata.extend_from_slice(&word.to_be_bytes::<32>());
+        }
+        self.vm()
+            .static_call(&self, precompile, &calldata)
+            .map_err(|_| HonkErrors::shplemini_failed())
+    }
+}
+
+fn decode_g1(output: &[u8]) -> Result<G1Point, HonkErrors> {
+    if output.len() != 64 {
+        return Err(HonkErrors::shplemini_failed());
+    }
+    Ok(G1Point {
+        x: U256::from_be_slice(&output[..32]),
+        y: U256::from_be_slice(&output[32..]),
+    })
+}
+
+fn negate(point: G1Point) -> G1Point {
+    if point.x.is_zero() && point.y.is_zero() {
+        return point;
+    }
+    G1Point {
+        x: point.x,
+        y: BASE_FIELD_MODULUS - (point.y % BASE_FIELD_MODULUS),
+    }
+}
@@ -0,0 +1,88 @@
+use stylus_sdk::alloy_primitives::U256;
+
+use crate::{
+    field::Fr, key::G1Point, BATCHED_RELATION_PARTIAL_LENGTH, CONST_PROOF_SIZE_LOG_N,
+    NUMBER_OF_ENTITIES,
+};
+
+/*
+ * proof layout in 32-byte words, for the bb version in package.json. Commitments are 4 words,
+ * x and y each split into a low 136-bit and a high limb:
+ *   w1 w2 w3 || lookup read counts, read tags || w4 || lookup inverses || z_perm
+ *   || 28 * 8 sumcheck univariates || 40 sumcheck evaluations
+ *   || 27 gemini fold commitments || 28 gemini evaluations || shplonk Q || KZG quotient
+ */
+pub(crate) const POINT_WORDS: usize = 4;
+
+pub(crate) const W1: usize = 0;
+pub(crate) const W2: usize = W1 + POINT_WORDS;
+pub(crate) const W3: usize = W2 + POINT_WORDS;
+pub(crate) const LOOKUP_READ_COUNTS: usize = W3 + POINT_WORDS;
+pub(crate) const LOOKUP_READ_TAGS: usize = LOOKUP_READ_COUNTS + POINT_WORDS;
+pub(crate) const W4: usize = LOOKUP_READ_TAGS + POINT_WORDS;
+pub(crate) const LOOKUP_INVERSES: usize = W4 + POINT_WORDS;
+pub(crate) const Z_PERM: usize = LOOKUP_INVERSES + POINT_WORDS;
+const SUMCHECK_UNIVARIATES: usize = Z_PERM + POINT_WORDS;
+const SUMCHECK_EVALUATIONS: usize =
+    SUMCHECK_UNIVARIATES + CONST_PROOF_SIZE_LOG_N * BATCHED_RELATION_PARTIAL_LENGTH;
+pub(crate) const GEMINI_FOLD_COMMS: usize = SUMCHECK_EVALUATIONS + NUMBER_OF_ENTITIES;
+const GEMINI_A_EVALUATIONS: usize = GEMINI_FOLD_COMMS + (CONST_PROOF_SIZE_LOG_N - 1) * POINT_WORDS;
+pub(crate) const SHPLONK_Q: usize = GEMINI_A_EVALUATIONS + CONST_PROOF_SIZE_LOG_N;
+pub(crate) const KZG_QUOTIENT: usize = SHPLONK_Q + POINT_WORDS;
+
+/// Proof size in 32-byte words.
+pub const PROOF_SIZE: usize = KZG_QUOTIENT + POINT_WORDS;
+
+/// A keccak-transcript UltraHonk proof, read in place.
+#[derive(Clone, Copy)]
+pub struct Proof<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Proof<'a> {
+    /// `None` unless `bytes` holds exactly `PROOF_SIZE` words.
+    pub fn new(bytes: &'a [u8]) -> Option<Self> {
+        (bytes.len() == PROOF_SIZE * 32).then_some(Self { bytes })
+    }
+
+    /// Raw word, as the transcript hashes commitment limbs.
+    pub fn word(&self, index: usize) -> &'a [u8] {
+        &self.bytes[index * 32..(index + 1) * 32]
+    }
+
+    /// Raw words of the commitment at `offset`.
+    pub fn point_words(&self, offset: usize) -> &'a [u8] {
+        &self.bytes[offset * 32..(offset + POINT_WORDS) * 32]
+    }
+
+    pub fn point(&self, offset: usize) -> G1Point {
+        let limb = |i: usize| U256::from_be_slice(self.word(offset + i));
+        G1Point {
+            x: limb(0) | (limb(1) << 136),
+            y: limb(2) | (limb(3) << 136),
+        }
+    }
+
+    pub fn sumcheck_univariate(&self, round: usize) -> [Fr; BATCHED_RELATION_PARTIAL_LENGTH] {
+        let offset = SUMCHECK_UNIVARIATES + round * BATCHED_RELATION_PARTIAL_LENGTH;
+        core::array::from_fn(|i| self.field(offset + i))
+    }
+
+    pub fn sumcheck_evaluations(&self) -> [Fr; NUMBER_OF_ENTITIES] {
+        core::array::from_fn(|i| self.field(SUMCHECK_EVALUATIONS + i))
+    }
+
+    /// Commitment to the fold polynomial `A_{i + 1}`.
+    pub fn gemini_fold_comm(&self, i: usize) -> G1Point {
+        self.point(GEMINI_FOLD_COMMS + i * POINT_WORDS)
+    }
+
+    /// `A_i(-r^{2^i})` for every fold, padding included.
+    pub fn gemini_a_evaluations(&self) -> [Fr; CONST_PROOF_SIZE_LOG_N] {
+        core::array::from_fn(|i| self.field(GEMINI_A_EVALUATIONS + i))
+    }
+
+    fn field(&self, index: usize) -> Fr {
+        Fr::from_be_slice(self.word(index))
+    }
+}
@@ -0,0 +1,365 @@
+//! UltraHonk relations, evaluated once on the sumcheck's claimed evaluations.
+
+use stylus_sdk::alloy_primitives::uint;
+
+use crate::{
+    field::Fr, transcript::RelationParameters, NUMBER_OF_ALPHAS, NUMBER_OF_ENTITIES,
+    NUMBER_OF_SUBRELATIONS,
+};
+
+/* entity order of the sumcheck evaluations: precomputed, witness, then shifted witness */
+const Q_M: usize = 0;
+const Q_C: usize = 1;
+const Q_L: usize = 2;
+const Q_R: usize = 3;
+const Q_O: usize = 4;
+const Q_4: usize = 5;
+const Q_LOOKUP: usize = 6;
+const Q_ARITH: usize = 7;
+const Q_RANGE: usize = 8;
+const Q_ELLIPTIC: usize = 9;
+const Q_AUX: usize = 10;
+const Q_POSEIDON2_EXTERNAL: usize = 11;
+const Q_POSEIDON2_INTERNAL: usize = 12;
+const SIGMA_1: usize = 13;
+const SIGMA_2: usize = 14;
+const SIGMA_3: usize = 15;
+const SIGMA_4: usize = 16;
+const ID_1: usize = 17;
+const ID_2: usize = 18;
+const ID_3: usize = 19;
+const ID_4: usize = 20;
+const TABLE_1: usize = 21;
+const TABLE_2: usize = 22;
+const TABLE_3: usize = 23;
+const TABLE_4: usize = 24;
+const LAGRANGE_FIRST: usize = 25;
+const LAGRANGE_LAST: usize = 26;
+const W_L: usize = 27;
+const W_R: usize = 28;
+const W_O: usize = 29;
+const W_4: usize = 30;
+const Z_PERM: usize = 31;
+const LOOKUP_INVERSES: usize = 32;
+const LOOKUP_READ_COUNTS: usize = 33;
+const LOOKUP_READ_TAGS: usize = 34;
+const W_L_SHIFT: usize = 35;
+const W_R_SHIFT: usize = 36;
+const W_O_SHIFT: usize = 37;
+const W_4_SHIFT: usize = 38;
+const Z_PERM_SHIFT: usize = 39;
+
+/* grumpkin's b is -17 */
+const GRUMPKIN_CURVE_B_PARAMETER_NEGATED: u64 = 17;
+
+/* limbs of the non-native field gates */
+const LIMB_SIZE: Fr = Fr::from_raw(uint!(0x100000000000000000_U256));
+const SUBLIMB_SHIFT: Fr = Fr::from_raw(uint!(0x4000_U256));
+
+/* Poseidon2 t = 4 internal matrix diagonal minus one, as in openzeppelin-poseidon */
+const INTERNAL_MATRIX_DIAGONAL: [Fr; 4] = [
+    Fr::from_raw(uint!(0x10dc6e9c006ea38b04b1e03b4bd9490c0d03f98929ca1d7fb56821fd19d3b6e7_U256)),
+    Fr::from_raw(uint!(0x0c28145b6a44df3e0149b3d0a30b3bb599df9756d4dd9b84a86b38cfb45a740b_U256)),
+    Fr::from_raw(uint!(0x00544b8338791518b2c7645a50392798b21f75bb60e3596170067d00141cac15_U256)),
+    Fr::from_raw(uint!(0x222c01175718386f2e2e82eb122789e352e105a3b8fa852613bc534433ee428b_U256)),
+];
+
+type Evaluations = [Fr; NUMBER_OF_SUBRELATIONS];
+
+/// Every subrelation at the sumcheck point, scaled by its pow factor and batched with `alphas`.
+pub fn accumulate(
+    p: &[Fr; NUMBER_OF_ENTITIES],
+    rp: &RelationParameters,
+    alphas: &[Fr; NUMBER_OF_ALPHAS],
+    pow_partial_evaluation: Fr,
+) -> Fr {
+    let mut evals = [Fr::ZERO; NUMBER_OF_SUBRELATIONS];
+    arithmetic(p, &mut evals, pow_partial_evaluation);
+    permutation(p, rp, &mut evals, pow_partial_evaluation);
+    log_derivative_lookup(p, rp, &mut evals, pow_partial_evaluation);
+    delta_range(p, &mut evals, pow_partial_evaluation);
+    elliptic(p, &mut evals, pow_partial_evaluation);
+    auxiliary(p, rp, &mut evals, pow_partial_evaluation);
+    poseidon_external(p, &mut evals, pow_partial_evaluation);
+    poseidon_internal(p, &mut evals, pow_partial_evaluation);
+
+    let mut accumulator = evals[0];
+    for (eval, alpha) in evals[1..].iter().zip(alphas) {
+        accumulator = accumulator + *eval * *alpha;
+    }
+    accumulator
+}
+
+fn small(value: u64) -> Fr {
+    Fr::from_u64(value)
+}
+
+/* subrelations 0 and 1 */
+fn arithmetic(p: &[Fr; NUMBER_OF_ENTITIES], evals: &mut Evaluations, domain_sep: Fr) {
+    let q_arith = p[Q_ARITH];
+    {
+        let neg_half = -small(2).invert();
+        let mut accum = (q_arith - small(3)) * (p[Q_M] * p[W_R] * p[W_L]) * neg_half;
+        accum = accum
+            + p[Q_L] * p[W_L]
+            + p[Q_R] * p[W_R]
+            + p[Q_O] * p[W_O]
+            + p[Q_4] * p[W_4]
+            + p[Q_C];
+        accum = accum + (q_arith - Fr::ONE) * p[W_4_SHIFT];
+        evals[0] = accum * q_arith * domain_sep;
+    }
+    {
+        let mut accum = p[W_L] + p[W_4] - p[W_L_SHIFT] + p[Q_M];
+        accum = accum * (q_arith - small(2)) * (q_arith - Fr::ONE) * q_arith;
+        evals[1] = accum * domain_sep;
+    }
+}
+
+/* subrelations 2 and 3 */
+fn permutation(
+    p: &[Fr; NUMBER_OF_ENTITIES],
+    rp: &RelationParameters,
+    evals: &mut Evaluations,
+    domain_sep: Fr,
+) {
+    let term = |wire: usize, id: usize| p[wire] + p[id] * rp.beta + rp.gamma;
+    let numerator = term(W_L, ID_1) * term(W_R, ID_2) * term(W_O, ID_3) * term(W_4, ID_4);
+    let denominator =
+        term(W_L, SIGMA_1) * term(W_R, SIGMA_2) * term(W_O, SIGMA_3) * term(W_4, SIGMA_4);
+
+    let acc = (p[Z_PERM] + p[LAGRANGE_FIRST]) * numerator
+        - (p[Z_PERM_SHIFT] + p[LAGRANGE_LAST] * rp.public_inputs_delta) * denominator;
+    evals[2] = acc * domain_sep;
+    evals[3] = p[LAGRANGE_LAST] * p[Z_PERM_SHIFT] * domain_sep;
+}
+
+/* subrelations 4 and 5, the second is linearly dependent and not scaled */
+fn log_derivative_lookup(
+    p: &[Fr; NUMBER_OF_ENTITIES],
+    rp: &RelationParameters,
+    evals: &mut Evaluations,
+    domain_sep: Fr,
+) {
+    let write_term = p[TABLE_1]
+        + rp.gamma
+        + p[TABLE_2] * rp.eta
+        + p[TABLE_3] * rp.eta_two
+        + p[TABLE_4] * rp.eta_three;
+
+    let derived_entry_1 = p[W_L] + rp.gamma + p[Q_R] * p[W_L_SHIFT];
+    let derived_entry_2 = p[W_R] + p[Q_M] * p[W_R_SHIFT];
+    let derived_entry_3 = p[W_O] + p[Q_C] * p[W_O_SHIFT];
+    let read_term = derived_entry_1
+        + derived_entry_2 * rp.eta
+        + derived_entry_3 * rp.eta_two
+        + p[Q_O] * rp.eta_three;
+
+    let read_inverse = p[LOOKUP_INVERSES] * write_term;
+    let write_inverse = p[LOOKUP_INVERSES] * read_term;
+
+    let inverse_exists_xor =
+        p[LOOKUP_READ_TAGS] + p[Q_LOOKUP] - p[LOOKUP_READ_TAGS] * p[Q_LOOKUP];
+
+    evals[4] = (read_term * write_term * p[LOOKUP_INVERSES] - inverse_exists_xor) * domain_sep;
+    evals[5] = p[Q_LOOKUP] * read_inverse - p[LOOKUP_READ_COUNTS] * write_inverse;
+}
+
+/* subrelations 6 to 9 */
+fn delta_range(p: &[Fr; NUMBER_OF_ENTITIES], evals: &mut Evaluations, domain_sep: Fr) {
+    let deltas = [
+        p[W_R] - p[W_L],
+        p[W_O] - p[W_R],
+        p[W_4] - p[W_O],
+        p[W_L_SHIFT] - p[W_4],
+    ];
+    for (i, delta) in deltas.into_iter().enumerate() {
+        let acc = delta * (delta - Fr::ONE) * (delta - small(2)) * (delta - small(3));
+        evals[6 + i] = acc * p[Q_RANGE] * domain_sep;
+    }
+}
+
+/* subrelations 10 and 11, point addition or doubling on grumpkin */
+fn elliptic(p: &[Fr; NUMBER_OF_ENTITIES], evals: &mut Evaluations, domain_sep: Fr) {
+    let x_1 = p[W_R];
+    let y_1 = p[W_O];
+    let x_2 = p[W_L_SHIFT];
+    let y_2 = p[W_4_SHIFT];
+    let y_3 = p[W_O_SHIFT];
+    let x_3 = p[W_R_SHIFT];
+
+    let q_sign = p[Q_L];
+    let q_is_double = p[Q_M];
+    let q_elliptic = p[Q_ELLIPTIC];
+
+    let x_diff = x_2 - x_1;
+    let y1_sqr = y_1 * y_1;
+
+    /* addition: (x3 + x2 + x1)(x2 - x1)^2 - y2^2 - y1^2 + 2 y1 y2 q_sign = 0 */
+    {
+        let y2_sqr = y_2 * y_2;
+        let y1y2 = y_1 * y_2 * q_sign;
+        let x_add_identity = (x_3 + x_2 + x_1) * x_diff * x_diff - y2_sqr - y1_sqr + y1y2 + y1y2;
+        evals[10] = x_add_identity * domain_sep * q_elliptic * (Fr::ONE - q_is_double);
+    }
+    /* addition: (y1 + y3)(x2 - x1) + (x3 - x1)(q_sign y2 - y1) = 0 */
+    {
+        let y_add_identity = (y_1 + y_3) * x_diff + (x_3 - x_1) * (y_2 * q_sign - y_1);
+        evals[11] = y_add_identity * domain_sep * q_elliptic * (Fr::ONE - q_is_double);
+    }
+    /* doubling: (x3 + 2 x1) 4 y1^2 - 9 x1^4 = 0, with x1^3 = y1^2 - b */
+    {
+        let x_pow_4 = (y1_sqr + small(GRUMPKIN_CURVE_B_PARAMETER_NEGATED)) * x_1;
+        let y1_sqr_mul_4 = y1_sqr + y1_sqr + y1_sqr + y1_sqr;
+        let x_double_identity = (x_3 + x_1 + x_1) * y1_sqr_mul_4 - x_pow_4 * small(9);
+        evals[10] = evals[10] + x_double_identity * domain_sep * q_elliptic * q_is_double;
+    }
+    /* doubling: 3 x1^2 (x1 - x3) - 2 y1 (y1 + y3) = 0 */
+    {
+        let x1_sqr_mul_3 = (x_1 + x_1 + x_1) * x_1;
+        let y_double_identity = x1_sqr_mul_3 * (x_1 - x_3) - (y_1 + y_1) * (y_1 + y_3);
+        evals[11] = evals[11] + y_double_identity * domain_sep * q_elliptic * q_is_double;
+    }
+}
+
+/* subrelations 12 to 17: non-native field arithmetic, limb accumulation, ROM and RAM */
+fn auxiliary(
+    p: &[Fr; NUMBER_OF_ENTITIES],
+    rp: &RelationParameters,
+    evals: &mut Evaluations,
+    domain_sep: Fr,
+) {
+    let minus_one = -Fr::ONE;
+    let q_aux_by_scaling = p[Q_AUX] * domain_sep;
+
+    /* non-native field gates */
+    let mut limb_subproduct = p[W_L] * p[W_R_SHIFT] + p[W_L_SHIFT] * p[W_R];
+    let mut non_native_field_gate_2 = p[W_L] * p[W_4] + p[W_R] * p[W_O] - p[W_O_SHIFT];
+    non_native_field_gate_2 = non_native_field_gate_2 * LIMB_SIZE;
+    non_native_field_gate_2 = non_native_field_gate_2 - p[W_4_SHIFT];
+    non_native_field_gate_2 = non_native_field_gate_2 + limb_subproduct;
+    non_native_field_gate_2 = non_native_field_gate_2 * p[Q_4];
+
+    limb_subproduct = limb_subproduct * LIMB_SIZE;
+    limb_subproduct = limb_subproduct + p[W_L_SHIFT] * p[W_R_SHIFT];
+    let non_native_field_gate_1 = (limb_subproduct - (p[W_O] + p[W_4])) * p[Q_O];
+    let non_native_field_gate_3 =
+        (limb_subproduct + p[W_4] - (p[W_O_SHIFT] + p[W_4_SHIFT])) * p[Q_M];
+
+    let non_native_field_identity =
+        (non_native_field_gate_1 + non_native_field_gate_2 + non_native_field_gate_3) * p[Q_R];
+
+    /* ((((w2' * 2^14 + w1') * 2^14 + w3) * 2^14 + w2) * 2^14 + w1 - w4) * q_4 */
+    let mut limb_accumulator_1 = p[W_R_SHIFT] * SUBLIMB_SHIFT;
+    limb_accumulator_1 = (limb_accumulator_1 + p[W_L_SHIFT]) * SUBLIMB_SHIFT;
+    limb_accumulator_1 = (limb_accumulator_1 + p[W_O]) * SUBLIMB_SHIFT;
+    limb_accumulator_1 = (limb_accumulator_1 + p[W_R]) * SUBLIMB_SHIFT;
+    limb_accumulator_1 = (limb_accumulator_1 + p[W_L] - p[W_4]) * p[Q_4];
+
+    /* ((((w3' * 2^14 + w2') * 2^14 + w1') * 2^14 + w4) * 2^14 + w3 - w4') * q_m */
+    let mut limb_accumulator_2 = p[W_O_SHIFT] * SUBLIMB_SHIFT;
+    limb_accumulator_2 = (limb_accumulator_2 + p[W_R_SHIFT]) * SUBLIMB_SHIFT;
+    limb_accumulator_2 = (limb_accumulator_2 + p[W_L_SHIFT]) * SUBLIMB_SHIFT;
+    limb_accumulator_2 = (limb_accumulator_2 + p[W_4]) * SUBLIMB_SHIFT;
+    limb_accumulator_2 = (limb_accumulator_2 + p[W_O] - p[W_4_SHIFT]) * p[Q_M];
+
+    let limb_accumulator_identity = (limb_accumulator_1 + limb_accumulator_2) * p[Q_O];
+
+    /* memory record: w3 eta_three + w2 eta_two + w1 eta + q_c, checked against w4 */
+    let partial_record_check =
+        p[W_O] * rp.eta_three + p[W_R] * rp.eta_two + p[W_L] * rp.eta + p[Q_C];
+    let memory_record_check = partial_record_check - p[W_4];
+
+    /* ROM consistency */
+    let index_delta = p[W_L_SHIFT] - p[W_L];
+    let record_delta = p[W_4_SHIFT] - p[W_4];
+    let index_is_monotonically_increasing = index_delta * index_delta - index_delta;
+    let adjacent_values_match_if_adjacent_indices_match =
+        (index_delta * minus_one + Fr::ONE) * record_delta;
+
+    let q_l_by_q_r = p[Q_L] * p[Q_R];
+    evals[13] = adjacent_values_match_if_adjacent_indices_match * q_l_by_q_r * q_aux_by_scaling;
+    evals[14] = index_is_monotonically_increasing * q_l_by_q_r * q_aux_by_scaling;
+    let rom_consistency_check_identity = memory_record_check * q_l_by_q_r;
+
+    /* RAM consistency */
+    let access_type = p[W_4] - partial_record_check;
+    let access_check = access_type * access_type - access_type;
+
+    let next_gate_access_type = p[W_4_SHIFT]
+        - (p[W_O_SHIFT] * rp.eta_three + p[W_R_SHIFT] * rp.eta_two + p[W_L_SHIFT] * rp.eta);
+    let value_delta = p[W_O_SHIFT] - p[W_O];
+    let adjacent_values_match_if_adjacent_indices_match_and_next_access_is_a_read_operation =
+        (index_delta * minus_one + Fr::ONE)
+            * value_delta
+            * (next_gate_access_type * minus_one + Fr::ONE);
+    let next_gate_access_type_is_boolean =
+        next_gate_access_type * next_gate_access_type - next_gate_access_type;
+
+    let q_arith = p[Q_ARITH];
+    evals[15] = adjacent_values_match_if_adjacent_indices_match_and_next_access_is_a_read_operation
+        * q_arith
+        * q_aux_by_scaling;
+    evals[16] = index_is_monotonically_increasing * q_arith * q_aux_by_scaling;
+    evals[17] = next_gate_access_type_is_boolean * q_arith * q_aux_by_scaling;
+    let ram_consistency_check_identity = access_check * q_arith;
+
+    /* RAM timestamps */
+    let timestamp_delta = p[W_R_SHIFT] - p[W_R];
+    let ram_timestamp_check_identity =
+        (index_delta * minus_one + Fr::ONE) * timestamp_delta - p[W_O];
+
+    let memory_identity = rom_consistency_check_identity
+        + ram_timestamp_check_identity * (p[Q_4] * p[Q_L])
+        + memory_record_check * (p[Q_M] * p[Q_L])
+        + ram_consistency_check_identity;
+
+    let auxiliary_identity =
+        memory_identity + non_native_field_identity + limb_accumulator_identity;
+    evals[12] = auxiliary_identity * q_aux_by_scaling;
+}
+
+/* subrelations 18 to 21, a full Poseidon2 round */
+fn poseidon_external(p: &[Fr; NUMBER_OF_ENTITIES], evals: &mut Evaluations, domain_sep: Fr) {
+    let sbox = |x: Fr| {
+        let x2 = x * x;
+        x2 * x2 * x
+    };
+    let u1 = sbox(p[W_L] + p[Q_L]);
+    let u2 = sbox(p[W_R] + p[Q_R]);
+    let u3 = sbox(p[W_O] + p[Q_O]);
+    let u4 = sbox(p[W_4] + p[Q_4]);
+
+    /* external matrix M_E, 14 additions */
+    let t0 = u1 + u2;
+    let t1 = u3 + u4;
+    let t2 = u2 + u2 + t1;
+    let t3 = u4 + u4 + t0;
+    let mut v4 = t1 + t1;
+    v4 = v4 + v4 + t3;
+    let mut v2 = t0 + t0;
+    v2 = v2 + v2 + t2;
+    let v1 = t3 + v2;
+    let v3 = t2 + v4;
+
+    let q_pos_by_scaling = p[Q_POSEIDON2_EXTERNAL] * domain_sep;
+    evals[18] = evals[18] + q_pos_by_scaling * (v1 - p[W_L_SHIFT]);
+    evals[19] = evals[19] + q_pos_by_scaling * (v2 - p[W_R_SHIFT]);
+    evals[20] = evals[20] + q_pos_by_scaling * (v3 - p[W_O_SHIFT]);
+    evals[21] = evals[21] + q_pos_by_scaling * (v4 - p[W_4_SHIFT]);
+}
+
+/* subrelations 22 to 25, a partial Poseidon2 round */
+fn poseidon_internal(p: &[Fr; NUMBER_OF_ENTITIES], evals: &mut Evaluations, domain_sep: Fr) {
+    let s1 = p[W_L] + p[Q_L];
+    let s1_sqr = s1 * s1;
+    let u = [s1_sqr * s1_sqr * s1, p[W_R], p[W_O], p[W_4]];
+    let u_sum = u[0] + u[1] + u[2] + u[3];
+
+    let q_pos_by_scaling = p[Q_POSEIDON2_INTERNAL] * domain_sep;
+    let shifted = [W_L_SHIFT, W_R_SHIFT, W_O_SHIFT, W_4_SHIFT];
+    for i in 0..4 {
+        let v = u[i] * INTERNAL_MATRIX_DIAGONAL[i] + u_sum;
+        evals[22 + i] = evals[22 + i] + q_pos_by_scaling * (v - p[shifted[i]]);
+    }
+}
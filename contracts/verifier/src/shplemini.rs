@@ -0,0 +1,143 @@
+use alloc::vec::Vec;
+
+use crate::{
+    field::Fr,
+    key::{G1Point, VerificationKey},
+    proof::{
+        Proof, KZG_QUOTIENT, LOOKUP_INVERSES, LOOKUP_READ_COUNTS, LOOKUP_READ_TAGS, SHPLONK_Q,
+        W1, W2, W3, W4, Z_PERM,
+    },
+    transcript::Transcript,
+    CONST_PROOF_SIZE_LOG_N, NUMBER_OF_ENTITIES, NUMBER_UNSHIFTED,
+};
+
+/// Terms of the multi-scalar multiplication whose result is paired against the KZG quotient.
+pub struct BatchOpening {
+    pub terms: Vec<(G1Point, Fr)>,
+    pub kzg_quotient: G1Point,
+}
+
+/// Reduces the sumcheck evaluation claims to one KZG opening, as Shplemini does: Gemini folds
+/// the batched polynomial, Shplonk batches the fold openings at `±r^{2^l}` into one at `z`.
+pub fn batch_opening(
+    proof: &Proof,
+    vk: &VerificationKey,
+    tp: &Transcript,
+    log_n: usize,
+) -> BatchOpening {
+    let mut terms: Vec<(G1Point, Fr)> =
+        Vec::with_capacity(NUMBER_OF_ENTITIES + CONST_PROOF_SIZE_LOG_N + 2);
+
+    /* r, r^2, r^4, ... */
+    let mut powers_of_evaluation_challenge = [Fr::ZERO; CONST_PROOF_SIZE_LOG_N];
+    powers_of_evaluation_challenge[0] = tp.gemini_r;
+    for i in 1..CONST_PROOF_SIZE_LOG_N {
+        powers_of_evaluation_challenge[i] = powers_of_evaluation_challenge[i - 1].square();
+    }
+
+    let mut pos_inverted_denominator = (tp.shplonk_z - powers_of_evaluation_challenge[0]).invert();
+    let mut neg_inverted_denominator = (tp.shplonk_z + powers_of_evaluation_challenge[0]).invert();
+    let unshifted_scalar = pos_inverted_denominator + tp.shplonk_nu * neg_inverted_denominator;
+    let shifted_scalar = tp.gemini_r.invert()
+        * (pos_inverted_denominator - tp.shplonk_nu * neg_inverted_denominator);
+
+    terms.push((proof.point(SHPLONK_Q), Fr::ONE));
+
+    /* A_0 = F + G / X, batched with powers of rho in entity order */
+    let witness = [
+        W1,
+        W2,
+        W3,
+        W4,
+        Z_PERM,
+        LOOKUP_INVERSES,
+        LOOKUP_READ_COUNTS,
+        LOOKUP_READ_TAGS,
+    ];
+    let to_be_shifted = [W1, W2, W3, W4, Z_PERM];
+    let commitments = vk.commitments.iter().copied().chain(
+        witness
+            .into_iter()
+            .chain(to_be_shifted)
+            .map(|offset| proof.point(offset)),
+    );
+
+    let evaluations = proof.sumcheck_evaluations();
+    let mut batching_challenge = Fr::ONE;
+    let mut batched_evaluation = Fr::ZERO;
+    for (i, commitment) in commitments.enumerate() {
+        let scalar = if i < NUMBER_UNSHIFTED {
+            unshifted_scalar
+        } else {
+            shifted_scalar
+        };
+        terms.push((commitment, -scalar * batching_challenge));
+        batched_evaluation = batched_evaluation + evaluations[i] * batching_challenge;
+        batching_challenge = batching_challenge * tp.rho;
+    }
+
+    /* A_l(r^{2^l}) from the claimed A_l(-r^{2^l}) */
+    let gemini_a_evaluations = proof.gemini_a_evaluations();
+    let fold_pos_evaluations = fold_pos_evaluations(
+        &tp.sumcheck_u_challenges,
+        batched_evaluation,
+        &gemini_a_evaluations,
+        &powers_of_evaluation_challenge,
+        log_n,
+    );
+
+    let mut constant_term_accumulator = fold_pos_evaluations[0] * pos_inverted_denominator
+        + gemini_a_evaluations[0] * tp.shplonk_nu * neg_inverted_denominator;
+    batching_challenge = tp.shplonk_nu.square();
+
+    /* padding rounds carry no fold, their commitments would be scaled by zero */
+    for i in 0..log_n - 1 {
+        pos_inverted_denominator =
+            (tp.shplonk_z - powers_of_evaluation_challenge[i + 1]).invert();
+        neg_inverted_denominator =
+            (tp.shplonk_z + powers_of_evaluation_challenge[i + 1]).invert();
+
+        let scaling_factor_pos = batching_challenge * pos_inverted_denominator;
+        let scaling_factor_neg = batching_challenge * tp.shplonk_nu * neg_inverted_denominator;
+        terms.push((
+            proof.gemini_fold_comm(i),
+            -scaling_factor_neg - scaling_factor_pos,
+        ));
+
+        constant_term_accumulator = constant_term_accumulator
+            + scaling_factor_neg * gemini_a_evaluations[i + 1]
+            + scaling_factor_pos * fold_pos_evaluations[i + 1];
+        batching_challenge = batching_challenge * tp.shplonk_nu * tp.shplonk_nu;
+    }
+
+    let kzg_quotient = proof.point(KZG_QUOTIENT);
+    terms.push((G1Point::GENERATOR, constant_term_accumulator));
+    terms.push((kzg_quotient, tp.shplonk_z));
+
+    BatchOpening {
+        terms,
+        kzg_quotient,
+    }
+}
+
+/// `A_l(r^{2^l})` for every real round, walking down from the batched multilinear evaluation.
+/// `A_{l + 1}` folds `A_l` with the sumcheck challenge `u_l`.
+pub fn fold_pos_evaluations(
+    sumcheck_u_challenges: &[Fr; CONST_PROOF_SIZE_LOG_N],
+    batched_evaluation: Fr,
+    gemini_a_evaluations: &[Fr; CONST_PROOF_SIZE_LOG_N],
+    powers_of_evaluation_challenge: &[Fr; CONST_PROOF_SIZE_LOG_N],
+    log_n: usize,
+) -> [Fr; CONST_PROOF_SIZE_LOG_N] {
+    let mut fold_pos_evaluations = [Fr::ZERO; CONST_PROOF_SIZE_LOG_N];
+    let mut accumulator = batched_evaluation;
+    for i in (0..log_n).rev() {
+        let challenge_power = powers_of_evaluation_challenge[i];
+        let u = sumcheck_u_challenges[i];
+        let numerator = challenge_power * accumulator * Fr::from_u64(2)
+            - gemini_a_evaluations[i] * (challenge_power * (Fr::ONE - u) - u);
+        accumulator = numerator * (challenge_power * (Fr::ONE - u) + u).invert();
+        fold_pos_evaluations[i] = accumulator;
+    }
+    fold_pos_evaluations
+}
@@ -0,0 +1,59 @@
+use stylus_sdk::alloy_primitives::uint;
+
+use crate::{
+    field::Fr, proof::Proof, relations, transcript::Transcript, BATCHED_RELATION_PARTIAL_LENGTH,
+};
+
+/* prod_{j != i} (i - j) over the evaluation domain 0..8 */
+const BARYCENTRIC_LAGRANGE_DENOMINATORS: [Fr; BATCHED_RELATION_PARTIAL_LENGTH] = [
+    Fr::from_raw(uint!(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593efffec51_U256)),
+    Fr::from_raw(uint!(0x00000000000000000000000000000000000000000000000000000000000002d0_U256)),
+    Fr::from_raw(uint!(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593efffff11_U256)),
+    Fr::from_raw(uint!(0x0000000000000000000000000000000000000000000000000000000000000090_U256)),
+    Fr::from_raw(uint!(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593efffff71_U256)),
+    Fr::from_raw(uint!(0x00000000000000000000000000000000000000000000000000000000000000f0_U256)),
+    Fr::from_raw(uint!(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593effffd31_U256)),
+    Fr::from_raw(uint!(0x00000000000000000000000000000000000000000000000000000000000013b0_U256)),
+];
+
+/// Runs the `log_n` real sumcheck rounds, then checks the relations at the final point.
+pub fn verify(proof: &Proof, tp: &Transcript, log_n: usize) -> bool {
+    let mut round_target = Fr::ZERO;
+    let mut pow_partial_evaluation = Fr::ONE;
+
+    for round in 0..log_n {
+        let univariate = proof.sumcheck_univariate(round);
+        if univariate[0] + univariate[1] != round_target {
+            return false;
+        }
+        let challenge = tp.sumcheck_u_challenges[round];
+        round_target = next_target_sum(&univariate, challenge);
+        pow_partial_evaluation = pow_partial_evaluation
+            * (Fr::ONE + challenge * (tp.gate_challenges[round] - Fr::ONE));
+    }
+
+    let grand_honk_relation_sum = relations::accumulate(
+        &proof.sumcheck_evaluations(),
+        &tp.relation_parameters,
+        &tp.alphas,
+        pow_partial_evaluation,
+    );
+    grand_honk_relation_sum == round_target
+}
+
+/// Evaluates the round univariate, given on `0..8`, at `challenge`.
+pub fn next_target_sum(univariate: &[Fr; BATCHED_RELATION_PARTIAL_LENGTH], challenge: Fr) -> Fr {
+    /* B(x) = prod (x - i) */
+    let mut numerator = Fr::ONE;
+    for i in 0..BATCHED_RELATION_PARTIAL_LENGTH {
+        numerator = numerator * (challenge - Fr::from_u64(i as u64));
+    }
+
+    let mut target_sum = Fr::ZERO;
+    for (i, value) in univariate.iter().enumerate() {
+        let denominator =
+            BARYCENTRIC_LAGRANGE_DENOMINATORS[i] * (challenge - Fr::from_u64(i as u64));
+        target_sum = target_sum + *value * denominator.invert();
+    }
+    target_sum * numerator
+}
@@ -0,0 +1,207 @@
+use alloc::vec::Vec;
+use stylus_sdk::alloy_primitives::{keccak256, FixedBytes, U256};
+
+use crate::{
+    field::Fr,
+    key::VerificationKey,
+    proof::{
+        Proof, GEMINI_FOLD_COMMS, LOOKUP_INVERSES, LOOKUP_READ_COUNTS, LOOKUP_READ_TAGS,
+        POINT_WORDS, SHPLONK_Q, W1, W2, W3, W4, Z_PERM,
+    },
+    CONST_PROOF_SIZE_LOG_N, NUMBER_OF_ALPHAS, NUMBER_OF_ENTITIES,
+};
+
+/// Challenges the relations are evaluated with.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RelationParameters {
+    pub eta: Fr,
+    pub eta_two: Fr,
+    pub eta_three: Fr,
+    pub beta: Fr,
+    pub gamma: Fr,
+    pub public_inputs_delta: Fr,
+}
+
+/// Every Fiat-Shamir challenge of one proof, in the order bb's keccak transcript draws them.
+#[derive(Clone, Debug)]
+pub struct Transcript {
+    pub relation_parameters: RelationParameters,
+    pub alphas: [Fr; NUMBER_OF_ALPHAS],
+    pub gate_challenges: [Fr; CONST_PROOF_SIZE_LOG_N],
+    pub sumcheck_u_challenges: [Fr; CONST_PROOF_SIZE_LOG_N],
+    pub rho: Fr,
+    pub gemini_r: Fr,
+    pub shplonk_nu: Fr,
+    pub shplonk_z: Fr,
+}
+
+impl Transcript {
+    pub fn new(proof: &Proof, public_inputs: &[FixedBytes<32>], vk: &VerificationKey) -> Self {
+        /* eta, eta two and eta three: the circuit, its public inputs and the first three wires */
+        let mut round = Round {
+            buf: Vec::with_capacity((3 + public_inputs.len() + 3 * POINT_WORDS) * 32),
+        };
+        round.push_u256(U256::from(vk.circuit_size));
+        round.push_u256(U256::from(vk.num_public_inputs));
+        round.push_u256(U256::from(vk.pub_inputs_offset));
+        for input in public_inputs {
+            round.push_word(input.as_slice());
+        }
+        for offset in [W1, W2, W3] {
+            round.push_word(proof.point_words(offset));
+        }
+        let mut previous = round.challenge();
+        let (eta, eta_two) = split_challenge(previous);
+        previous = hash(previous);
+        let (eta_three, _) = split_challenge(previous);
+
+        /* beta and gamma: lookup read counts and tags, then w4 */
+        let mut round = Round::new(previous);
+        for offset in [LOOKUP_READ_COUNTS, LOOKUP_READ_TAGS, W4] {
+            round.push_word(proof.point_words(offset));
+        }
+        previous = round.challenge();
+        let (beta, gamma) = split_challenge(previous);
+
+        /* alphas: lookup inverses and z_perm, then two per hash of the previous challenge */
+        let mut round = Round::new(previous);
+        for offset in [LOOKUP_INVERSES, Z_PERM] {
+            round.push_word(proof.point_words(offset));
+        }
+        previous = round.challenge();
+        let mut alphas = [Fr::ZERO; NUMBER_OF_ALPHAS];
+        (alphas[0], alphas[1]) = split_challenge(previous);
+        for i in 1..NUMBER_OF_ALPHAS / 2 {
+            previous = hash(previous);
+            (alphas[2 * i], alphas[2 * i + 1]) = split_challenge(previous);
+        }
+        if NUMBER_OF_ALPHAS % 2 == 1 && NUMBER_OF_ALPHAS > 2 {
+            previous = hash(previous);
+            alphas[NUMBER_OF_ALPHAS - 1] = split_challenge(previous).0;
+        }
+
+        let mut gate_challenges = [Fr::ZERO; CONST_PROOF_SIZE_LOG_N];
+        for challenge in gate_challenges.iter_mut() {
+            previous = hash(previous);
+            *challenge = split_challenge(previous).0;
+        }
+
+        /* one sumcheck challenge per round univariate, padding rounds included */
+        let mut sumcheck_u_challenges = [Fr::ZERO; CONST_PROOF_SIZE_LOG_N];
+        for (i, challenge) in sumcheck_u_challenges.iter_mut().enumerate() {
+            let mut round = Round::new(previous);
+            for value in proof.sumcheck_univariate(i) {
+                round.push_fr(value);
+            }
+            previous = round.challenge();
+            *challenge = split_challenge(previous).0;
+        }
+
+        let mut round = Round::new(previous);
+        for value in proof.sumcheck_evaluations() {
+            round.push_fr(value);
+        }
+        previous = round.challenge();
+        let rho = split_challenge(previous).0;
+
+        let mut round = Round::new(previous);
+        for i in 0..CONST_PROOF_SIZE_LOG_N - 1 {
+            round.push_word(proof.point_words(GEMINI_FOLD_COMMS + i * POINT_WORDS));
+        }
+        previous = round.challenge();
+        let gemini_r = split_challenge(previous).0;
+
+        let mut round = Round::new(previous);
+        for value in proof.gemini_a_evaluations() {
+            round.push_fr(value);
+        }
+        previous = round.challenge();
+        let shplonk_nu = split_challenge(previous).0;
+
+        let mut round = Round::new(previous);
+        round.push_word(proof.point_words(SHPLONK_Q));
+        previous = round.challenge();
+        let shplonk_z = split_challenge(previous).0;
+
+        Self {
+            relation_parameters: RelationParameters {
+                eta,
+                eta_two,
+                eta_three,
+                beta,
+                gamma,
+                public_inputs_delta: Fr::ZERO,
+            },
+            alphas,
+            gate_challenges,
+            sumcheck_u_challenges,
+            rho,
+            gemini_r,
+            shplonk_nu,
+            shplonk_z,
+        }
+    }
+}
+
+/* data hashed for one challenge, starting with the previous one */
+struct Round {
+    buf: Vec<u8>,
+}
+
+impl Round {
+    fn new(previous: Fr) -> Self {
+        let mut buf = Vec::with_capacity((1 + NUMBER_OF_ENTITIES) * 32);
+        buf.extend_from_slice(&previous.to_be_bytes());
+        Self { buf }
+    }
+
+    fn push_word(&mut self, word: &[u8]) {
+        self.buf.extend_from_slice(word);
+    }
+
+    fn push_u256(&mut self, value: U256) {
+        self.buf.extend_from_slice(&value.to_be_bytes::<32>());
+    }
+
+    fn push_fr(&mut self, value: Fr) {
+        self.buf.extend_from_slice(&value.to_be_bytes());
+    }
+
+    fn challenge(self) -> Fr {
+        Fr::from_bytes32(keccak256(&self.buf))
+    }
+}
+
+fn hash(previous: Fr) -> Fr {
+    Fr::from_bytes32(keccak256(previous.to_be_bytes()))
+}
+
+/// Low and high 128 bits of a challenge, bb draws two challenges from one hash this way.
+pub fn split_challenge(challenge: Fr) -> (Fr, Fr) {
+    let value = challenge.into_u256();
+    let lo = value & U256::from(u128::MAX);
+    let hi = value >> 128;
+    (Fr::from_u256(lo), Fr::from_u256(hi))
+}
+
+/// Grand product correction for the public inputs, computed over the field.
+pub fn public_inputs_delta(
+    public_inputs: &[FixedBytes<32>],
+    beta: Fr,
+    gamma: Fr,
+    circuit_size: u64,
+    offset: u64,
+) -> Fr {
+    let mut numerator = Fr::ONE;
+    let mut denominator = Fr::ONE;
+    let mut numerator_acc = gamma + beta * Fr::from_u64(circuit_size + offset);
+    let mut denominator_acc = gamma - beta * Fr::from_u64(offset + 1);
+    for input in public_inputs {
+        let input = Fr::from_bytes32(*input);
+        numerator = numerator * (numerator_acc + input);
+        denominator = denominator * (denominator_acc + input);
+        numerator_acc = numerator_acc + beta;
+        denominator_acc = denominator_acc - beta;
+    }
+    numerator * denominator.invert()
+}
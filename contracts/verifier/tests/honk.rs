@@ -0,0 +1,131 @@
+use std::path::PathBuf;
+
+use alloy_primitives::{keccak256, U256};
+use stylus_honk_verifier::{
+    field::Fr,
+    key::{VerificationKey, VK_SIZE},
+    shplemini::fold_pos_evaluations,
+    sumcheck::next_target_sum,
+    transcript::split_challenge,
+    CONST_PROOF_SIZE_LOG_N, NUMBER_OF_PRECOMPUTED,
+};
+
+#[test]
+fn parses_the_built_vk() {
+    let Some(bytes) = built_vk() else {
+        return;
+    };
+    assert_eq!(bytes.len(), VK_SIZE);
+
+    let vk = VerificationKey::parse(&bytes).expect("vk parses");
+    assert_eq!(vk.circuit_size, 1 << vk.log_circuit_size);
+    assert_eq!(vk.pub_inputs_offset, 1);
+    assert_eq!(vk.commitments.len(), NUMBER_OF_PRECOMPUTED);
+    /* q_m, read right after the header */
+    assert_eq!(vk.commitments[0].x, U256::from_be_slice(&bytes[32..64]));
+    assert_eq!(vk.commitments[0].y, U256::from_be_slice(&bytes[64..96]));
+}
+
+#[test]
+fn rejects_malformed_vk() {
+    let Some(bytes) = built_vk() else {
+        return;
+    };
+    assert!(VerificationKey::parse(&bytes[..VK_SIZE - 1]).is_none());
+
+    /* circuit size that is not 2^log_n */
+    let mut wrong_size = bytes.clone();
+    wrong_size[7] ^= 1;
+    assert!(VerificationKey::parse(&wrong_size).is_none());
+
+    /* more rounds than the proof has room for */
+    let mut too_large = bytes.clone();
+    too_large[..8].copy_from_slice(&0u64.to_be_bytes());
+    too_large[8..16].copy_from_slice(&(CONST_PROOF_SIZE_LOG_N as u64 + 1).to_be_bytes());
+    assert!(VerificationKey::parse(&too_large).is_none());
+}
+
+#[test]
+fn split_challenge_takes_low_then_high_bits() {
+    let challenge = Fr::from_u256((U256::from(7) << 128) | U256::from(5));
+    assert_eq!(split_challenge(challenge), (Fr::from_u64(5), Fr::from_u64(7)));
+}
+
+#[test]
+fn next_target_sum_evaluates_the_round_univariate() {
+    /* degree 7 polynomial, given by its values on 0..8 */
+    let coefficients: Vec<Fr> = (0..8).map(fr).collect();
+    let values: [Fr; 8] = core::array::from_fn(|x| eval(&coefficients, Fr::from_u64(x as u64)));
+
+    let challenge = fr(100);
+    assert_eq!(
+        next_target_sum(&values, challenge),
+        eval(&coefficients, challenge)
+    );
+}
+
+#[test]
+fn fold_pos_evaluations_recover_every_fold() {
+    const LOG_N: usize = 4;
+    let u: [Fr; CONST_PROOF_SIZE_LOG_N] = core::array::from_fn(|i| fr(200 + i as u64));
+    let r = fr(300);
+
+    /* A_0 holds the multilinear's values on the hypercube, A_{l + 1} folds A_l with u_l */
+    let mut folds: Vec<Vec<Fr>> = vec![(0..1 << LOG_N).map(|i| fr(400 + i)).collect()];
+    for l in 0..LOG_N {
+        let next = folds[l]
+            .chunks(2)
+            .map(|pair| (Fr::ONE - u[l]) * pair[0] + u[l] * pair[1])
+            .collect();
+        folds.push(next);
+    }
+    let batched_evaluation = folds[LOG_N][0];
+
+    let mut powers = [Fr::ZERO; CONST_PROOF_SIZE_LOG_N];
+    powers[0] = r;
+    for i in 1..CONST_PROOF_SIZE_LOG_N {
+        powers[i] = powers[i - 1].square();
+    }
+    let mut negative = [Fr::ZERO; CONST_PROOF_SIZE_LOG_N];
+    for l in 0..LOG_N {
+        negative[l] = eval(&folds[l], -powers[l]);
+    }
+
+    let positive = fold_pos_evaluations(&u, batched_evaluation, &negative, &powers, LOG_N);
+    for l in 0..LOG_N {
+        assert_eq!(positive[l], eval(&folds[l], powers[l]), "A_{l}(r^2^{l})");
+    }
+    assert!(positive[LOG_N..].iter().all(|value| value.is_zero()));
+}
+
+fn fr(seed: u64) -> Fr {
+    Fr::from_bytes32(keccak256(seed.to_be_bytes()))
+}
+
+/* coefficients in increasing degree */
+fn eval(coefficients: &[Fr], x: Fr) -> Fr {
+    coefficients
+        .iter()
+        .rev()
+        .fold(Fr::ZERO, |acc, coefficient| acc * x + *coefficient)
+}
+
+/* gitignored, scripts/build_circuits.sh writes it and scripts/check.sh runs that first */
+fn built_vk() -> Option<Vec<u8>> {
+    let path = repo_root().join("circuits/target/vk");
+    let Ok(bytes) = std::fs::read(&path) else {
+        eprintln!("skipped, no {}, run scripts/build_circuits.sh", path.display());
+        return None;
+    };
+    Some(bytes)
+}
+
+fn repo_root() -> PathBuf {
+    /* contracts/verifier -> project root */
+    let crate_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    crate_dir
+        .parent()
+        .and_then(|p| p.parent())
+        .unwrap()
+        .to_path_buf()
+}
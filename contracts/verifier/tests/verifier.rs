@@ -0,0 +1,250 @@
+#![cfg(feature = "e2e")]
+
+use alloy::{primitives::Bytes, providers::Provider};
+use alloy_primitives::{uint, Address, FixedBytes, U256};
+use e2e::{constructor, receipt, Account, Revert};
+use eyre::Result;
+use serde::Deserialize;
+use std::{path::PathBuf, process::Command};
+use stylus_common::{circuits::ULTRA_HONK_PROOF_SIZE, vk::vk_hash_from_file};
+use stylus_mixer_bindings::{
+    honk::{self, IHonkVerifier},
+    mixer::IMixer,
+};
+use stylus_mixer_client::{Deployment, Note};
+
+const DENOMINATION: U256 = uint!(1_000_000_000_000_000_000_U256);
+/* circuits/target/circuits.json, the artifact generateProof.ts proves without CIRCUIT_DEPTH */
+const CIRCUIT_DEPTH: u32 = 15;
+
+/* ======================================================================
+ *                               verify()
+ * ====================================================================== */
+#[e2e::test]
+async fn honk_verify_accepts_generated_proof(alice: Account) -> Result<()> {
+    let verifier = deploy_verifier(&alice).await?;
+    let contract = IHonkVerifier::new(verifier, &alice.wallet);
+
+    let (proof, public_inputs) = prove(&alice, Address::repeat_byte(0x11)).await?;
+    let IHonkVerifier::verifyReturn { _0: verified } = contract
+        .verify(proof.into(), public_inputs)
+        .call()
+        .await?;
+    assert!(verified);
+
+    Ok(())
+}
+
+#[e2e::test]
+async fn honk_verify_rejects_tampered_proof(alice: Account) -> Result<()> {
+    let verifier = deploy_verifier(&alice).await?;
+    let contract = IHonkVerifier::new(verifier, &alice.wallet);
+    let (proof, public_inputs) = prove(&alice, Address::repeat_byte(0x11)).await?;
+
+    /* first sumcheck univariate, right after the 8 witness commitments */
+    let mut tampered = proof.clone();
+    tampered[32 * 32 + 31] ^= 1;
+    let err = contract
+        .verify(tampered.into(), public_inputs.clone())
+        .call()
+        .await
+        .expect_err("should revert");
+    assert!(err.reverted_with(honk::SumcheckFailed {}));
+
+    /* another recipient changes every challenge */
+    let mut other_inputs = public_inputs.clone();
+    other_inputs[2] = Address::repeat_byte(0x22).into_word();
+    let err = contract
+        .verify(proof.clone().into(), other_inputs)
+        .call()
+        .await
+        .expect_err("should revert");
+    assert!(err.reverted_with(honk::SumcheckFailed {}));
+
+    Ok(())
+}
+
+#[e2e::test]
+async fn honk_verify_rejects_malformed_inputs(alice: Account) -> Result<()> {
+    let verifier = deploy_verifier(&alice).await?;
+    let contract = IHonkVerifier::new(verifier, &alice.wallet);
+    let (proof, public_inputs) = prove(&alice, Address::repeat_byte(0x11)).await?;
+
+    let mut truncated = proof.clone();
+    truncated.truncate(proof.len() - 32);
+    let err = contract
+        .verify(truncated.into(), public_inputs.clone())
+        .call()
+        .await
+        .expect_err("should revert");
+    assert!(err.reverted_with(honk::ProofLengthWrong {}));
+
+    let err = contract
+        .verify(proof.into(), public_inputs[..public_inputs.len() - 1].to_vec())
+        .call()
+        .await
+        .expect_err("should revert");
+    assert!(err.reverted_with(honk::PublicInputsLengthWrong {}));
+
+    Ok(())
+}
+
+#[e2e::test]
+async fn honk_rejects_malformed_vk(alice: Account) -> Result<()> {
+    let mut vk = std::fs::read(vk_path())?;
+    vk.pop();
+    let deployed = alice
+        .as_deployer()
+        .with_constructor(constructor!(Bytes::from(vk)))
+        .deploy()
+        .await;
+    assert!(deployed.is_err(), "verifier should not deploy with a truncated vk");
+
+    Ok(())
+}
+
+#[e2e::test]
+async fn honk_vk_hash_matches_vk_file(alice: Account) -> Result<()> {
+    let verifier = deploy_verifier(&alice).await?;
+    let contract = IHonkVerifier::new(verifier, &alice.wallet);
+
+    let IHonkVerifier::vkHashReturn { _0: hash } = contract.vkHash().call().await?;
+    assert_eq!(hash, vk_hash_from_file(vk_path())?);
+
+    Ok(())
+}
+
+/* ======================================================================
+ *                         as the Mixer's verifier
+ * ====================================================================== */
+#[e2e::test]
+async fn mixer_withdraws_through_honk_verifier(alice: Account) -> Result<()> {
+    let verifier = deploy_verifier(&alice).await?;
+    let imt = alice
+        .as_deployer()
+        .with_constructor(constructor!(U256::from(CIRCUIT_DEPTH)))
+        .deploy_wasm(&wasm_path("imt", "stylus_imt.wasm"))
+        .await?
+        .contract_address;
+    let mixer_addr = alice
+        .as_deployer()
+        .with_constructor(constructor!(
+            verifier,
+            imt,
+            alice.address(),
+            vk_hash_from_file(vk_path())?,
+            ULTRA_HONK_PROOF_SIZE,
+            CIRCUIT_DEPTH,
+            DENOMINATION
+        ))
+        .deploy_wasm(&wasm_path("mixer", "stylus_mixer.wasm"))
+        .await?
+        .contract_address;
+    let mixer = IMixer::new(mixer_addr, &alice.wallet);
+
+    let note = random_note();
+    receipt!(mixer.deposit(note.commitment()).value(DENOMINATION))?;
+
+    let (proof, public_inputs) = generate_proof(
+        &note,
+        alice.address(),
+        alice.wallet.get_chain_id().await?,
+        mixer_addr,
+    )?;
+    receipt!(mixer.withdraw(
+        proof.into(),
+        public_inputs[0],
+        public_inputs[1],
+        alice.address(),
+        1
+    ))?;
+
+    let IMixer::isSpentReturn { _0: spent } = mixer.isSpent(public_inputs[1]).call().await?;
+    assert!(spent);
+
+    Ok(())
+}
+
+/* ======================================================================
+ *                               INTERNAL HELPERS
+ * ====================================================================== */
+#[derive(Deserialize)]
+struct ProofResponse {
+    proof: String,
+    #[serde(rename = "publicInputs")]
+    public_inputs: Vec<String>,
+}
+
+async fn deploy_verifier(alice: &Account) -> Result<Address> {
+    let vk = std::fs::read(vk_path())?;
+    let rcpt = alice
+        .as_deployer()
+        .with_constructor(constructor!(Bytes::from(vk)))
+        .deploy()
+        .await?;
+    Ok(rcpt.contract_address)
+}
+
+fn random_note() -> Note {
+    /* only the nullifier and secret reach the circuit */
+    let deployment = Deployment::eth(U256::ZERO, 0, Address::ZERO);
+    Note::random(deployment, &mut rand::thread_rng())
+}
+
+/* proof for a fresh note that is the only leaf of its tree */
+async fn prove(alice: &Account, pool: Address) -> Result<(Vec<u8>, Vec<FixedBytes<32>>)> {
+    let chain_id = alice.wallet.get_chain_id().await?;
+    generate_proof(&random_note(), alice.address(), chain_id, pool)
+}
+
+fn generate_proof(
+    note: &Note,
+    recipient: Address,
+    chain_id: u64,
+    pool: Address,
+) -> Result<(Vec<u8>, Vec<FixedBytes<32>>)> {
+    let root = repo_root();
+    let script = root.join("scripts/js/generateProof.ts");
+    let args = [
+        "tsx".to_string(),
+        script.to_str().expect("valid script path").to_string(),
+        note.nullifier().to_string(),
+        note.secret().to_string(),
+        recipient.into_word().to_string(),
+        chain_id.to_string(),
+        pool.into_word().to_string(),
+        note.commitment().to_string(),
+    ];
+
+    let output = Command::new("npx").args(args).current_dir(&root).output()?;
+    let resp: ProofResponse = serde_json::from_slice(&output.stdout)?;
+    let proof = alloy::hex::decode(&resp.proof)?;
+    let public_inputs = resp
+        .public_inputs
+        .iter()
+        .map(|s| Ok(FixedBytes::<32>::from_slice(&alloy::hex::decode(s)?)))
+        .collect::<Result<Vec<_>>>()?;
+    Ok((proof, public_inputs))
+}
+
+fn vk_path() -> PathBuf {
+    repo_root().join("circuits/target/vk")
+}
+
+fn wasm_path(crate_dir: &str, file: &str) -> PathBuf {
+    repo_root()
+        .join("contracts")
+        .join(crate_dir)
+        .join("target/wasm32-unknown-unknown/release")
+        .join(file)
+}
+
+fn repo_root() -> PathBuf {
+    /* contracts/verifier -> project root */
+    let crate_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    crate_dir
+        .parent()
+        .and_then(|p| p.parent())
+        .unwrap()
+        .to_path_buf()
+}
@@ -0,0 +1,134 @@
+use alloy::primitives::{Bytes, FixedBytes};
+use argon2::{Algorithm, Argon2, Params, Version};
+use chacha20poly1305::{
+    aead::{Aead, KeyInit, Payload},
+    XChaCha20Poly1305, XNonce,
+};
+use rand::{rngs::OsRng, RngCore};
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
+
+use crate::WalletError;
+
+/* bumped whenever the envelope or the plaintext layout changes */
+pub(crate) const WALLET_VERSION: u32 = 1;
+
+/// Argon2id cost of turning the password into the file key, stored in the file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KdfParams {
+    pub memory_kib: u32,
+    pub iterations: u32,
+    pub parallelism: u32,
+}
+
+impl Default for KdfParams {
+    /// The argon2 crate's defaults, the OWASP minimum for Argon2id.
+    fn default() -> Self {
+        Self {
+            memory_kib: Params::DEFAULT_M_COST,
+            iterations: Params::DEFAULT_T_COST,
+            parallelism: Params::DEFAULT_P_COST,
+        }
+    }
+}
+
+impl KdfParams {
+    pub(crate) fn derive_key(
+        &self,
+        password: &str,
+        salt: &FixedBytes<16>,
+    ) -> Result<Zeroizing<[u8; 32]>, WalletError> {
+        let params = Params::new(self.memory_kib, self.iterations, self.parallelism, Some(32))?;
+        let mut key = Zeroizing::new([0u8; 32]);
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, params).hash_password_into(
+            password.as_bytes(),
+            salt.as_slice(),
+            key.as_mut_slice(),
+        )?;
+        Ok(key)
+    }
+}
+
+/* on-disk form, everything but the ciphertext is authenticated as associated data */
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Envelope {
+    pub version: u32,
+    pub kdf: KdfParams,
+    pub salt: FixedBytes<16>,
+    nonce: FixedBytes<24>,
+    ciphertext: Bytes,
+}
+
+impl Envelope {
+    /// Encrypts under a fresh nonce, every save gets its own.
+    pub fn seal(key: &[u8; 32], kdf: KdfParams, salt: FixedBytes<16>, plaintext: &[u8]) -> Self {
+        let mut nonce = FixedBytes::<24>::ZERO;
+        OsRng.fill_bytes(nonce.as_mut_slice());
+
+        let aad = associated_data(WALLET_VERSION, &kdf, &salt, &nonce);
+        /* only fails past 256 GiB of plaintext */
+        let ciphertext = cipher(key)
+            .encrypt(
+                XNonce::from_slice(nonce.as_slice()),
+                Payload {
+                    msg: plaintext,
+                    aad: &aad,
+                },
+            )
+            .expect("wallet fits one XChaCha20-Poly1305 message");
+
+        Self {
+            version: WALLET_VERSION,
+            kdf,
+            salt,
+            nonce,
+            ciphertext: ciphertext.into(),
+        }
+    }
+
+    pub fn open(&self, key: &[u8; 32]) -> Result<Zeroizing<Vec<u8>>, WalletError> {
+        if self.version != WALLET_VERSION {
+            return Err(WalletError::UnsupportedVersion(self.version));
+        }
+        let aad = associated_data(self.version, &self.kdf, &self.salt, &self.nonce);
+        let plaintext = cipher(key)
+            .decrypt(
+                XNonce::from_slice(self.nonce.as_slice()),
+                Payload {
+                    msg: &self.ciphertext,
+                    aad: &aad,
+                },
+            )
+            .map_err(|_| WalletError::WrongPassword)?;
+        Ok(Zeroizing::new(plaintext))
+    }
+}
+
+pub(crate) fn random_salt() -> FixedBytes<16> {
+    let mut salt = FixedBytes::<16>::ZERO;
+    OsRng.fill_bytes(salt.as_mut_slice());
+    salt
+}
+
+fn cipher(key: &[u8; 32]) -> XChaCha20Poly1305 {
+    XChaCha20Poly1305::new(key.into())
+}
+
+/* binds the version and kdf cost, so weakening them in the file breaks decryption */
+fn associated_data(
+    version: u32,
+    kdf: &KdfParams,
+    salt: &FixedBytes<16>,
+    nonce: &FixedBytes<24>,
+) -> Vec<u8> {
+    let mut aad = Vec::with_capacity(4 + 12 + 16 + 24);
+    aad.extend_from_slice(&version.to_be_bytes());
+    aad.extend_from_slice(&kdf.memory_kib.to_be_bytes());
+    aad.extend_from_slice(&kdf.iterations.to_be_bytes());
+    aad.extend_from_slice(&kdf.parallelism.to_be_bytes());
+    aad.extend_from_slice(salt.as_slice());
+    aad.extend_from_slice(nonce.as_slice());
+    aad
+}
@@ -0,0 +1,85 @@
+use core::fmt;
+use std::io;
+
+use alloy::{contract, primitives::FixedBytes, transports::TransportError};
+use stylus_mixer_client::NoteError;
+
+#[derive(Debug)]
+pub enum WalletError {
+    Io(io::Error),
+    Json(serde_json::Error),
+    Transport(TransportError),
+    Contract(contract::Error),
+    /// Argon2 refused the key derivation parameters.
+    Kdf(argon2::Error),
+    /// The file did not decrypt, either the password is wrong or the file was altered.
+    WrongPassword,
+    UnsupportedVersion(u32),
+    /// `create` would overwrite an existing wallet.
+    AlreadyExists,
+    Note(NoteError),
+    /// A note with this commitment is already stored.
+    DuplicateNote(FixedBytes<32>),
+    /// No stored note has this commitment.
+    UnknownNote(FixedBytes<32>),
+}
+
+impl fmt::Display for WalletError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "wallet file: {err}"),
+            Self::Json(err) => write!(f, "malformed wallet file: {err}"),
+            Self::Transport(err) => write!(f, "rpc error: {err}"),
+            Self::Contract(err) => write!(f, "contract call failed: {err}"),
+            Self::Kdf(err) => write!(f, "cannot derive the wallet key: {err}"),
+            Self::WrongPassword => write!(f, "wrong password, or the wallet file was altered"),
+            Self::UnsupportedVersion(version) => {
+                write!(f, "unsupported wallet version {version}")
+            }
+            Self::AlreadyExists => write!(f, "a wallet already exists at this path"),
+            Self::Note(err) => write!(f, "{err}"),
+            Self::DuplicateNote(commitment) => write!(f, "note {commitment} is already stored"),
+            Self::UnknownNote(commitment) => {
+                write!(f, "no stored note has commitment {commitment}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for WalletError {}
+
+impl From<io::Error> for WalletError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<serde_json::Error> for WalletError {
+    fn from(err: serde_json::Error) -> Self {
+        Self::Json(err)
+    }
+}
+
+impl From<TransportError> for WalletError {
+    fn from(err: TransportError) -> Self {
+        Self::Transport(err)
+    }
+}
+
+impl From<contract::Error> for WalletError {
+    fn from(err: contract::Error) -> Self {
+        Self::Contract(err)
+    }
+}
+
+impl From<argon2::Error> for WalletError {
+    fn from(err: argon2::Error) -> Self {
+        Self::Kdf(err)
+    }
+}
+
+impl From<NoteError> for WalletError {
+    fn from(err: NoteError) -> Self {
+        Self::Note(err)
+    }
+}
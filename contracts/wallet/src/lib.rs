@@ -0,0 +1,10 @@
+//! Password-encrypted local store for mixer notes, and what became of each one on chain.
+
+mod envelope;
+mod error;
+mod spent;
+mod wallet;
+
+pub use envelope::KdfParams;
+pub use error::WalletError;
+pub use wallet::{NoteStatus, NoteWallet, StoredNote};
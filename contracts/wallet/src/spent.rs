@@ -0,0 +1,36 @@
+use alloy::{primitives::FixedBytes, providers::Provider, transports::Transport};
+use stylus_mixer_bindings::mixer::IMixer;
+
+use crate::{NoteWallet, WalletError};
+
+impl NoteWallet {
+    /// Asks each pool whether the nullifier hash of every unspent note on the provider's chain
+    /// is in its `nullifier_hashes`, and marks the ones that are. Returns their commitments.
+    pub async fn refresh_spent<P, T>(
+        &mut self,
+        provider: &P,
+    ) -> Result<Vec<FixedBytes<32>>, WalletError>
+    where
+        P: Provider<T>,
+        T: Transport + Clone,
+    {
+        let chain_id = provider.get_chain_id().await?;
+
+        let mut spent = Vec::new();
+        for stored in &mut self.notes {
+            let deployment = stored.note().deployment();
+            if stored.is_spent() || deployment.chain_id != chain_id {
+                continue;
+            }
+            let IMixer::isSpentReturn { _0: is_spent } = IMixer::new(deployment.pool, provider)
+                .isSpent(stored.note().nullifier_hash())
+                .call()
+                .await?;
+            if is_spent {
+                stored.mark_spent();
+                spent.push(stored.commitment());
+            }
+        }
+        Ok(spent)
+    }
+}
@@ -0,0 +1,256 @@
+use core::fmt;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use alloy::primitives::{FixedBytes, B256};
+use serde::{Deserialize, Serialize};
+use stylus_mixer_client::Note;
+use zeroize::Zeroizing;
+
+use crate::{
+    envelope::{random_salt, Envelope},
+    KdfParams, WalletError,
+};
+
+/// A note and what the wallet has seen of it on chain.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoredNote {
+    #[serde(with = "portable")]
+    note: Note,
+    deposit_tx: Option<B256>,
+    leaf_index: Option<u32>,
+    spent: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NoteStatus {
+    /// No deposit is known, the note may still be unused.
+    New,
+    Deposited,
+    /// Its nullifier hash is in the pool's `nullifier_hashes`.
+    Spent,
+}
+
+impl fmt::Display for NoteStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        /* `pad`, so `mixer-cli note list` can align it */
+        f.pad(match self {
+            Self::New => "new",
+            Self::Deposited => "deposited",
+            Self::Spent => "spent",
+        })
+    }
+}
+
+impl StoredNote {
+    fn new(note: Note) -> Self {
+        Self {
+            note,
+            deposit_tx: None,
+            leaf_index: None,
+            spent: false,
+        }
+    }
+
+    pub fn note(&self) -> &Note {
+        &self.note
+    }
+
+    pub fn commitment(&self) -> FixedBytes<32> {
+        self.note.commitment()
+    }
+
+    pub fn deposit_tx(&self) -> Option<B256> {
+        self.deposit_tx
+    }
+
+    pub fn leaf_index(&self) -> Option<u32> {
+        self.leaf_index
+    }
+
+    pub fn is_spent(&self) -> bool {
+        self.spent
+    }
+
+    pub fn status(&self) -> NoteStatus {
+        if self.spent {
+            NoteStatus::Spent
+        } else if self.deposit_tx.is_some() || self.leaf_index.is_some() {
+            NoteStatus::Deposited
+        } else {
+            NoteStatus::New
+        }
+    }
+
+    pub(crate) fn mark_spent(&mut self) {
+        self.spent = true;
+    }
+}
+
+/* plaintext inside the envelope */
+#[derive(Serialize, Deserialize)]
+struct Contents {
+    notes: Vec<StoredNote>,
+}
+
+/// Notes kept in a file encrypted with XChaCha20-Poly1305, under a key Argon2id derives
+/// from the password. Changes stay in memory until `save`.
+pub struct NoteWallet {
+    path: PathBuf,
+    kdf: KdfParams,
+    salt: FixedBytes<16>,
+    key: Zeroizing<[u8; 32]>,
+    pub(crate) notes: Vec<StoredNote>,
+}
+
+impl NoteWallet {
+    /// Writes an empty wallet to `path`, refusing to replace an existing file.
+    pub fn create(path: &Path, password: &str, kdf: KdfParams) -> Result<Self, WalletError> {
+        if path.exists() {
+            return Err(WalletError::AlreadyExists);
+        }
+        let salt = random_salt();
+        let wallet = Self {
+            path: path.to_path_buf(),
+            kdf,
+            salt,
+            key: kdf.derive_key(password, &salt)?,
+            notes: Vec::new(),
+        };
+        wallet.save()?;
+        Ok(wallet)
+    }
+
+    pub fn open(path: &Path, password: &str) -> Result<Self, WalletError> {
+        let envelope: Envelope = serde_json::from_str(&fs::read_to_string(path)?)?;
+        let key = envelope.kdf.derive_key(password, &envelope.salt)?;
+        let plaintext = envelope.open(&key)?;
+        let contents: Contents = serde_json::from_slice(&plaintext)?;
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            kdf: envelope.kdf,
+            salt: envelope.salt,
+            key,
+            notes: contents.notes,
+        })
+    }
+
+    /// Re-encrypts every note under a fresh nonce and replaces the file in one rename.
+    pub fn save(&self) -> Result<(), WalletError> {
+        let contents = Contents {
+            notes: self.notes.clone(),
+        };
+        let plaintext = Zeroizing::new(serde_json::to_vec(&contents)?);
+        let envelope = Envelope::seal(&self.key, self.kdf, self.salt, &plaintext);
+
+        let tmp = self.path.with_extension("tmp");
+        fs::write(&tmp, serde_json::to_string_pretty(&envelope)?)?;
+        fs::rename(&tmp, &self.path)?;
+        Ok(())
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn notes(&self) -> &[StoredNote] {
+        &self.notes
+    }
+
+    pub fn get(&self, commitment: FixedBytes<32>) -> Option<&StoredNote> {
+        self.notes
+            .iter()
+            .find(|stored| stored.commitment() == commitment)
+    }
+
+    pub fn add(&mut self, note: Note) -> Result<&StoredNote, WalletError> {
+        let commitment = note.commitment();
+        if self.get(commitment).is_some() {
+            return Err(WalletError::DuplicateNote(commitment));
+        }
+        self.notes.push(StoredNote::new(note));
+        Ok(self.notes.last().unwrap())
+    }
+
+    /// Remembers the transaction that inserted the note and the leaf it got.
+    pub fn record_deposit(
+        &mut self,
+        commitment: FixedBytes<32>,
+        tx_hash: B256,
+        leaf_index: u32,
+    ) -> Result<(), WalletError> {
+        let stored = self
+            .notes
+            .iter_mut()
+            .find(|stored| stored.commitment() == commitment)
+            .ok_or(WalletError::UnknownNote(commitment))?;
+        stored.deposit_tx = Some(tx_hash);
+        stored.leaf_index = Some(leaf_index);
+        Ok(())
+    }
+
+    /// Marks the note behind `nullifier_hash` spent, returns whether the wallet holds one.
+    pub fn mark_spent(&mut self, nullifier_hash: FixedBytes<32>) -> bool {
+        match self
+            .notes
+            .iter_mut()
+            .find(|stored| stored.note.nullifier_hash() == nullifier_hash)
+        {
+            Some(stored) => {
+                stored.mark_spent();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Adds every note in `notes`, one portable note string per line. Blank lines are skipped
+    /// and notes the wallet already holds are left alone. Nothing is added unless every line
+    /// parses. Returns the commitments of the added notes.
+    pub fn import(&mut self, notes: &str) -> Result<Vec<FixedBytes<32>>, WalletError> {
+        let parsed = notes
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(str::parse::<Note>)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut added = Vec::new();
+        for note in parsed {
+            let commitment = note.commitment();
+            if self.get(commitment).is_none() {
+                self.notes.push(StoredNote::new(note));
+                added.push(commitment);
+            }
+        }
+        Ok(added)
+    }
+
+    /// Every note as a portable note string, one per line, in the form `import` reads.
+    pub fn export(&self) -> String {
+        self.notes
+            .iter()
+            .map(|stored| format!("{}\n", stored.note))
+            .collect()
+    }
+}
+
+/* notes are stored as their portable string, so the file survives changes to `Note` */
+mod portable {
+    use serde::{de, Deserialize, Deserializer, Serializer};
+    use stylus_mixer_client::Note;
+
+    pub fn serialize<S: Serializer>(note: &Note, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(note)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Note, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(de::Error::custom)
+    }
+}
@@ -0,0 +1,176 @@
+use std::{fs, path::PathBuf};
+
+use alloy::primitives::{address, b256, uint};
+use rand::{rngs::StdRng, SeedableRng};
+use stylus_mixer_client::{Deployment, Note, NoteError};
+use stylus_mixer_wallet::{KdfParams, NoteStatus, NoteWallet, WalletError};
+
+const PASSWORD: &str = "correct horse battery staple";
+
+/* argon2's minimum cost, the default one is slow in debug builds */
+const FAST_KDF: KdfParams = KdfParams {
+    memory_kib: 8,
+    iterations: 1,
+    parallelism: 1,
+};
+
+fn scratch(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("mixer-wallet-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join(name);
+    let _ = fs::remove_file(&path);
+    path
+}
+
+fn notes(count: u64) -> Vec<Note> {
+    let deployment = Deployment::eth(
+        uint!(1_000_000_000_000_000_000_U256),
+        412346,
+        address!("5FbDB2315678afecb367f032d93F642f64180aa3"),
+    );
+    let mut rng = StdRng::seed_from_u64(count);
+    (0..count)
+        .map(|_| Note::random(deployment.clone(), &mut rng))
+        .collect()
+}
+
+#[test]
+fn notes_and_their_history_survive_a_reopen() {
+    let path = scratch("reopen.json");
+    let [first, second] = <[Note; 2]>::try_from(notes(2)).unwrap();
+
+    let mut wallet = NoteWallet::create(&path, PASSWORD, FAST_KDF).unwrap();
+    wallet.add(first.clone()).unwrap();
+    wallet.add(second.clone()).unwrap();
+    let tx = b256!("1111111111111111111111111111111111111111111111111111111111111111");
+    wallet.record_deposit(first.commitment(), tx, 7).unwrap();
+    assert!(wallet.mark_spent(first.nullifier_hash()));
+    wallet.save().unwrap();
+
+    let wallet = NoteWallet::open(&path, PASSWORD).unwrap();
+    assert_eq!(wallet.notes().len(), 2);
+    let stored = wallet.get(first.commitment()).unwrap();
+    assert_eq!(stored.note(), &first);
+    assert_eq!(stored.deposit_tx(), Some(tx));
+    assert_eq!(stored.leaf_index(), Some(7));
+    assert_eq!(stored.status(), NoteStatus::Spent);
+    assert_eq!(
+        wallet.get(second.commitment()).unwrap().status(),
+        NoteStatus::New
+    );
+}
+
+#[test]
+fn file_does_not_reveal_notes() {
+    let path = scratch("opaque.json");
+    let note = notes(1).remove(0);
+
+    let mut wallet = NoteWallet::create(&path, PASSWORD, FAST_KDF).unwrap();
+    wallet.add(note.clone()).unwrap();
+    wallet.save().unwrap();
+
+    let file = fs::read_to_string(&path).unwrap();
+    assert!(!file.contains("stylusmixer"));
+    assert!(!file.contains(&alloy::hex::encode(note.secret())));
+}
+
+#[test]
+fn wrong_password_is_refused() {
+    let path = scratch("password.json");
+    NoteWallet::create(&path, PASSWORD, FAST_KDF).unwrap();
+
+    assert!(matches!(
+        NoteWallet::open(&path, "hunter2"),
+        Err(WalletError::WrongPassword)
+    ));
+}
+
+#[test]
+fn altered_file_is_refused() {
+    let path = scratch("altered.json");
+    let mut wallet = NoteWallet::create(&path, PASSWORD, FAST_KDF).unwrap();
+    wallet.add(notes(1).remove(0)).unwrap();
+    wallet.save().unwrap();
+    let original: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+
+    let mut flipped = original.clone();
+    let ciphertext = flipped["ciphertext"].as_str().unwrap();
+    let last = if ciphertext.ends_with('0') { "1" } else { "0" };
+    flipped["ciphertext"] = format!("{}{last}", &ciphertext[..ciphertext.len() - 1]).into();
+    fs::write(&path, flipped.to_string()).unwrap();
+    assert!(matches!(
+        NoteWallet::open(&path, PASSWORD),
+        Err(WalletError::WrongPassword)
+    ));
+
+    let mut unsupported = original;
+    unsupported["version"] = 2.into();
+    fs::write(&path, unsupported.to_string()).unwrap();
+    assert!(matches!(
+        NoteWallet::open(&path, PASSWORD),
+        Err(WalletError::UnsupportedVersion(2))
+    ));
+}
+
+#[test]
+fn create_does_not_replace_a_wallet() {
+    let path = scratch("twice.json");
+    NoteWallet::create(&path, PASSWORD, FAST_KDF).unwrap();
+
+    assert!(matches!(
+        NoteWallet::create(&path, "other", FAST_KDF),
+        Err(WalletError::AlreadyExists)
+    ));
+    NoteWallet::open(&path, PASSWORD).unwrap();
+}
+
+#[test]
+fn notes_are_stored_once() {
+    let path = scratch("duplicate.json");
+    let note = notes(1).remove(0);
+    let mut wallet = NoteWallet::create(&path, PASSWORD, FAST_KDF).unwrap();
+
+    wallet.add(note.clone()).unwrap();
+    assert!(matches!(
+        wallet.add(note.clone()),
+        Err(WalletError::DuplicateNote(commitment)) if commitment == note.commitment()
+    ));
+    assert!(matches!(
+        wallet.record_deposit(notes(2)[1].commitment(), Default::default(), 0),
+        Err(WalletError::UnknownNote(_))
+    ));
+    assert!(!wallet.mark_spent(notes(2)[1].nullifier_hash()));
+}
+
+#[test]
+fn export_imports_into_another_wallet() {
+    let notes = notes(3);
+    let mut source = NoteWallet::create(&scratch("export.json"), PASSWORD, FAST_KDF).unwrap();
+    for note in &notes {
+        source.add(note.clone()).unwrap();
+    }
+    let exported = source.export();
+    assert_eq!(exported.lines().count(), 3);
+
+    let mut target = NoteWallet::create(&scratch("import.json"), "other", FAST_KDF).unwrap();
+    target.add(notes[1].clone()).unwrap();
+    let added = target.import(&format!("\n{exported}\n")).unwrap();
+
+    assert_eq!(added, [notes[0].commitment(), notes[2].commitment()]);
+    for note in &notes {
+        assert_eq!(target.get(note.commitment()).unwrap().note(), note);
+    }
+}
+
+#[test]
+fn import_is_all_or_nothing() {
+    let mut wallet = NoteWallet::create(&scratch("partial.json"), PASSWORD, FAST_KDF).unwrap();
+    let text = format!("{}\nstylusmixer-v1-eth-1\n", notes(1)[0]);
+
+    assert!(matches!(
+        wallet.import(&text),
+        Err(WalletError::Note(NoteError::InvalidFormat))
+    ));
+    assert!(wallet.notes().is_empty());
+}
@@ -0,0 +1,57 @@
+#!/usr/bin/env bash
+set -euo pipefail
+
+# builds the withdraw circuit once per supported tree depth, then the default and aggregate ones:
+#   circuits/target/depth_<d>/{circuits.json,vk}
+#   contracts/mixer/src/verifiers/depth_<d>/{Verifier.sol,PinnedVerifier.sol}
+#   circuits/target/{circuits.json,vk}                 -> contracts/mixer/src/
+#   circuits/aggregate/target/{circuits_aggregate.json,vk} -> contracts/mixer/src/aggregate/
+# keep DEPTHS in sync with stylus_common::circuits::SUPPORTED_DEPTHS
+
+ROOT_DIR="$(cd "$(dirname "$0")/.." && pwd)"
+DEPTHS=(10 15 20 31)
+PINNED_TEMPLATE="${ROOT_DIR}/contracts/mixer/src/PinnedVerifier.sol.in"
+
+WORK_DIR="$(mktemp -d)"
+trap 'rm -rf "${WORK_DIR}"' EXIT
+
+# writes Verifier.sol for a vk into a directory, and PinnedVerifier.sol with the vk's
+# keccak256 baked in, the value stylus_common::vk::vk_hash_from_file computes
+pin_verifier() {
+  local vk="$1"
+  local verifier_dir="$2"
+
+  mkdir -p "${verifier_dir}"
+  bb write_solidity_verifier -k "${vk}" -o "${verifier_dir}/Verifier.sol"
+  local vk_hash
+  vk_hash="$(cast keccak "0x$(od -An -v -tx1 "${vk}" | tr -d ' \n')")"
+  sed "s/@VK_HASH@/${vk_hash}/" "${PINNED_TEMPLATE}" > "${verifier_dir}/PinnedVerifier.sol"
+}
+
+for depth in "${DEPTHS[@]}"; do
+  echo "==> circuits: depth ${depth}"
+  build_dir="${WORK_DIR}/depth_${depth}"
+  out_dir="${ROOT_DIR}/circuits/target/depth_${depth}"
+
+  mkdir -p "${build_dir}" "${out_dir}"
+  cp -r "${ROOT_DIR}/circuits/Nargo.toml" "${ROOT_DIR}/circuits/src" "${build_dir}/"
+  sed -i.bak -E "s/^global DEPTH: u32 = [0-9]+;/global DEPTH: u32 = ${depth};/" "${build_dir}/src/main.nr"
+
+  (cd "${build_dir}" && nargo compile)
+  cp "${build_dir}/target/circuits.json" "${out_dir}/circuits.json"
+
+  bb write_vk --oracle_hash keccak -b "${out_dir}/circuits.json" -o "${out_dir}"
+  pin_verifier "${out_dir}/vk" "${ROOT_DIR}/contracts/mixer/src/verifiers/depth_${depth}"
+done
+
+# the artifact generateProof.ts uses without CIRCUIT_DEPTH
+echo "==> circuits: default"
+(cd "${ROOT_DIR}/circuits" && nargo compile)
+bb write_vk --oracle_hash keccak -b "${ROOT_DIR}/circuits/target/circuits.json" -o "${ROOT_DIR}/circuits/target"
+pin_verifier "${ROOT_DIR}/circuits/target/vk" "${ROOT_DIR}/contracts/mixer/src"
+
+echo "==> circuits: aggregate"
+aggregate_dir="${ROOT_DIR}/circuits/aggregate"
+(cd "${aggregate_dir}" && nargo compile)
+bb write_vk --oracle_hash keccak -b "${aggregate_dir}/target/circuits_aggregate.json" -o "${aggregate_dir}/target"
+pin_verifier "${aggregate_dir}/target/vk" "${ROOT_DIR}/contracts/mixer/src/aggregate"
@@ -2,7 +2,13 @@
 set -euo pipefail
 
 ROOT_DIR="$(cd "$(dirname "$0")/.." && pwd)"
-CRATES=(poseidon imt mixer)
+CRATES=(poseidon imt groth16 mixer verifier)
+# host-only crates, no stylus check and no e2e feature
+HOST_CRATES=(common client bindings wallet indexer)
+# host binaries whose tests need the dev node
+NODE_CRATES=(cli relayer deploy)
+# hashers behind a non-default feature, as crate:feature
+HASHER_RUNS=(imt:keccak imt:poseidon2-domain poseidon:poseidon-v1)
 
 # defaults for e2e tests if not already set
 export RPC_URL="${RPC_URL:-http://localhost:8547}"
@@ -37,23 +43,71 @@ if [ "$RUN_CHECKS" = true ]; then
     (cd "${ROOT_DIR}/contracts/${crate}" && cargo stylus check) || \
       echo "[warn] stylus check failed for ${crate} (continuing)"
   done
+
+  echo "==> contracts/bindings: exported ABI up to date"
+  if ! bash "${ROOT_DIR}/scripts/export_abi.sh" --check; then
+    echo "[error] stale ABI in contracts/bindings/abi" >&2
+    exit 1
+  fi
 fi
 
 
 # run e2e tests per crate, per test file (ignore failures)
 if [ "$RUN_TESTS" = true ]; then
+  # circuits/target is gitignored, the tests that read it need a fresh build
+  echo "==> circuits: scripts/build_circuits.sh"
+  if ! bash "${ROOT_DIR}/scripts/build_circuits.sh"; then
+    echo "[error] circuit build failed" >&2
+    exit 1
+  fi
+
   for crate in "${CRATES[@]}"; do
-    echo "==> contracts/${crate}: cargo test --features e2e (per test file)"
+    # the imt's off-chain tree tests need its host-side module
+    features="e2e"
+    [ "$crate" = "imt" ] && features="e2e,std"
+    echo "==> contracts/${crate}: cargo test --features ${features} (per test file)"
     (
       cd "${ROOT_DIR}/contracts/${crate}" || exit 1
       shopt -s nullglob
       for tf in tests/*.rs; do
         test_name="$(basename "${tf%.rs}")"
-        echo "---- running: cargo test --features e2e --test ${test_name}"
-        cargo test --features e2e --test "${test_name}" || \
+        echo "---- running: cargo test --features ${features} --test ${test_name}"
+        cargo test --features "${features}" --test "${test_name}" || \
           echo "[warn] test ${crate}/${test_name} failed (continuing)"
       done
     )
   done
+
+  for crate in "${HOST_CRATES[@]}"; do
+    echo "==> contracts/${crate}: cargo test"
+    (cd "${ROOT_DIR}/contracts/${crate}" && cargo test) || \
+      echo "[warn] tests for ${crate} failed (continuing)"
+  done
+
+  for crate in "${NODE_CRATES[@]}"; do
+    echo "==> contracts/${crate}: cargo test --features e2e"
+    (cd "${ROOT_DIR}/contracts/${crate}" && cargo test --features e2e) || \
+      echo "[warn] tests for ${crate} failed (continuing)"
+  done
+
+  # e2e deploys the crate's release wasm, so it is rebuilt with the hasher under test
+  for run in "${HASHER_RUNS[@]}"; do
+    crate="${run%%:*}"
+    hasher="${run#*:}"
+    echo "==> contracts/${crate}: cargo test --no-default-features --features e2e,${hasher}"
+    (
+      cd "${ROOT_DIR}/contracts/${crate}" || exit 1
+      cargo build --release --lib --target wasm32-unknown-unknown \
+        --no-default-features --features "contract,${hasher}" && \
+        cargo test --no-default-features --features "e2e,${hasher}"
+    ) || echo "[warn] ${hasher} tests for ${crate} failed (continuing)"
+  done
+
+  # leave the default builds behind for the mixer and anything run after this script
+  for crate in imt poseidon; do
+    (cd "${ROOT_DIR}/contracts/${crate}" && \
+      cargo build --release --lib --target wasm32-unknown-unknown) || \
+      echo "[warn] default wasm build failed for ${crate} (continuing)"
+  done
 fi
 
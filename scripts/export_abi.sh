@@ -0,0 +1,51 @@
+#!/usr/bin/env bash
+set -euo pipefail
+
+# writes `cargo stylus export-abi` of each contract to contracts/bindings/abi/<interface>.sol,
+# which stylus-mixer-bindings compiles into the alloy types used by tests and tools.
+# --check leaves the files alone and fails if any of them is stale.
+
+ROOT_DIR="$(cd "$(dirname "$0")/.." && pwd)"
+ABI_DIR="${ROOT_DIR}/contracts/bindings/abi"
+# <crate>:<interface name export-abi gives the entrypoint>
+CONTRACTS=(mixer:IMixer imt:IIMT poseidon:IPoseidon groth16:IGroth16Verifier verifier:IHonkVerifier)
+
+CHECK=false
+for arg in "$@"; do
+  case "$arg" in
+    --check)
+      CHECK=true
+      ;;
+    -h|--help)
+      echo "usage: $0 [--check]"
+      exit 0
+      ;;
+    *)
+      echo "unknown option: $arg" >&2
+      echo "usage: $0 [--check]" >&2
+      exit 1
+      ;;
+  esac
+done
+
+WORK_DIR="$(mktemp -d)"
+trap 'rm -rf "${WORK_DIR}"' EXIT
+
+stale=0
+for entry in "${CONTRACTS[@]}"; do
+  crate="${entry%%:*}"
+  interface="${entry#*:}"
+  echo "==> contracts/${crate}: cargo stylus export-abi"
+  (cd "${ROOT_DIR}/contracts/${crate}" && cargo stylus export-abi) > "${WORK_DIR}/${interface}.sol"
+
+  if [ "$CHECK" = true ]; then
+    if ! diff -u "${ABI_DIR}/${interface}.sol" "${WORK_DIR}/${interface}.sol"; then
+      echo "[error] contracts/bindings/abi/${interface}.sol is stale, run scripts/export_abi.sh" >&2
+      stale=1
+    fi
+  else
+    cp "${WORK_DIR}/${interface}.sol" "${ABI_DIR}/${interface}.sol"
+  fi
+done
+
+exit "${stale}"
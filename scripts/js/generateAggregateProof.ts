@@ -0,0 +1,131 @@
+import {
+  Barretenberg,
+  Fr,
+  UltraHonkBackend,
+  deflattenFields,
+} from "@aztec/bb.js";
+import { ethers } from "ethers";
+import { merkleTree } from "./merkleTree.js";
+import { Noir } from "@noir-lang/noir_js";
+import path from "path";
+import fs from "fs";
+
+const readCircuit = (dir: string, name: string) =>
+  JSON.parse(
+    fs.readFileSync(
+      path.resolve(__dirname, `../../circuits/${dir}/${name}.json`),
+      "utf8"
+    )
+  );
+
+const circuit = readCircuit("target", "circuits");
+const aggregateCircuit = readCircuit("aggregate/target", "circuits_aggregate");
+
+/*
+ * usage: generateAggregateProof <chain_id> <pool> <count> [<nullifier> <secret> <recipient>]*count <leaves...>
+ * every note is proven with the poseidon2 transcript so it can be verified in circuits/aggregate,
+ * the aggregated proof uses the keccak transcript for the on-chain verifier
+ */
+export default async function generateAggregateProof() {
+  const bb = await Barretenberg.new();
+
+  const inputs = process.argv.slice(2);
+
+  const chainId = inputs[0];
+  const pool = inputs[1];
+  const count = Number(inputs[2]);
+  const notes = inputs.slice(3, 3 + count * 3);
+  const leaves = inputs.slice(3 + count * 3);
+
+  const tree = await merkleTree(leaves);
+
+  try {
+    const noir = new Noir(circuit);
+    const honk = new UltraHonkBackend(
+      circuit.bytecode,
+      { threads: 1 },
+      { recursive: true }
+    );
+
+    const proofs: string[][] = [];
+    const publicInputs: string[][] = [];
+    for (let i = 0; i < count; i++) {
+      const nullifier = Fr.fromString(notes[i * 3]);
+      const secret = Fr.fromString(notes[i * 3 + 1]);
+      const nullifierHash = await bb.poseidon2Hash([nullifier]);
+      const commitment = await bb.poseidon2Hash([nullifier, secret]);
+      const merkleProof = tree.proof(tree.getIndex(commitment.toString()));
+
+      const { witness } = await noir.execute({
+        root: merkleProof.root,
+        nullifier_hash: nullifierHash.toString(),
+        recipient: notes[i * 3 + 2],
+        chain_id: chainId,
+        pool: pool,
+        nullifier: nullifier.toString(),
+        secret: secret.toString(),
+        merkle_proof: merkleProof.pathElements.map((i) => i.toString()),
+        is_even: merkleProof.pathIndices.map((i) => i % 2 == 0),
+      });
+
+      const proof = await honk.generateProof(witness);
+      proofs.push(deflattenFields(proof.proof));
+      publicInputs.push(proof.publicInputs);
+    }
+
+    const { vkAsFields } = await honk.generateRecursiveProofArtifacts(
+      new Uint8Array(),
+      publicInputs[0].length
+    );
+    const keyHash = await bb.poseidon2Hash(
+      vkAsFields.map((value: string) => Fr.fromString(value))
+    );
+
+    const aggregateNoir = new Noir(aggregateCircuit);
+    const aggregateHonk = new UltraHonkBackend(aggregateCircuit.bytecode, {
+      threads: 1,
+    });
+    const { witness } = await aggregateNoir.execute({
+      key_hash: keyHash.toString(),
+      public_inputs: publicInputs,
+      verification_key: vkAsFields,
+      proofs: proofs,
+    });
+
+    const { proof, publicInputs: aggregatePublicInputs } =
+      await aggregateHonk.generateProof(witness, { keccak: true });
+
+    const toHexString = (value: string) => {
+      try {
+        return ethers.hexlify(Fr.fromString(value).toBuffer());
+      } catch {
+        return ethers.hexlify(ethers.getBytes(value));
+      }
+    };
+
+    const result = {
+      proof: ethers.hexlify(proof),
+      publicInputs: aggregatePublicInputs.map((value: string) =>
+        toHexString(value)
+      ),
+      keyHash: toHexString(keyHash.toString()),
+    };
+
+    return JSON.stringify(result);
+  } catch (error) {
+    console.log(error);
+    throw error;
+  }
+}
+
+(async () => {
+  generateAggregateProof()
+    .then((result) => {
+      process.stdout.write(result);
+      process.exit(0);
+    })
+    .catch((error) => {
+      console.error(error);
+      process.exit(1);
+    });
+})();
@@ -5,11 +5,14 @@ import { Noir } from "@noir-lang/noir_js";
 import path from "path";
 import fs from "fs";
 
+/* CIRCUIT_DEPTH picks an artifact from scripts/build_circuits.sh, the default build is depth 15 */
+const depth = Number(process.env.CIRCUIT_DEPTH ?? 15);
+const artifact = process.env.CIRCUIT_DEPTH
+  ? `../../circuits/target/depth_${depth}/circuits.json`
+  : "../../circuits/target/circuits.json";
+
 const circuit = JSON.parse(
-  fs.readFileSync(
-    path.resolve(__dirname, "../../circuits/target/circuits.json"),
-    "utf8"
-  )
+  fs.readFileSync(path.resolve(__dirname, artifact), "utf8")
 );
 
 export default async function generateProof() {
@@ -20,9 +23,9 @@ export default async function generateProof() {
   const nullifier = Fr.fromString(inputs[0]);
   const secret = Fr.fromString(inputs[1]);
   const nullifierHash = await bb.poseidon2Hash([nullifier]);
-  const leaves = inputs.slice(3);
+  const leaves = inputs.slice(5);
 
-  const tree = await merkleTree(leaves);
+  const tree = await merkleTree(leaves, depth);
   const commitment = await bb.poseidon2Hash([nullifier, secret]);
   const merkleProof = tree.proof(tree.getIndex(commitment.toString()));
 
@@ -33,6 +36,8 @@ export default async function generateProof() {
       root: merkleProof.root,
       nullifier_hash: nullifierHash.toString(),
       recipient: inputs[2],
+      chain_id: inputs[3],
+      pool: inputs[4],
       nullifier: nullifier.toString(),
       secret: secret.toString(),
       merkle_proof: merkleProof.pathElements.map((i) => i.toString()),
@@ -53,6 +58,7 @@ export default async function generateProof() {
     };
 
     const proofHex = ethers.hexlify(proof);
+    /* bb returns them in circuit ABI order, see WithdrawPublicInputs in stylus-common */
     const publicInputsHex = publicInputs.map((value: string) =>
       toHexString(value)
     );
@@ -168,11 +168,26 @@ const ZERO_VALUES = [
   "0x06fa06667c34201bcd5f6334de6b8c0b22b5f6bc57e401ed7660c40afd880b26",
   "0x26ec3289eb146620b56807d58b3fae45adb7d7dfdc0a65194333e6dc2aa3de9e",
   "0x2d2f60a05d456896411242de0eff23497c889f762e2eb5db0a07df329f452a92",
+  "0x1ee903a4eac57310c624c0e30f2bd083eb68a595306df83b1111db0fffce45ea",
+  "0x05f96e491710c7e1d65207b36e0031c1de403eb32753de2489e8abce4c2e86ff",
+  "0x2375b170da8f212cf2b23538990cb6a2e319c50eee555a3fcbed25946326be6c",
+  "0x14307dca3f2b6224ff19c5c0a19129c5fa79d48c645ebb1c5302cb41a131e72a",
+  "0x051e91aeea86b05dcd2b5218126fb3cf3990c81d53f0947028a933026eb94b3a",
+  "0x089fcba3da069d909de7d9ea88e8c7bb49d5934947e53a6a9c1f5eb662c27f2e",
+  "0x1f36ef937da9689c0f70364036741031e0f2b87eef22c735019371d4396c6b3f",
+  "0x21ad2f97aebd9fcd4c41433e5db8d4f64c863360ddecc343cd500b62180134ee",
+  "0x0acf88c5a38c8f9279a313844e7af8026eeeaceeabad6b582d9f3fb123b62d70",
+  "0x1175dd405c3e38670903ef572cfa3ccf441a7cf70c88f8fd8be1b46f33915970",
+  "0x1881fadef7baed7dab5ec3999ade81286cd070fdf234fc1b451e34a3bc4231c6",
+  "0x2219be343fd079048377a2560467d13407dd0e99dc810aa82aa014c617dd1b9e",
+  "0x20311205434cb48873977f5a36fc509939eae9e64cf66395df8ea03264c7798b",
+  "0x29084b176141114483fc6c6eb4444a01c644361a2c004a7efd2e252d8cce4e70",
+  "0x0bad0880fc04d5a9b2fccef498448214d50b92a973390a93ca7533ff02fc7721",
+  "0x13b6403089d691e83af7392d8e9bddd76e83d8204b2475fc6c60679bd338dea8",
 ];
 
-export async function merkleTree(leaves) {
-  const TREE_HEIGHT = 15;
-  const tree = new PoseidonTree(TREE_HEIGHT, ZERO_VALUES);
+export async function merkleTree(leaves, height = 15) {
+  const tree = new PoseidonTree(height, ZERO_VALUES);
 
   // Initialize tree with no leaves (all zeros)
   await tree.init();